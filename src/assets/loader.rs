@@ -0,0 +1,116 @@
+use std::collections::{HashMap};
+use std::sync::{Arc};
+use std::error::Error;
+use std::fmt;
+
+use image::{self, RgbaImage, ImageError};
+
+use assets::{AssetManifest};
+use components::{BlockRegistry};
+use core::{ConfigErr};
+
+// Decodes images named in the manifest on first use and hands out shared copies after that
+pub struct AssetLoader {
+    manifest: AssetManifest,
+    images: HashMap<String, Arc<RgbaImage>>,
+}
+
+impl AssetLoader {
+    pub fn new(manifest: AssetManifest) -> AssetLoader {
+        AssetLoader {
+            manifest: manifest,
+            images: HashMap::new(),
+        }
+    }
+
+    pub fn load(manifest_path: &str) -> Result<AssetLoader, AssetErr> {
+        match AssetManifest::load(manifest_path) {
+            Ok(manifest) => Ok(AssetLoader::new(manifest)),
+            Err(err) => Err(AssetErr::Manifest(manifest_path.to_string(), err)),
+        }
+    }
+
+    pub fn get_manifest(&self) -> &AssetManifest {
+        &self.manifest
+    }
+
+    pub fn is_cached(&self, name: &str) -> bool {
+        self.images.contains_key(name)
+    }
+
+    pub fn load_image(&mut self, name: &str) -> Result<Arc<RgbaImage>, AssetErr> {
+        if let Some(image) = self.images.get(name) {
+            return Ok(image.clone());
+        }
+        let path = match self.manifest.get_path(name) {
+            Some(path) => path,
+            None => return Err(AssetErr::Unknown(name.to_string())),
+        };
+        let path_name = path.to_string_lossy().into_owned();
+        if !path.is_file() {
+            return Err(AssetErr::Missing(name.to_string(), path_name));
+        }
+        let image = match image::open(&path) {
+            Ok(image) => Arc::new(image.to_rgba()),
+            Err(err) => return Err(AssetErr::Corrupt(name.to_string(), path_name, err)),
+        };
+        self.images.insert(name.to_string(), image.clone());
+        Ok(image)
+    }
+
+    // Decodes every image in the manifest and checks each block texture is listed in it,
+    // returning all the problems found instead of stopping at the first
+    pub fn check(&mut self, block_registry: &BlockRegistry) -> Vec<AssetErr> {
+        let mut errs = vec!();
+        let names: Vec<String> = self.manifest.get_images().iter().map(|entry| entry.get_name().to_string()).collect();
+        for name in names {
+            match self.load_image(&name) {
+                Ok(_) => (),
+                Err(err) => errs.push(err),
+            }
+        }
+        for block_type in block_registry.get_block_types() {
+            match block_registry.get(block_type) {
+                Some(block_def) => match block_def.get_texture() {
+                    Some(texture) if self.manifest.get_image(texture).is_none() => errs.push(AssetErr::UnknownTexture(block_def.get_name().to_string(), texture.to_string())),
+                    _ => (),
+                },
+                None => (),
+            }
+        }
+        errs
+    }
+}
+
+#[derive(Debug)]
+pub enum AssetErr {
+    Manifest(String, ConfigErr),
+    Unknown(String),
+    UnknownTexture(String, String),
+    Missing(String, String),
+    Corrupt(String, String, ImageError),
+}
+
+impl fmt::Display for AssetErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AssetErr::Manifest(ref path, ref err) => write!(f, "{}: {}", path, err),
+            AssetErr::Unknown(ref name) => write!(f, "{} is not in the asset manifest", name),
+            AssetErr::UnknownTexture(ref block, ref name) => write!(f, "Block [{}] uses texture {} which is not in the asset manifest", block, name),
+            AssetErr::Missing(ref name, ref path) => write!(f, "{}: {} does not exist", name, path),
+            AssetErr::Corrupt(ref name, ref path, ref err) => write!(f, "{}: {} could not be decoded: {}", name, path, err),
+        }
+    }
+}
+
+impl Error for AssetErr {
+    fn description(&self) -> &str {
+        match *self {
+            AssetErr::Manifest(_, ref err) => err.description(),
+            AssetErr::Unknown(_) => "Asset not in manifest",
+            AssetErr::UnknownTexture(_, _) => "Block texture not in manifest",
+            AssetErr::Missing(_, _) => "Asset file does not exist",
+            AssetErr::Corrupt(_, _, ref err) => err.description(),
+        }
+    }
+}
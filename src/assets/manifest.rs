@@ -0,0 +1,89 @@
+use std::path::{Path, PathBuf};
+
+use core::{Config, ConfigErr};
+
+pub static MANIFEST_PATH: &'static str = "assets/manifest.cfg";
+
+// Logical asset names such as block.brick mapped to files relative to the manifest
+#[derive(Clone, Debug)]
+pub struct AssetManifest {
+    root: PathBuf,
+    images: Vec<AssetEntry>,
+}
+
+#[derive(Clone, Debug)]
+pub struct AssetEntry {
+    name: String,
+    file: String,
+    line: usize,
+}
+
+impl AssetManifest {
+    pub fn load(path: &str) -> Result<AssetManifest, ConfigErr> {
+        let root = match Path::new(path).parent() {
+            Some(root) => root.to_path_buf(),
+            None => PathBuf::new(),
+        };
+        match Config::load(path) {
+            Ok(config) => AssetManifest::from_config(&config, root),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn parse(text: &str, root: PathBuf) -> Result<AssetManifest, ConfigErr> {
+        match Config::parse(text) {
+            Ok(config) => AssetManifest::from_config(&config, root),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn from_config(config: &Config, root: PathBuf) -> Result<AssetManifest, ConfigErr> {
+        let mut images = vec!();
+        for section in config.get_sections() {
+            match section.get_name() {
+                "images" => for entry in section.get_entries() {
+                    images.push(AssetEntry {
+                        name: entry.get_key().to_string(),
+                        file: entry.get_value().to_string(),
+                        line: entry.get_line(),
+                    });
+                },
+                _ => return Err(ConfigErr::Syntax(section.get_line(), "Unknown asset section, expected [images]")),
+            }
+        }
+        Ok(AssetManifest {
+            root: root,
+            images: images,
+        })
+    }
+
+    pub fn get_root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn get_images(&self) -> &Vec<AssetEntry> {
+        &self.images
+    }
+
+    pub fn get_image(&self, name: &str) -> Option<&AssetEntry> {
+        self.images.iter().find(|entry| entry.name == name)
+    }
+
+    pub fn get_path(&self, name: &str) -> Option<PathBuf> {
+        self.get_image(name).map(|entry| self.root.join(&entry.file))
+    }
+}
+
+impl AssetEntry {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_file(&self) -> &str {
+        &self.file
+    }
+
+    pub fn get_line(&self) -> usize {
+        self.line
+    }
+}
@@ -0,0 +1,5 @@
+pub mod manifest;
+pub mod loader;
+
+pub use self::manifest::{AssetManifest, AssetEntry, MANIFEST_PATH};
+pub use self::loader::{AssetLoader, AssetErr};
@@ -0,0 +1,48 @@
+// Energy an action costs, and what a normal speed actor gains each turn
+pub const ACTION_COST: i32 = 100;
+pub const NORMAL_SPEED: i32 = 100;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Actor {
+    speed: i32,
+    energy: i32,
+}
+
+impl Actor {
+    pub fn new(speed: i32) -> Actor {
+        Actor {
+            speed: speed.max(1),
+            energy: 0,
+        }
+    }
+
+    // Picks up where a saved actor left off
+    pub fn with_energy(mut self, energy: i32) -> Actor {
+        self.energy = energy;
+        self
+    }
+
+    pub fn get_speed(&self) -> i32 {
+        self.speed
+    }
+
+    pub fn set_speed(&mut self, speed: i32) {
+        self.speed = speed.max(1);
+    }
+
+    pub fn get_energy(&self) -> i32 {
+        self.energy
+    }
+
+    pub fn gain_energy(&mut self) {
+        self.energy += self.speed;
+    }
+
+    pub fn can_act(&self) -> bool {
+        self.energy >= ACTION_COST
+    }
+
+    pub fn spend_energy(&mut self, cost: i32) {
+        self.energy -= cost;
+    }
+}
@@ -9,9 +9,26 @@ use dorp::{
 use core::{RogueData, RogueDataErr, RogueWorld};
 use components::{BlockCoords, BlockMap};
 
-pub enum BlockType {
-    Air,
-    Dirt,
+// Id into the BlockRegistry, the properties of each type live in assets/blocks.cfg
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct BlockType(u16);
+
+impl BlockType {
+    // Core types the engine and generators refer to directly, the registry pins them to these ids
+    pub const AIR: BlockType = BlockType(0);
+    pub const DIRT: BlockType = BlockType(1);
+    pub const DOOR: BlockType = BlockType(2);
+    pub const CHEST: BlockType = BlockType(3);
+    pub const STONE: BlockType = BlockType(4);
+    pub const GRASS: BlockType = BlockType(5);
+
+    pub fn new(id: u16) -> BlockType {
+        BlockType(id)
+    }
+
+    pub fn get_id(&self) -> u16 {
+        self.0
+    }
 }
 
 enum NeighborState {
@@ -32,9 +49,8 @@ impl Block {
         let block_map_id = match world.get_mut_entity_by_name(block_map_name) {
             OptErr::Full(block_map) => {
                 match block_map.get_mut_block_map() {
-                    OptErr::Full(block_map) => match block_coords.register(id, block_map) {
-                        Ok(()) => (),
-                        Err(err) => panic!(),
+                    OptErr::Full(block_map) => {
+                        block_map.insert(block_coords, id);
                     },
                     OptErr::Empty => return Err(BlockErr::Get("Block Map Get Mut Map 3d")),
                     OptErr::Error(err) => return Err(BlockErr::RogueData("Block map get mut map 3d", err)),
@@ -55,7 +71,7 @@ impl Block {
     }
 
     pub fn new_with_block_map(block_type: BlockType, id: Id, block_coords: &BlockCoords, block_map: &mut BlockMap, block_map_id: Id) -> Block {
-        block_coords.register(id, block_map);
+        block_map.insert(block_coords, id);
         Block {
             block_type: block_type,
             block_map_id: Some(block_map_id),
@@ -64,82 +80,73 @@ impl Block {
         }
     }
 
-    pub fn tick_mut(&mut self, my_id: Id, world: &mut RogueWorld) -> Result<(), BlockErr> {
+    pub fn get_block_type(&self) -> BlockType {
+        self.block_type
+    }
+
+    pub fn get_neighbors(&self) -> &Vec<Id> {
+        &self.neighbors
+    }
+
+    pub fn tick_mut(&mut self, block_coords: &BlockCoords, world: &mut RogueWorld) -> Result<(), BlockErr> {
         match self.neighbor_state {
             NeighborState::Clean => Ok(()),
-            NeighborState::Dirty => match self.update_neighbors(my_id, false, world) {
+            NeighborState::Dirty => match self.update_neighbors(block_coords, false, world) {
                 Ok(()) => Ok(()),
-                Err(err) => return Err(BlockErr::Block("self Update Neighbors my id false world", Box::new(err))),
+                Err(err) => return Err(BlockErr::Block("self Update Neighbors Block Coords false world", Box::new(err))),
             },
-            NeighborState::New => match self.update_neighbors(my_id, true, world) {
+            NeighborState::New => match self.update_neighbors(block_coords, true, world) {
                 Ok(()) => Ok(()),
-                Err(err) => return Err(BlockErr::Block("Self Update Neighbors My Id True World", Box::new(err))),
+                Err(err) => return Err(BlockErr::Block("Self Update Neighbors Block Coords True World", Box::new(err))),
             },
         }
     }
 
-    fn update_neighbors(&mut self, my_id: Id, trigger_more: bool, world: &mut RogueWorld) -> Result<(), BlockErr> {
-        match world.get_entity_by_id(my_id) {
-            Some(my_entity) => {
-                match my_entity.get_block_coords() {
-                    Some(block_coords) => {
-                        match self.block_map_id {
-                            Some(block_map_id) => {
-                                match world.get_entity_by_id(block_map_id) {
-                                    Some(block_map_entity) => {
-                                        match block_map_entity.get_block_map() {
-                                            Some(block_map) => {
-                                                for z in -1..2 {
-                                                    for y in -1..2 {
-                                                        for x in -1..2 {
-                                                            match block_map.get(block_coords.get_x() + x, block_coords.get_y() + y, block_coords.get_z() + z) {
-                                                                Some(block_id) => {
-                                                                    if trigger_more {
-                                                                        match world.take_entity_by_id(block_id) {
-                                                                            OptErr::Full(mut block_entity_arc) => {
-                                                                                match Arc::get_mut(&mut block_entity_arc) {
-                                                                                    Some(block_entity) => {
-                                                                                        match block_entity.get_mut_block() {
-                                                                                            OptErr::Full(block) => {
-                                                                                                match block.update_neighbors(block_id, false, world) {
-                                                                                                    Ok(()) => (),
-                                                                                                    Err(err) => return Err(BlockErr::Block("Block Update Neighbors Block Id False World", Box::new(err))),
-                                                                                                }
-                                                                                            },
-                                                                                            OptErr::Empty => return Err(BlockErr::Get("Block Entity Get Mut Block")),
-                                                                                            OptErr::Error(err) => return Err(BlockErr::RogueData("Block Entity Get Mut Block", err)),
-                                                                                        }
-                                                                                    },
-                                                                                    None => return Err(BlockErr::GetMut("Arc Get Mut Block Entity Arc")),
-                                                                                }
-                                                                                world.add_entity_arc(block_entity_arc);
-                                                                            },
-                                                                            OptErr::Empty => return Err(BlockErr::Get("World Get Mut Entity By Id Block Id")),
-                                                                            OptErr::Error(err) => return Err(BlockErr::World("World get Mut Entity by Id Block Id", err)),
-                                                                        }
-                                                                    }
-                                                                    self.neighbors.push(block_id);
-                                                                },
-                                                                None => return Err(BlockErr::Get("Block Map Get Tile Block Coords + Offset")),
-                                                            }
-                                                        }
-                                                    }
-                                                }
-                                            },
-                                            None => return Err(BlockErr::Get("Block Map Entitty Get Block Map")),
+    fn update_neighbors(&mut self, block_coords: &BlockCoords, trigger_more: bool, world: &mut RogueWorld) -> Result<(), BlockErr> {
+        let block_map_id = match self.block_map_id {
+            Some(block_map_id) => block_map_id,
+            None => return Ok(()),
+        };
+        let block_map = match world.get_entity_by_id(block_map_id) {
+            Some(block_map_entity) => match block_map_entity.get_block_map() {
+                Some(block_map) => block_map,
+                None => return Err(BlockErr::Get("Block Map Entitty Get Block Map")),
+            },
+            None => return Err(BlockErr::Get("World Get Entity by Id self block map id")),
+        };
+        self.neighbors.clear();
+        for block_id in block_map.get_neighbors(block_coords) {
+            if trigger_more {
+                match world.take_entity_by_id(block_id) {
+                    OptErr::Full(mut block_entity_arc) => {
+                        match Arc::get_mut(&mut block_entity_arc) {
+                            Some(block_entity) => {
+                                let neighbor_coords = match block_entity.get_block_coords() {
+                                    Some(neighbor_coords) => neighbor_coords,
+                                    None => return Err(BlockErr::Get("Block Entity Get Block Coords")),
+                                };
+                                match block_entity.get_mut_block() {
+                                    OptErr::Full(block) => {
+                                        match block.update_neighbors(&neighbor_coords, false, world) {
+                                            Ok(()) => (),
+                                            Err(err) => return Err(BlockErr::Block("Block Update Neighbors Neighbor Coords False World", Box::new(err))),
                                         }
                                     },
-                                    None => return Err(BlockErr::Get("World Get Entity by Id self block map id")),
+                                    OptErr::Empty => return Err(BlockErr::Get("Block Entity Get Mut Block")),
+                                    OptErr::Error(err) => return Err(BlockErr::RogueData("Block Entity Get Mut Block", err)),
                                 }
                             },
-                            None => (),
+                            None => return Err(BlockErr::GetMut("Arc Get Mut Block Entity Arc")),
                         }
+                        world.add_entity_arc(block_entity_arc);
                     },
-                    None => return Err(BlockErr::Get("Entity Get Block Coords")),
+                    OptErr::Empty => (),
+                    OptErr::Error(err) => return Err(BlockErr::World("World get Mut Entity by Id Block Id", err)),
                 }
-            },
-            None => return Err(BlockErr::Get("World Get Entity by id My ID")),
+            }
+            self.neighbors.push(block_id);
         }
+        self.neighbor_state = NeighborState::Clean;
         Ok(())
     }
 }
@@ -1,5 +1,84 @@
-use dorp::{Map3dCoords};
+use std::fmt;
+use std::str::{FromStr};
 
 use core::{BlockCoordSize};
+use components::{ChunkCoords, CHUNK_SIZE};
 
-pub type BlockCoords = Map3dCoords<BlockCoordSize>;
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct BlockCoords {
+    x: BlockCoordSize,
+    y: BlockCoordSize,
+    z: BlockCoordSize,
+}
+
+impl BlockCoords {
+    pub fn new(x: BlockCoordSize, y: BlockCoordSize, z: BlockCoordSize) -> BlockCoords {
+        BlockCoords {
+            x: x,
+            y: y,
+            z: z,
+        }
+    }
+
+    pub fn get_x(&self) -> BlockCoordSize {
+        self.x
+    }
+
+    pub fn get_y(&self) -> BlockCoordSize {
+        self.y
+    }
+
+    pub fn get_z(&self) -> BlockCoordSize {
+        self.z
+    }
+
+    pub fn offset(&self, x: BlockCoordSize, y: BlockCoordSize, z: BlockCoordSize) -> BlockCoords {
+        BlockCoords::new(self.x + x, self.y + y, self.z + z)
+    }
+
+    pub fn get_chunk_coords(&self) -> ChunkCoords {
+        ChunkCoords::new(floor_div(self.x, CHUNK_SIZE), floor_div(self.y, CHUNK_SIZE), floor_div(self.z, CHUNK_SIZE))
+    }
+
+    // Position inside the owning chunk, each axis in 0..CHUNK_SIZE
+    pub fn get_local(&self) -> (usize, usize, usize) {
+        (floor_mod(self.x, CHUNK_SIZE) as usize, floor_mod(self.y, CHUNK_SIZE) as usize, floor_mod(self.z, CHUNK_SIZE) as usize)
+    }
+}
+
+// Written as 'x,y,z' so lists of coords can be whitespace separated
+impl fmt::Display for BlockCoords {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+impl FromStr for BlockCoords {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<BlockCoords, ()> {
+        let mut values = vec!();
+        for part in text.split(',') {
+            match part.trim().parse() {
+                Ok(value) => values.push(value),
+                Err(_) => return Err(()),
+            }
+        }
+        if values.len() != 3 {
+            return Err(());
+        }
+        Ok(BlockCoords::new(values[0], values[1], values[2]))
+    }
+}
+
+fn floor_div(value: BlockCoordSize, size: BlockCoordSize) -> BlockCoordSize {
+    if value >= 0 {
+        value / size
+    } else {
+        (value - size + 1) / size
+    }
+}
+
+fn floor_mod(value: BlockCoordSize, size: BlockCoordSize) -> BlockCoordSize {
+    value - floor_div(value, size) * size
+}
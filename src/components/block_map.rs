@@ -1,5 +1,120 @@
-use dorp::{Map3d};
+use std::collections::{HashMap};
+
+use dorp::{Id};
 
 use core::{BlockCoordSize};
+use components::{BlockCoords, Chunk, ChunkCoords};
+
+pub struct BlockMap {
+    chunks: HashMap<ChunkCoords, Chunk>,
+}
+
+impl BlockMap {
+    pub fn new() -> BlockMap {
+        BlockMap {
+            chunks: HashMap::new(),
+        }
+    }
+
+    pub fn get(&self, x: BlockCoordSize, y: BlockCoordSize, z: BlockCoordSize) -> Option<Id> {
+        self.get_at(&BlockCoords::new(x, y, z))
+    }
+
+    pub fn get_at(&self, block_coords: &BlockCoords) -> Option<Id> {
+        match self.chunks.get(&block_coords.get_chunk_coords()) {
+            Some(chunk) => {
+                let (x, y, z) = block_coords.get_local();
+                chunk.get(x, y, z)
+            },
+            None => None,
+        }
+    }
+
+    pub fn insert(&mut self, block_coords: &BlockCoords, id: Id) -> Option<Id> {
+        self.set(block_coords, Some(id))
+    }
+
+    pub fn remove(&mut self, block_coords: &BlockCoords) -> Option<Id> {
+        if !self.chunks.contains_key(&block_coords.get_chunk_coords()) {
+            return None;
+        }
+        self.set(block_coords, None)
+    }
+
+    fn set(&mut self, block_coords: &BlockCoords, block: Option<Id>) -> Option<Id> {
+        let chunk_coords = block_coords.get_chunk_coords();
+        let (x, y, z) = block_coords.get_local();
+        let old = self.load_chunk(chunk_coords).set(x, y, z, block);
+        // Neighbors reach diagonally, so a block on a chunk's face, edge or corner changes what
+        // every other chunk holding one of its 26 surrounding cells sees
+        for dz in -1..2 {
+            for dy in -1..2 {
+                for dx in -1..2 {
+                    let neighbor_coords = block_coords.offset(dx, dy, dz).get_chunk_coords();
+                    if neighbor_coords != chunk_coords {
+                        if let Some(chunk) = self.chunks.get_mut(&neighbor_coords) {
+                            chunk.set_dirty();
+                        }
+                    }
+                }
+            }
+        }
+        old
+    }
+
+    pub fn load_chunk(&mut self, chunk_coords: ChunkCoords) -> &mut Chunk {
+        self.chunks.entry(chunk_coords).or_insert_with(Chunk::new)
+    }
+
+    pub fn insert_chunk(&mut self, chunk_coords: ChunkCoords, chunk: Chunk) -> Option<Chunk> {
+        self.chunks.insert(chunk_coords, chunk)
+    }
+
+    pub fn unload_chunk(&mut self, chunk_coords: &ChunkCoords) -> Option<Chunk> {
+        self.chunks.remove(chunk_coords)
+    }
+
+    pub fn is_chunk_loaded(&self, chunk_coords: &ChunkCoords) -> bool {
+        self.chunks.contains_key(chunk_coords)
+    }
+
+    pub fn get_chunk(&self, chunk_coords: &ChunkCoords) -> Option<&Chunk> {
+        self.chunks.get(chunk_coords)
+    }
+
+    pub fn get_mut_chunk(&mut self, chunk_coords: &ChunkCoords) -> Option<&mut Chunk> {
+        self.chunks.get_mut(chunk_coords)
+    }
+
+    pub fn get_loaded_chunks(&self) -> Vec<ChunkCoords> {
+        self.chunks.keys().cloned().collect()
+    }
+
+    pub fn get_dirty_chunks(&self) -> Vec<ChunkCoords> {
+        self.chunks.iter().filter(|&(_, chunk)| chunk.is_dirty()).map(|(chunk_coords, _)| *chunk_coords).collect()
+    }
+
+    pub fn clean_chunk(&mut self, chunk_coords: &ChunkCoords) {
+        if let Some(chunk) = self.chunks.get_mut(chunk_coords) {
+            chunk.set_clean();
+        }
+    }
 
-pub type BlockMap = Map3d<BlockCoordSize>;
+    // Ids of the up to 26 blocks surrounding block_coords, skipping empty cells and unloaded chunks
+    pub fn get_neighbors(&self, block_coords: &BlockCoords) -> Vec<Id> {
+        let mut neighbors = vec!();
+        for z in -1..2 {
+            for y in -1..2 {
+                for x in -1..2 {
+                    if x == 0 && y == 0 && z == 0 {
+                        continue;
+                    }
+                    if let Some(id) = self.get_at(&block_coords.offset(x, y, z)) {
+                        neighbors.push(id);
+                    }
+                }
+            }
+        }
+        neighbors
+    }
+}
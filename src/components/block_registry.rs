@@ -0,0 +1,243 @@
+use std::collections::{HashMap};
+use std::error::Error;
+use std::fmt;
+
+use core::{Config, ConfigErr, ConfigSection};
+use components::{BlockType};
+
+pub static BLOCKS_PATH: &'static str = "assets/blocks.cfg";
+
+// Index is the id of the matching BlockType constant
+static CORE_BLOCKS: [&'static str; 6] = ["air", "dirt", "door", "chest", "stone", "grass"];
+
+#[derive(Clone, Debug)]
+pub struct BlockDef {
+    name: String,
+    solid: bool,
+    opaque: bool,
+    special: bool,
+    walk_cost: u32,
+    color: [f32; 4],
+    texture: Option<String>,
+    hardness: f32,
+    drop: Option<String>,
+}
+
+impl BlockDef {
+    fn from_section(section: &ConfigSection) -> Result<BlockDef, ConfigErr> {
+        let solid = match section.get_parsed("solid", true) {
+            Ok(solid) => solid,
+            Err(err) => return Err(err),
+        };
+        let opaque = match section.get_parsed("opaque", solid) {
+            Ok(opaque) => opaque,
+            Err(err) => return Err(err),
+        };
+        let special = match section.get_parsed("special", false) {
+            Ok(special) => special,
+            Err(err) => return Err(err),
+        };
+        let walk_cost = match section.get_parsed("walk_cost", 1) {
+            Ok(walk_cost) => walk_cost,
+            Err(err) => return Err(err),
+        };
+        let hardness = match section.get_parsed("hardness", 1.0) {
+            Ok(hardness) => hardness,
+            Err(err) => return Err(err),
+        };
+        let color = match section.get_entry("color") {
+            Some(entry) => match entry.parse_list::<f32>() {
+                Ok(ref color) if color.len() == 4 => [color[0], color[1], color[2], color[3]],
+                Ok(ref color) if color.len() == 3 => [color[0], color[1], color[2], 1.0],
+                Ok(_) => return Err(ConfigErr::Value(entry.get_line(), entry.get_key().to_string(), entry.get_value().to_string())),
+                Err(err) => return Err(err),
+            },
+            None => [1.0, 0.0, 1.0, 1.0],
+        };
+        Ok(BlockDef {
+            name: section.get_name().to_string(),
+            solid: solid,
+            opaque: opaque,
+            special: special,
+            walk_cost: walk_cost,
+            color: color,
+            texture: section.get("texture").map(|texture| texture.to_string()),
+            hardness: hardness,
+            drop: section.get("drop").map(|drop| drop.to_string()),
+        })
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    // Blocks movement
+    pub fn is_solid(&self) -> bool {
+        self.solid
+    }
+
+    // Blocks sight and hides the faces of neighbours
+    pub fn is_opaque(&self) -> bool {
+        self.opaque
+    }
+
+    // Special blocks carry state of their own and are spawned as entities instead of living only in a VoxelVolume
+    pub fn is_special(&self) -> bool {
+        self.special
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.color[3] > 0.0 || self.texture.is_some()
+    }
+
+    pub fn get_walk_cost(&self) -> u32 {
+        self.walk_cost
+    }
+
+    pub fn get_color(&self) -> [f32; 4] {
+        self.color
+    }
+
+    pub fn get_texture(&self) -> Option<&str> {
+        self.texture.as_ref().map(|texture| texture.as_str())
+    }
+
+    pub fn get_hardness(&self) -> f32 {
+        self.hardness
+    }
+
+    pub fn get_drop(&self) -> Option<&str> {
+        self.drop.as_ref().map(|drop| drop.as_str())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BlockRegistry {
+    defs: Vec<BlockDef>,
+    names: HashMap<String, BlockType>,
+}
+
+impl BlockRegistry {
+    pub fn load(path: &str) -> Result<BlockRegistry, BlockRegistryErr> {
+        match Config::load(path) {
+            Ok(config) => BlockRegistry::from_config(&config),
+            Err(err) => Err(BlockRegistryErr::Config("Config Load Path", err)),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<BlockRegistry, BlockRegistryErr> {
+        match Config::parse(text) {
+            Ok(config) => BlockRegistry::from_config(&config),
+            Err(err) => Err(BlockRegistryErr::Config("Config Parse Text", err)),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Result<BlockRegistry, BlockRegistryErr> {
+        let mut slots: Vec<Option<BlockDef>> = CORE_BLOCKS.iter().map(|_| None).collect();
+        for section in config.get_sections() {
+            let block_def = match BlockDef::from_section(section) {
+                Ok(block_def) => block_def,
+                Err(err) => return Err(BlockRegistryErr::Config("Block Def From Section", err)),
+            };
+            match CORE_BLOCKS.iter().position(|name| *name == section.get_name()) {
+                Some(index) => slots[index] = Some(block_def),
+                None => slots.push(Some(block_def)),
+            }
+        }
+        if slots.len() > u16::max_value() as usize {
+            return Err(BlockRegistryErr::TooMany(slots.len()));
+        }
+        let mut defs = vec!();
+        let mut names = HashMap::new();
+        for (index, slot) in slots.into_iter().enumerate() {
+            match slot {
+                Some(block_def) => {
+                    names.insert(block_def.get_name().to_string(), BlockType::new(index as u16));
+                    defs.push(block_def);
+                },
+                None => return Err(BlockRegistryErr::MissingCore(CORE_BLOCKS[index])),
+            }
+        }
+        for block_def in &defs {
+            match block_def.get_drop() {
+                Some(drop) if !names.contains_key(drop) => return Err(BlockRegistryErr::UnknownDrop(block_def.get_name().to_string(), drop.to_string())),
+                _ => (),
+            }
+        }
+        Ok(BlockRegistry {
+            defs: defs,
+            names: names,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.defs.len()
+    }
+
+    pub fn get(&self, block_type: BlockType) -> Option<&BlockDef> {
+        self.defs.get(block_type.get_id() as usize)
+    }
+
+    pub fn get_by_name(&self, name: &str) -> Option<BlockType> {
+        self.names.get(name).cloned()
+    }
+
+    pub fn get_block_types(&self) -> Vec<BlockType> {
+        (0..self.defs.len()).map(|id| BlockType::new(id as u16)).collect()
+    }
+
+    // Unknown ids read as air so stale data can't crash the mesher or movement
+    pub fn is_solid(&self, block_type: BlockType) -> bool {
+        self.get(block_type).map_or(false, |block_def| block_def.is_solid())
+    }
+
+    pub fn is_opaque(&self, block_type: BlockType) -> bool {
+        self.get(block_type).map_or(false, |block_def| block_def.is_opaque())
+    }
+
+    pub fn is_special(&self, block_type: BlockType) -> bool {
+        self.get(block_type).map_or(false, |block_def| block_def.is_special())
+    }
+
+    pub fn is_visible(&self, block_type: BlockType) -> bool {
+        self.get(block_type).map_or(false, |block_def| block_def.is_visible())
+    }
+
+    pub fn get_color(&self, block_type: BlockType) -> [f32; 4] {
+        self.get(block_type).map_or([0.0, 0.0, 0.0, 0.0], |block_def| block_def.get_color())
+    }
+
+    pub fn get_walk_cost(&self, block_type: BlockType) -> u32 {
+        self.get(block_type).map_or(1, |block_def| block_def.get_walk_cost())
+    }
+}
+
+#[derive(Debug)]
+pub enum BlockRegistryErr {
+    Config(&'static str, ConfigErr),
+    MissingCore(&'static str),
+    UnknownDrop(String, String),
+    TooMany(usize),
+}
+
+impl fmt::Display for BlockRegistryErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BlockRegistryErr::Config(_, ref err) => err.fmt(f),
+            BlockRegistryErr::MissingCore(name) => write!(f, "Core block [{}] is not defined", name),
+            BlockRegistryErr::UnknownDrop(ref name, ref drop) => write!(f, "Block [{}] drops unknown block {}", name, drop),
+            BlockRegistryErr::TooMany(count) => write!(f, "{} blocks defined, ids only go to {}", count, u16::max_value()),
+        }
+    }
+}
+
+impl Error for BlockRegistryErr {
+    fn description(&self) -> &str {
+        match *self {
+            BlockRegistryErr::Config(_, ref err) => err.description(),
+            BlockRegistryErr::MissingCore(_) => "Core block is not defined",
+            BlockRegistryErr::UnknownDrop(_, _) => "Block drops unknown block",
+            BlockRegistryErr::TooMany(_) => "Too many blocks defined",
+        }
+    }
+}
@@ -0,0 +1,332 @@
+use std::error::Error;
+use std::fmt;
+use std::f32::consts::{PI};
+
+use dorp::{Mat4, Vec3};
+
+use core::{RogueWorld, INPUT_NAME};
+use components::{BlockCoords};
+use graphics::{BillboardView, Cutaway, CutawayMode};
+use input::{Action, InputState};
+
+static PITCH_DEGREES: f32 = 45.0;
+// The grid sits 45 degrees off the screen axes, each quarter turn adds another 90
+static YAW_DEGREES: f32 = 45.0;
+static VIEW_SIZE: f32 = 90.0;
+static NEAR: f32 = 0.1;
+static FAR: f32 = 100.0;
+static MIN_ZOOM: f32 = 0.25;
+static MAX_ZOOM: f32 = 8.0;
+static SNAP_DISTANCE: f32 = 0.001;
+static ZOOM_STEP: f32 = 1.25;
+// Blocks per tick while a pan action is held
+static PAN_SPEED: f32 = 0.5;
+
+pub struct Camera {
+    focus: [f32; 3],
+    pan: [f32; 3],
+    zoom: f32,
+    quarter_turns: u8,
+    aspect_ratio: f32,
+    scale: [f32; 3],
+    follow: Option<&'static str>,
+    follow_rate: f32,
+    cutaway_mode: CutawayMode,
+    cutaway_focus: Option<BlockCoords>,
+    input_frame: Option<u64>,
+    version: u64,
+}
+
+impl Camera {
+    pub fn new(aspect_ratio: f32) -> Camera {
+        Camera {
+            focus: [0.0; 3],
+            pan: [0.0; 3],
+            zoom: 1.0,
+            quarter_turns: 0,
+            aspect_ratio: aspect_ratio,
+            scale: [1.0; 3],
+            follow: None,
+            follow_rate: 0.2,
+            cutaway_mode: CutawayMode::Auto,
+            cutaway_focus: None,
+            input_frame: None,
+            version: 0,
+        }
+    }
+
+    // Block units to render units, matching the Transform the chunks are drawn with
+    pub fn with_scale(mut self, scale: [f32; 3]) -> Camera {
+        self.scale = scale;
+        self
+    }
+
+    pub fn with_focus(mut self, focus: [f32; 3]) -> Camera {
+        self.focus = focus;
+        self
+    }
+
+    // Named entity whose BlockCoords the camera eases towards each tick
+    pub fn with_follow(mut self, name: &'static str) -> Camera {
+        self.follow = Some(name);
+        self
+    }
+
+    // Fraction of the remaining distance covered per tick, 1 follows rigidly
+    pub fn with_follow_rate(mut self, follow_rate: f32) -> Camera {
+        self.follow_rate = follow_rate.max(0.0).min(1.0);
+        self
+    }
+
+    pub fn with_cutaway_mode(mut self, cutaway_mode: CutawayMode) -> Camera {
+        self.cutaway_mode = cutaway_mode;
+        self
+    }
+
+    // Bumped on every change so renderables know when to pick up the new matrices
+    pub fn get_version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn get_focus(&self) -> [f32; 3] {
+        self.focus
+    }
+
+    pub fn get_pan(&self) -> [f32; 3] {
+        self.pan
+    }
+
+    pub fn get_zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    pub fn get_quarter_turns(&self) -> u8 {
+        self.quarter_turns
+    }
+
+    pub fn get_aspect_ratio(&self) -> f32 {
+        self.aspect_ratio
+    }
+
+    pub fn get_follow(&self) -> Option<&'static str> {
+        self.follow
+    }
+
+    pub fn get_pitch(&self) -> f32 {
+        PITCH_DEGREES.to_radians()
+    }
+
+    pub fn get_yaw(&self) -> f32 {
+        (YAW_DEGREES + 90.0 * self.quarter_turns as f32).to_radians() % (2.0 * PI)
+    }
+
+    pub fn get_billboard_view(&self) -> BillboardView {
+        BillboardView::new(self.get_pitch(), self.get_yaw())
+    }
+
+    pub fn get_cutaway_mode(&self) -> CutawayMode {
+        self.cutaway_mode
+    }
+
+    // Cuts around the followed entity's block as of the last tick
+    pub fn get_cutaway(&self) -> Cutaway {
+        let cutaway = Cutaway::new(self.cutaway_mode, self.get_billboard_view());
+        match self.cutaway_focus {
+            Some(cutaway_focus) => cutaway.with_focus(cutaway_focus),
+            None => cutaway,
+        }
+    }
+
+    pub fn get_view_size(&self) -> f32 {
+        VIEW_SIZE / self.zoom
+    }
+
+    pub fn set_zoom(&mut self, zoom: f32) {
+        let zoom = zoom.max(MIN_ZOOM).min(MAX_ZOOM);
+        if zoom != self.zoom {
+            self.zoom = zoom;
+            self.version += 1;
+        }
+    }
+
+    pub fn zoom_by(&mut self, factor: f32) {
+        let zoom = self.zoom * factor;
+        self.set_zoom(zoom);
+    }
+
+    // Moves the focus along the ground, x to the right of the screen and y up it
+    pub fn pan(&mut self, x: f32, y: f32) {
+        let view = self.get_billboard_view();
+        let right = view.get_right();
+        let forward = view.get_forward();
+        let length = (forward[0] * forward[0] + forward[2] * forward[2]).sqrt();
+        self.pan[0] += right[0] * x + forward[0] / length * y;
+        self.pan[2] += right[2] * x + forward[2] / length * y;
+        self.version += 1;
+    }
+
+    pub fn reset_pan(&mut self) {
+        self.pan = [0.0; 3];
+        self.version += 1;
+    }
+
+    // Positive turns rotate the view clockwise, snapping straight to the next quarter
+    pub fn rotate(&mut self, quarter_turns: i32) {
+        self.quarter_turns = ((self.quarter_turns as i32 + quarter_turns) % 4 + 4) as u8 % 4;
+        self.version += 1;
+    }
+
+    pub fn set_cutaway_mode(&mut self, cutaway_mode: CutawayMode) {
+        self.cutaway_mode = cutaway_mode;
+    }
+
+    pub fn set_cutaway_focus(&mut self, cutaway_focus: Option<BlockCoords>) {
+        self.cutaway_focus = cutaway_focus;
+    }
+
+    pub fn set_aspect_ratio(&mut self, aspect_ratio: f32) {
+        if aspect_ratio != self.aspect_ratio {
+            self.aspect_ratio = aspect_ratio;
+            self.version += 1;
+        }
+    }
+
+    // Eases the focus towards target, snapping once close enough so the version settles
+    pub fn follow(&mut self, target: [f32; 3]) {
+        let mut moved = false;
+        for axis in 0..3 {
+            let distance = target[axis] - self.focus[axis];
+            if distance.abs() <= SNAP_DISTANCE {
+                if distance != 0.0 {
+                    self.focus[axis] = target[axis];
+                    moved = true;
+                }
+            } else {
+                self.focus[axis] += distance * self.follow_rate;
+                moved = true;
+            }
+        }
+        if moved {
+            self.version += 1;
+        }
+    }
+
+    // Slicing starts from the focus layer and toggling drops back to no cutaway at all
+    pub fn slice(&mut self, layers: i64) {
+        let max_y = match self.cutaway_mode {
+            CutawayMode::Slice(max_y) => max_y,
+            _ => match self.cutaway_focus {
+                Some(cutaway_focus) => cutaway_focus.get_y(),
+                None => 0,
+            },
+        };
+        self.cutaway_mode = CutawayMode::Slice(max_y + layers);
+    }
+
+    pub fn toggle_cutaway(&mut self) {
+        self.cutaway_mode = match self.cutaway_mode {
+            CutawayMode::Off => CutawayMode::Auto,
+            _ => CutawayMode::Off,
+        };
+    }
+
+    // Presses act once per frame, held pans move every tick
+    pub fn read_input(&mut self, input_state: &InputState) {
+        if self.input_frame != Some(input_state.get_frame()) {
+            self.input_frame = Some(input_state.get_frame());
+            for action in input_state.get_pressed() {
+                match *action {
+                    Action::ZoomIn => self.zoom_by(ZOOM_STEP),
+                    Action::ZoomOut => self.zoom_by(1.0 / ZOOM_STEP),
+                    Action::RotateLeft => self.rotate(-1),
+                    Action::RotateRight => self.rotate(1),
+                    Action::ResetPan => self.reset_pan(),
+                    Action::SliceUp => self.slice(1),
+                    Action::SliceDown => self.slice(-1),
+                    Action::ToggleCutaway => self.toggle_cutaway(),
+                    _ => (),
+                }
+            }
+        }
+        let mut x = 0.0;
+        let mut y = 0.0;
+        if input_state.is_held(Action::PanLeft) {
+            x -= PAN_SPEED;
+        }
+        if input_state.is_held(Action::PanRight) {
+            x += PAN_SPEED;
+        }
+        if input_state.is_held(Action::PanDown) {
+            y -= PAN_SPEED;
+        }
+        if input_state.is_held(Action::PanUp) {
+            y += PAN_SPEED;
+        }
+        if x != 0.0 || y != 0.0 {
+            self.pan(x, y);
+        }
+    }
+
+    pub fn get_projection(&self) -> Mat4 {
+        Mat4::orthographic(NEAR, FAR, self.get_view_size(), self.aspect_ratio)
+    }
+
+    pub fn get_view(&self) -> Mat4 {
+        let centre = Vec3::from([
+            -(self.focus[0] + self.pan[0]) * self.scale[0],
+            -(self.focus[1] + self.pan[1]) * self.scale[1],
+            -(self.focus[2] + self.pan[2]) * self.scale[2]
+        ]);
+        Mat4::x_rotation(self.get_pitch()) * Mat4::y_rotation(self.get_yaw()) * Mat4::translation(centre)
+    }
+
+    pub fn tick_mut(&mut self, world: &RogueWorld) -> Result<(), CameraErr> {
+        self.set_aspect_ratio(world.get_aspect_ratio());
+        match world.get_entity_by_name(INPUT_NAME) {
+            Some(input_entity) => match input_entity.get_input_state() {
+                Some(input_state) => self.read_input(&input_state),
+                None => return Err(CameraErr::Get("Input Entity Get Input State")),
+            },
+            None => (),
+        }
+        let name = match self.follow {
+            Some(name) => name,
+            None => return Ok(()),
+        };
+        let block_coords = match world.get_entity_by_name(name) {
+            Some(entity) => match entity.get_block_coords() {
+                Some(block_coords) => block_coords,
+                None => return Err(CameraErr::Get("Followed Entity Get Block Coords")),
+            },
+            None => return Ok(()),
+        };
+        self.cutaway_focus = Some(*block_coords);
+        self.follow([
+            block_coords.get_x() as f32 + 0.5,
+            block_coords.get_y() as f32,
+            block_coords.get_z() as f32 + 0.5
+        ]);
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum CameraErr {
+    Get(&'static str),
+}
+
+impl fmt::Display for CameraErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CameraErr::Get(_) => write!(f, "Get was None"),
+        }
+    }
+}
+
+impl Error for CameraErr {
+    fn description(&self) -> &str {
+        match *self {
+            CameraErr::Get(_) => "Get was None",
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use dorp::{Id};
+
+use core::{BlockCoordSize};
+use components::{BlockCoords};
+
+pub const CHUNK_SIZE: BlockCoordSize = 16;
+pub const CHUNK_VOLUME: usize = (CHUNK_SIZE * CHUNK_SIZE * CHUNK_SIZE) as usize;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ChunkCoords {
+    x: BlockCoordSize,
+    y: BlockCoordSize,
+    z: BlockCoordSize,
+}
+
+impl ChunkCoords {
+    pub fn new(x: BlockCoordSize, y: BlockCoordSize, z: BlockCoordSize) -> ChunkCoords {
+        ChunkCoords {
+            x: x,
+            y: y,
+            z: z,
+        }
+    }
+
+    pub fn get_x(&self) -> BlockCoordSize {
+        self.x
+    }
+
+    pub fn get_y(&self) -> BlockCoordSize {
+        self.y
+    }
+
+    pub fn get_z(&self) -> BlockCoordSize {
+        self.z
+    }
+
+    pub fn offset(&self, x: BlockCoordSize, y: BlockCoordSize, z: BlockCoordSize) -> ChunkCoords {
+        ChunkCoords::new(self.x + x, self.y + y, self.z + z)
+    }
+
+    // Block coords of the lowest corner of this chunk
+    pub fn get_origin(&self) -> BlockCoords {
+        BlockCoords::new(self.x * CHUNK_SIZE, self.y * CHUNK_SIZE, self.z * CHUNK_SIZE)
+    }
+}
+
+pub struct Chunk {
+    blocks: Vec<Option<Id>>,
+    count: usize,
+    dirty: bool,
+}
+
+impl Chunk {
+    pub fn new() -> Chunk {
+        Chunk {
+            blocks: vec!(None; CHUNK_VOLUME),
+            count: 0,
+            dirty: true,
+        }
+    }
+
+    fn index(x: usize, y: usize, z: usize) -> usize {
+        (z * CHUNK_SIZE as usize + y) * CHUNK_SIZE as usize + x
+    }
+
+    pub fn get(&self, x: usize, y: usize, z: usize) -> Option<Id> {
+        self.blocks[Chunk::index(x, y, z)]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, z: usize, block: Option<Id>) -> Option<Id> {
+        let index = Chunk::index(x, y, z);
+        let old = self.blocks[index];
+        self.blocks[index] = block;
+        match (old.is_some(), block.is_some()) {
+            (false, true) => self.count += 1,
+            (true, false) => self.count -= 1,
+            _ => (),
+        }
+        self.dirty = true;
+        old
+    }
+
+    pub fn get_count(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    pub fn set_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    pub fn set_clean(&mut self) {
+        self.dirty = false;
+    }
+
+    pub fn get_ids(&self) -> Vec<Id> {
+        self.blocks.iter().filter_map(|block| *block).collect()
+    }
+}
@@ -0,0 +1,140 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc};
+
+use dorp::{Id};
+
+use core::{RogueWorld, BLOCK_REGISTRY_NAME, PLAYER_NAME, VOXEL_VOLUME_NAME};
+use components::{BlockCoords, ChunkCoords, FieldOfView, CHUNK_SIZE};
+use graphics::{Atlas, Cutaway, TexturedChunkMesh, mesh_chunk_textured};
+
+// Everything a drawn chunk needs to mesh itself again when the cutaway or the player's sight
+// changes what it shows
+pub struct ChunkView {
+    chunk_coords: ChunkCoords,
+    atlas: Arc<Atlas>,
+    cutaway: Cutaway,
+    sight_version: Option<u64>,
+    texture_id: Id,
+    vertex_id: Id,
+    model_id: Id,
+}
+
+impl ChunkView {
+    pub fn new(chunk_coords: ChunkCoords, atlas: Arc<Atlas>, cutaway: Cutaway, texture_id: Id, vertex_id: Id, model_id: Id) -> ChunkView {
+        ChunkView {
+            chunk_coords: chunk_coords,
+            atlas: atlas,
+            cutaway: cutaway,
+            sight_version: None,
+            texture_id: texture_id,
+            vertex_id: vertex_id,
+            model_id: model_id,
+        }
+    }
+
+    pub fn get_chunk_coords(&self) -> ChunkCoords {
+        self.chunk_coords
+    }
+
+    pub fn get_atlas(&self) -> Arc<Atlas> {
+        self.atlas.clone()
+    }
+
+    pub fn get_cutaway(&self) -> Cutaway {
+        self.cutaway
+    }
+
+    pub fn get_texture_id(&self) -> Id {
+        self.texture_id
+    }
+
+    pub fn get_vertex_id(&self) -> Id {
+        self.vertex_id
+    }
+
+    pub fn get_model_id(&self) -> Id {
+        self.model_id
+    }
+
+    // Whether a change to this block can alter the chunk's faces, which reach one block past its edges
+    fn touches(&self, block_coords: &BlockCoords) -> bool {
+        let origin = self.chunk_coords.get_origin();
+        let inside = |value, min| value >= min - 1 && value <= min + CHUNK_SIZE;
+        inside(block_coords.get_x(), origin.get_x()) && inside(block_coords.get_y(), origin.get_y()) && inside(block_coords.get_z(), origin.get_z())
+    }
+
+    // Whether the player's sight changed around this chunk since it was last meshed. Only the
+    // latest update's changes are known, so a skipped version always remeshes
+    fn sight_changed(&self, field_of_view: Option<&FieldOfView>) -> bool {
+        match (self.sight_version, field_of_view) {
+            (None, None) => false,
+            (Some(version), Some(field_of_view)) => {
+                if field_of_view.get_version() == version {
+                    false
+                } else if field_of_view.get_version() == version + 1 {
+                    field_of_view.get_changed().iter().any(|block_coords| self.touches(block_coords))
+                } else {
+                    true
+                }
+            },
+            _ => true,
+        }
+    }
+
+    // Returns a new mesh when the cutaway or the player's sight changes any block this chunk draws
+    pub fn tick_mut(&mut self, cutaway: &Cutaway, world: &RogueWorld) -> Result<Option<TexturedChunkMesh>, ChunkViewErr> {
+        let field_of_view = match world.get_entity_by_name(PLAYER_NAME) {
+            Some(entity) => entity.get_field_of_view(),
+            None => None,
+        };
+        let sight_changed = self.sight_changed(field_of_view.as_ref().map(|field_of_view| &**field_of_view));
+        if *cutaway == self.cutaway && !sight_changed {
+            return Ok(None);
+        }
+        let block_registry = match world.get_entity_by_name(BLOCK_REGISTRY_NAME) {
+            Some(entity) => match entity.get_block_registry() {
+                Some(block_registry) => block_registry,
+                None => return Err(ChunkViewErr::Get("Block Registry Entity Get Block Registry")),
+            },
+            None => return Err(ChunkViewErr::Get("World Get Entity By Name Block Registry Name")),
+        };
+        let voxel_volume = match world.get_entity_by_name(VOXEL_VOLUME_NAME) {
+            Some(entity) => match entity.get_voxel_volume() {
+                Some(voxel_volume) => voxel_volume,
+                None => return Err(ChunkViewErr::Get("Voxel Volume Entity Get Voxel Volume")),
+            },
+            None => return Err(ChunkViewErr::Get("World Get Entity By Name Voxel Volume Name")),
+        };
+        let changed = sight_changed || self.cutaway.changes_chunk(cutaway, &voxel_volume, &block_registry, &self.chunk_coords);
+        self.cutaway = *cutaway;
+        self.sight_version = field_of_view.as_ref().map(|field_of_view| field_of_view.get_version());
+        if changed {
+            let field_of_view = field_of_view.as_ref().map(|field_of_view| &**field_of_view);
+            Ok(Some(mesh_chunk_textured(&voxel_volume, &block_registry, &self.atlas, cutaway, field_of_view, &self.chunk_coords)))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ChunkViewErr {
+    Get(&'static str),
+}
+
+impl fmt::Display for ChunkViewErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ChunkViewErr::Get(_) => write!(f, "Get was None"),
+        }
+    }
+}
+
+impl Error for ChunkViewErr {
+    fn description(&self) -> &str {
+        match *self {
+            ChunkViewErr::Get(_) => "Get was None",
+        }
+    }
+}
@@ -0,0 +1,322 @@
+use std::str::{FromStr};
+
+use core::{Rng, BlockCoordSize};
+
+// Hits always have some chance to land and to miss
+static MIN_HIT_CHANCE: f64 = 0.05;
+static MAX_HIT_CHANCE: f64 = 0.95;
+
+static DAMAGE_TYPE_NAMES: [&'static str; 4] = ["physical", "fire", "cold", "poison"];
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DamageType {
+    Physical,
+    Fire,
+    Cold,
+    Poison,
+}
+
+impl DamageType {
+    pub fn get_all() -> Vec<DamageType> {
+        vec!(DamageType::Physical, DamageType::Fire, DamageType::Cold, DamageType::Poison)
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match *self {
+            DamageType::Physical => DAMAGE_TYPE_NAMES[0],
+            DamageType::Fire => DAMAGE_TYPE_NAMES[1],
+            DamageType::Cold => DAMAGE_TYPE_NAMES[2],
+            DamageType::Poison => DAMAGE_TYPE_NAMES[3],
+        }
+    }
+}
+
+impl FromStr for DamageType {
+    type Err = ();
+
+    fn from_str(name: &str) -> Result<DamageType, ()> {
+        match DamageType::get_all().into_iter().find(|damage_type| damage_type.get_name() == name) {
+            Some(damage_type) => Ok(damage_type),
+            None => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AttackResult {
+    Miss,
+    Hit {
+        damage: i32,
+        damage_type: DamageType,
+        critical: bool,
+        killed: bool,
+    },
+}
+
+// What equipment adds to an actor's combat stats, summed over everything it wears
+#[derive(Clone, PartialEq, Debug)]
+pub struct StatBonus {
+    damage: (i32, i32),
+    accuracy: i32,
+    defense: i32,
+    range: BlockCoordSize,
+    resistances: Vec<(DamageType, i32)>,
+}
+
+impl StatBonus {
+    pub fn new() -> StatBonus {
+        StatBonus {
+            damage: (0, 0),
+            accuracy: 0,
+            defense: 0,
+            range: 0,
+            resistances: vec!(),
+        }
+    }
+
+    pub fn with_damage(mut self, min: i32, max: i32) -> StatBonus {
+        self.damage = (min, max);
+        self
+    }
+
+    pub fn with_accuracy(mut self, accuracy: i32) -> StatBonus {
+        self.accuracy = accuracy;
+        self
+    }
+
+    pub fn with_defense(mut self, defense: i32) -> StatBonus {
+        self.defense = defense;
+        self
+    }
+
+    pub fn with_range(mut self, range: BlockCoordSize) -> StatBonus {
+        self.range = range;
+        self
+    }
+
+    pub fn with_resistance(mut self, damage_type: DamageType, percent: i32) -> StatBonus {
+        self.resistances.retain(|&(other, _)| other != damage_type);
+        self.resistances.push((damage_type, percent));
+        self
+    }
+
+    pub fn get_damage(&self) -> (i32, i32) {
+        self.damage
+    }
+
+    pub fn get_accuracy(&self) -> i32 {
+        self.accuracy
+    }
+
+    pub fn get_defense(&self) -> i32 {
+        self.defense
+    }
+
+    pub fn get_range(&self) -> BlockCoordSize {
+        self.range
+    }
+
+    pub fn get_resistance(&self, damage_type: DamageType) -> i32 {
+        self.resistances.iter().find(|&&(other, _)| other == damage_type).map_or(0, |&(_, percent)| percent)
+    }
+
+    pub fn add(&mut self, other: &StatBonus) {
+        self.damage = (self.damage.0 + other.damage.0, self.damage.1 + other.damage.1);
+        self.accuracy += other.accuracy;
+        self.defense += other.defense;
+        self.range += other.range;
+        for &(damage_type, percent) in other.resistances.iter() {
+            let total = self.get_resistance(damage_type) + percent;
+            self.resistances.retain(|&(other, _)| other != damage_type);
+            self.resistances.push((damage_type, total));
+        }
+    }
+}
+
+// Health and what an actor fights with. Damage is rolled between the minimum and maximum,
+// multiplied on a critical and cut by the target's resistance to its type in percent, where
+// negative resistances are weaknesses. Getters include the equipment bonus. The rng lives here so
+// every actor rolls its own attacks
+#[derive(Clone, Debug)]
+pub struct Combat {
+    max_health: i32,
+    health: i32,
+    damage: (i32, i32),
+    damage_type: DamageType,
+    accuracy: i32,
+    defense: i32,
+    crit_chance: f64,
+    crit_multiplier: f32,
+    range: BlockCoordSize,
+    resistances: Vec<(DamageType, i32)>,
+    bonus: StatBonus,
+    rng: Rng,
+}
+
+impl Combat {
+    pub fn new(max_health: i32, seed: u64) -> Combat {
+        let max_health = max_health.max(1);
+        Combat {
+            max_health: max_health,
+            health: max_health,
+            damage: (1, 1),
+            damage_type: DamageType::Physical,
+            accuracy: 10,
+            defense: 0,
+            crit_chance: 0.05,
+            crit_multiplier: 2.0,
+            range: 0,
+            resistances: vec!(),
+            bonus: StatBonus::new(),
+            rng: Rng::new(seed),
+        }
+    }
+
+    pub fn with_damage(mut self, min: i32, max: i32) -> Combat {
+        let min = min.max(0);
+        self.damage = (min, max.max(min));
+        self
+    }
+
+    pub fn with_damage_type(mut self, damage_type: DamageType) -> Combat {
+        self.damage_type = damage_type;
+        self
+    }
+
+    pub fn with_accuracy(mut self, accuracy: i32) -> Combat {
+        self.accuracy = accuracy.max(0);
+        self
+    }
+
+    pub fn with_defense(mut self, defense: i32) -> Combat {
+        self.defense = defense.max(0);
+        self
+    }
+
+    pub fn with_critical(mut self, chance: f64, multiplier: f32) -> Combat {
+        self.crit_chance = chance.max(0.0).min(1.0);
+        self.crit_multiplier = multiplier.max(1.0);
+        self
+    }
+
+    // How far ranged attacks reach, 0 for melee only
+    pub fn with_range(mut self, range: BlockCoordSize) -> Combat {
+        self.range = range.max(0);
+        self
+    }
+
+    pub fn with_resistance(mut self, damage_type: DamageType, percent: i32) -> Combat {
+        self.resistances.retain(|&(other, _)| other != damage_type);
+        self.resistances.push((damage_type, percent.min(100)));
+        self
+    }
+
+    pub fn with_health(mut self, health: i32) -> Combat {
+        self.health = health.min(self.max_health);
+        self
+    }
+
+    pub fn with_rng(mut self, rng: Rng) -> Combat {
+        self.rng = rng;
+        self
+    }
+
+    pub fn set_bonus(&mut self, bonus: StatBonus) {
+        self.bonus = bonus;
+    }
+
+    pub fn get_bonus(&self) -> &StatBonus {
+        &self.bonus
+    }
+
+    pub fn get_max_health(&self) -> i32 {
+        self.max_health
+    }
+
+    pub fn get_health(&self) -> i32 {
+        self.health
+    }
+
+    pub fn get_damage(&self) -> (i32, i32) {
+        let min = (self.damage.0 + self.bonus.damage.0).max(0);
+        (min, (self.damage.1 + self.bonus.damage.1).max(min))
+    }
+
+    pub fn get_damage_type(&self) -> DamageType {
+        self.damage_type
+    }
+
+    pub fn get_accuracy(&self) -> i32 {
+        (self.accuracy + self.bonus.accuracy).max(0)
+    }
+
+    pub fn get_defense(&self) -> i32 {
+        (self.defense + self.bonus.defense).max(0)
+    }
+
+    pub fn get_range(&self) -> BlockCoordSize {
+        (self.range + self.bonus.range).max(0)
+    }
+
+    pub fn get_resistance(&self, damage_type: DamageType) -> i32 {
+        let base = self.resistances.iter().find(|&&(other, _)| other == damage_type).map_or(0, |&(_, percent)| percent);
+        (base + self.bonus.get_resistance(damage_type)).min(100)
+    }
+
+    pub fn get_rng(&self) -> &Rng {
+        &self.rng
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.health <= 0
+    }
+
+    // Chance for an attack with this accuracy to land on this defense
+    pub fn get_hit_chance(accuracy: i32, defense: i32) -> f64 {
+        if accuracy + defense <= 0 {
+            return MAX_HIT_CHANCE;
+        }
+        (accuracy as f64 / (accuracy + defense) as f64).max(MIN_HIT_CHANCE).min(MAX_HIT_CHANCE)
+    }
+
+    // Rolls an attack against defender without applying it
+    pub fn roll_attack(&mut self, defender: &Combat) -> AttackResult {
+        if !self.rng.chance(Combat::get_hit_chance(self.get_accuracy(), defender.get_defense())) {
+            return AttackResult::Miss;
+        }
+        let (min, max) = self.get_damage();
+        let mut damage = self.rng.range(min as i64, max as i64 + 1) as f32;
+        let critical = self.rng.chance(self.crit_chance);
+        if critical {
+            damage *= self.crit_multiplier;
+        }
+        let resistance = defender.get_resistance(self.damage_type);
+        let damage = (damage * (100 - resistance) as f32 / 100.0).round().max(0.0) as i32;
+        AttackResult::Hit {
+            damage: damage,
+            damage_type: self.damage_type,
+            critical: critical,
+            killed: false,
+        }
+    }
+
+    // Applies a rolled attack, marking whether it killed
+    pub fn take_attack(&mut self, attack_result: AttackResult) -> AttackResult {
+        match attack_result {
+            AttackResult::Hit { damage, damage_type, critical, .. } => {
+                self.health -= damage;
+                AttackResult::Hit {
+                    damage: damage,
+                    damage_type: damage_type,
+                    critical: critical,
+                    killed: self.is_dead(),
+                }
+            },
+            AttackResult::Miss => AttackResult::Miss,
+        }
+    }
+
+    pub fn heal(&mut self, amount: i32) {
+        self.health = (self.health + amount.max(0)).min(self.max_health);
+    }
+}
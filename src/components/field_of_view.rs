@@ -0,0 +1,165 @@
+use std::collections::{HashSet};
+use std::error::Error;
+use std::fmt;
+
+use core::{RogueWorld, BlockCoordSize, BLOCK_REGISTRY_NAME, VOXEL_VOLUME_NAME};
+use components::{BlockCoords, BlockRegistry, VoxelVolume};
+use fov::{line_of_sight_volume, shadowcast_layer};
+use generation::{WALL_HEIGHT};
+
+pub static SIGHT_RADIUS: BlockCoordSize = 12;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Sight {
+    Unseen,
+    Remembered,
+    Visible,
+}
+
+// What its entity sees right now and has seen before. Each update bumps the version and keeps
+// the cells whose sight changed, so views can tell whether they need redrawing
+pub struct FieldOfView {
+    radius: BlockCoordSize,
+    origin: Option<BlockCoords>,
+    visible: HashSet<BlockCoords>,
+    remembered: HashSet<BlockCoords>,
+    changed: HashSet<BlockCoords>,
+    version: u64,
+}
+
+impl FieldOfView {
+    pub fn new(radius: BlockCoordSize) -> FieldOfView {
+        FieldOfView {
+            radius: radius,
+            origin: None,
+            visible: HashSet::new(),
+            remembered: HashSet::new(),
+            changed: HashSet::new(),
+            version: 0,
+        }
+    }
+
+    pub fn with_remembered(mut self, remembered: HashSet<BlockCoords>) -> FieldOfView {
+        self.remembered = remembered;
+        self
+    }
+
+    pub fn get_radius(&self) -> BlockCoordSize {
+        self.radius
+    }
+
+    pub fn get_origin(&self) -> Option<BlockCoords> {
+        self.origin
+    }
+
+    pub fn get_version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn get_visible(&self) -> &HashSet<BlockCoords> {
+        &self.visible
+    }
+
+    pub fn get_remembered(&self) -> &HashSet<BlockCoords> {
+        &self.remembered
+    }
+
+    pub fn get_changed(&self) -> &HashSet<BlockCoords> {
+        &self.changed
+    }
+
+    pub fn get_sight(&self, block_coords: &BlockCoords) -> Sight {
+        if self.visible.contains(block_coords) {
+            Sight::Visible
+        } else if self.remembered.contains(block_coords) {
+            Sight::Remembered
+        } else {
+            Sight::Unseen
+        }
+    }
+
+    pub fn is_visible(&self, block_coords: &BlockCoords) -> bool {
+        self.visible.contains(block_coords)
+    }
+
+    // Replaces what is visible, remembering everything that drops out of view
+    pub fn set_visible(&mut self, origin: BlockCoords, visible: HashSet<BlockCoords>) {
+        self.changed = self.visible.symmetric_difference(&visible).cloned().collect();
+        for block_coords in self.visible.drain() {
+            self.remembered.insert(block_coords);
+        }
+        for block_coords in visible.iter() {
+            self.remembered.remove(block_coords);
+        }
+        self.visible = visible;
+        self.origin = Some(origin);
+        self.version += 1;
+    }
+
+    pub fn update(&mut self, origin: BlockCoords, voxel_volume: &VoxelVolume, block_registry: &BlockRegistry) {
+        let visible = look(voxel_volume, block_registry, &origin, self.radius);
+        self.set_visible(origin, visible);
+    }
+
+    // Looks again whenever the entity has moved
+    pub fn tick_mut(&mut self, block_coords: &BlockCoords, world: &RogueWorld) -> Result<(), FieldOfViewErr> {
+        if self.origin == Some(*block_coords) {
+            return Ok(());
+        }
+        let block_registry = match world.get_entity_by_name(BLOCK_REGISTRY_NAME) {
+            Some(entity) => match entity.get_block_registry() {
+                Some(block_registry) => block_registry,
+                None => return Err(FieldOfViewErr::Get("Block Registry Entity Get Block Registry")),
+            },
+            None => return Ok(()),
+        };
+        let voxel_volume = match world.get_entity_by_name(VOXEL_VOLUME_NAME) {
+            Some(entity) => match entity.get_voxel_volume() {
+                Some(voxel_volume) => voxel_volume,
+                None => return Err(FieldOfViewErr::Get("Voxel Volume Entity Get Voxel Volume")),
+            },
+            None => return Ok(()),
+        };
+        self.update(*block_coords, &voxel_volume, &block_registry);
+        Ok(())
+    }
+}
+
+// Levels no taller than a floor and its walls, like dungeons and caves, are seen a layer at a
+// time by shadowcasting the one the origin stands in. Each cell seen lights its whole column so
+// floors and wall tops show with it. Anything taller needs sight in 3D
+fn look(voxel_volume: &VoxelVolume, block_registry: &BlockRegistry, origin: &BlockCoords, radius: BlockCoordSize) -> HashSet<BlockCoords> {
+    if voxel_volume.get_height() > WALL_HEIGHT + 1 {
+        return line_of_sight_volume(voxel_volume, block_registry, origin, radius);
+    }
+    let bottom = voxel_volume.get_origin().get_y();
+    let top = bottom + voxel_volume.get_height() as BlockCoordSize;
+    let mut visible = HashSet::new();
+    for block_coords in shadowcast_layer(voxel_volume, block_registry, origin, radius) {
+        for y in bottom..top {
+            visible.insert(BlockCoords::new(block_coords.get_x(), y, block_coords.get_z()));
+        }
+    }
+    visible
+}
+
+#[derive(Debug)]
+pub enum FieldOfViewErr {
+    Get(&'static str),
+}
+
+impl fmt::Display for FieldOfViewErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FieldOfViewErr::Get(_) => write!(f, "Get was None"),
+        }
+    }
+}
+
+impl Error for FieldOfViewErr {
+    fn description(&self) -> &str {
+        match *self {
+            FieldOfViewErr::Get(_) => "Get was None",
+        }
+    }
+}
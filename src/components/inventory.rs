@@ -0,0 +1,154 @@
+use std::error::Error;
+use std::fmt;
+
+use dorp::{Id};
+
+use components::{Item, EquipSlot, StatBonus};
+
+// What an actor carries, limited by how many things and how much weight. Equipped items stay
+// in the inventory and count towards both limits
+#[derive(Clone, Debug)]
+pub struct Inventory {
+    items: Vec<(Id, Item)>,
+    equipped: Vec<(EquipSlot, Id)>,
+    max_slots: usize,
+    max_weight: f32,
+}
+
+impl Inventory {
+    pub fn new(max_slots: usize, max_weight: f32) -> Inventory {
+        Inventory {
+            items: vec!(),
+            equipped: vec!(),
+            max_slots: max_slots,
+            max_weight: max_weight.max(0.0),
+        }
+    }
+
+    pub fn get_items(&self) -> &Vec<(Id, Item)> {
+        &self.items
+    }
+
+    pub fn get_equipped(&self) -> &Vec<(EquipSlot, Id)> {
+        &self.equipped
+    }
+
+    pub fn get_max_slots(&self) -> usize {
+        self.max_slots
+    }
+
+    pub fn get_max_weight(&self) -> f32 {
+        self.max_weight
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn get_weight(&self) -> f32 {
+        self.items.iter().map(|&(_, ref item)| item.get_weight()).sum()
+    }
+
+    pub fn get(&self, id: Id) -> Option<&Item> {
+        self.items.iter().find(|&&(other, _)| other == id).map(|&(_, ref item)| item)
+    }
+
+    pub fn get_equipped_in(&self, equip_slot: EquipSlot) -> Option<Id> {
+        self.equipped.iter().find(|&&(other, _)| other == equip_slot).map(|&(_, id)| id)
+    }
+
+    pub fn is_equipped(&self, id: Id) -> bool {
+        self.equipped.iter().any(|&(_, other)| other == id)
+    }
+
+    pub fn check_add(&self, item: &Item) -> Result<(), InventoryErr> {
+        if self.items.len() >= self.max_slots {
+            return Err(InventoryErr::Full("Inventory Check Add Slots"));
+        }
+        if self.get_weight() + item.get_weight() > self.max_weight {
+            return Err(InventoryErr::TooHeavy("Inventory Check Add Weight"));
+        }
+        Ok(())
+    }
+
+    pub fn add(&mut self, id: Id, item: Item) -> Result<(), InventoryErr> {
+        match self.check_add(&item) {
+            Ok(()) => (),
+            Err(err) => return Err(err),
+        }
+        self.items.push((id, item));
+        Ok(())
+    }
+
+    // Takes the item out, unequipping it first
+    pub fn remove(&mut self, id: Id) -> Result<Item, InventoryErr> {
+        match self.items.iter().position(|&(other, _)| other == id) {
+            Some(index) => {
+                self.equipped.retain(|&(_, other)| other != id);
+                Ok(self.items.remove(index).1)
+            },
+            None => Err(InventoryErr::NotCarried("Inventory Remove")),
+        }
+    }
+
+    // Wears the item in its slot, returning whatever was there before
+    pub fn equip(&mut self, id: Id) -> Result<Option<Id>, InventoryErr> {
+        let equip_slot = match self.get(id) {
+            Some(item) => match item.get_item_def().get_slot() {
+                Some(equip_slot) => equip_slot,
+                None => return Err(InventoryErr::NotEquippable("Inventory Equip")),
+            },
+            None => return Err(InventoryErr::NotCarried("Inventory Equip")),
+        };
+        let previous = self.unequip(equip_slot);
+        self.equipped.push((equip_slot, id));
+        Ok(previous)
+    }
+
+    pub fn unequip(&mut self, equip_slot: EquipSlot) -> Option<Id> {
+        let previous = self.get_equipped_in(equip_slot);
+        self.equipped.retain(|&(other, _)| other != equip_slot);
+        previous
+    }
+
+    // Everything equipped added together
+    pub fn get_bonus(&self) -> StatBonus {
+        let mut bonus = StatBonus::new();
+        for &(_, id) in self.equipped.iter() {
+            if let Some(item) = self.get(id) {
+                bonus.add(item.get_item_def().get_bonus());
+            }
+        }
+        bonus
+    }
+}
+
+#[derive(Debug)]
+pub enum InventoryErr {
+    Full(&'static str),
+    TooHeavy(&'static str),
+    NotCarried(&'static str),
+    NotEquippable(&'static str),
+}
+
+impl fmt::Display for InventoryErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InventoryErr::Full(_) => write!(f, "Inventory is full"),
+            InventoryErr::TooHeavy(_) => write!(f, "Item is too heavy"),
+            InventoryErr::NotCarried(_) => write!(f, "Item is not carried"),
+            InventoryErr::NotEquippable(_) => write!(f, "Item can't be equipped"),
+        }
+    }
+}
+
+impl Error for InventoryErr {
+    fn description(&self) -> &str {
+        match *self {
+            InventoryErr::Full(_) => "Inventory is full",
+            InventoryErr::TooHeavy(_) => "Item is too heavy",
+            InventoryErr::NotCarried(_) => "Item is not carried",
+            InventoryErr::NotEquippable(_) => "Item can't be equipped",
+        }
+    }
+}
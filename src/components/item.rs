@@ -0,0 +1,27 @@
+use components::{ItemDef};
+
+// One item, lying on a cell while the entity has block coords and carried otherwise
+#[derive(Clone, Debug)]
+pub struct Item {
+    item_def: ItemDef,
+}
+
+impl Item {
+    pub fn new(item_def: ItemDef) -> Item {
+        Item {
+            item_def: item_def,
+        }
+    }
+
+    pub fn get_item_def(&self) -> &ItemDef {
+        &self.item_def
+    }
+
+    pub fn get_name(&self) -> &str {
+        self.item_def.get_name()
+    }
+
+    pub fn get_weight(&self) -> f32 {
+        self.item_def.get_weight()
+    }
+}
@@ -0,0 +1,52 @@
+use std::collections::{HashMap};
+
+use dorp::{Id};
+
+use components::{BlockCoords};
+
+// Items lying on each cell, in the order they were put down
+pub struct ItemMap {
+    cells: HashMap<BlockCoords, Vec<Id>>,
+}
+
+impl ItemMap {
+    pub fn new() -> ItemMap {
+        ItemMap {
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn get_at(&self, block_coords: &BlockCoords) -> Vec<Id> {
+        self.cells.get(block_coords).cloned().unwrap_or(vec!())
+    }
+
+    pub fn insert(&mut self, block_coords: &BlockCoords, id: Id) {
+        self.cells.entry(*block_coords).or_insert(vec!()).push(id);
+    }
+
+    pub fn remove(&mut self, block_coords: &BlockCoords, id: Id) -> bool {
+        let (found, empty) = match self.cells.get_mut(block_coords) {
+            Some(ids) => {
+                let before = ids.len();
+                ids.retain(|other| *other != id);
+                (ids.len() != before, ids.is_empty())
+            },
+            None => (false, false),
+        };
+        if empty {
+            self.cells.remove(block_coords);
+        }
+        found
+    }
+
+    // Cells with items on them, sorted so the order is the same every run
+    pub fn get_cells(&self) -> Vec<BlockCoords> {
+        let mut cells: Vec<BlockCoords> = self.cells.keys().cloned().collect();
+        cells.sort_by_key(|block_coords| (block_coords.get_x(), block_coords.get_y(), block_coords.get_z()));
+        cells
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.values().map(|ids| ids.len()).sum()
+    }
+}
@@ -0,0 +1,219 @@
+use std::error::Error;
+use std::fmt;
+use std::str::{FromStr};
+
+use core::{Config, ConfigErr, ConfigSection};
+use components::{StatBonus, DamageType};
+
+pub static ITEMS_PATH: &'static str = "assets/items.cfg";
+
+static EQUIP_SLOT_NAMES: [&'static str; 4] = ["weapon", "head", "body", "feet"];
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum EquipSlot {
+    Weapon,
+    Head,
+    Body,
+    Feet,
+}
+
+impl EquipSlot {
+    pub fn get_all() -> Vec<EquipSlot> {
+        vec!(EquipSlot::Weapon, EquipSlot::Head, EquipSlot::Body, EquipSlot::Feet)
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match *self {
+            EquipSlot::Weapon => EQUIP_SLOT_NAMES[0],
+            EquipSlot::Head => EQUIP_SLOT_NAMES[1],
+            EquipSlot::Body => EQUIP_SLOT_NAMES[2],
+            EquipSlot::Feet => EQUIP_SLOT_NAMES[3],
+        }
+    }
+}
+
+impl FromStr for EquipSlot {
+    type Err = ();
+
+    fn from_str(name: &str) -> Result<EquipSlot, ()> {
+        match EquipSlot::get_all().into_iter().find(|equip_slot| equip_slot.get_name() == name) {
+            Some(equip_slot) => Ok(equip_slot),
+            None => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ItemDef {
+    name: String,
+    weight: f32,
+    slot: Option<EquipSlot>,
+    bonus: StatBonus,
+    heal: i32,
+    sprite: Option<String>,
+}
+
+impl ItemDef {
+    fn from_section(section: &ConfigSection) -> Result<ItemDef, ConfigErr> {
+        let weight = match section.get_parsed("weight", 1.0) {
+            Ok(weight) => weight,
+            Err(err) => return Err(err),
+        };
+        let slot = match section.get_entry("slot") {
+            Some(entry) => match entry.get_value().parse() {
+                Ok(slot) => Some(slot),
+                Err(()) => return Err(ConfigErr::Value(entry.get_line(), entry.get_key().to_string(), entry.get_value().to_string())),
+            },
+            None => None,
+        };
+        let mut bonus = StatBonus::new();
+        match section.get_entry("damage") {
+            Some(entry) => match entry.parse_list::<i32>() {
+                Ok(ref damage) if damage.len() == 1 => bonus = bonus.with_damage(damage[0], damage[0]),
+                Ok(ref damage) if damage.len() == 2 && damage[0] <= damage[1] => bonus = bonus.with_damage(damage[0], damage[1]),
+                Ok(_) => return Err(ConfigErr::Value(entry.get_line(), entry.get_key().to_string(), entry.get_value().to_string())),
+                Err(err) => return Err(err),
+            },
+            None => (),
+        }
+        match section.get_parsed("accuracy", 0) {
+            Ok(accuracy) => bonus = bonus.with_accuracy(accuracy),
+            Err(err) => return Err(err),
+        }
+        match section.get_parsed("defense", 0) {
+            Ok(defense) => bonus = bonus.with_defense(defense),
+            Err(err) => return Err(err),
+        }
+        match section.get_parsed("range", 0) {
+            Ok(range) => bonus = bonus.with_range(range),
+            Err(err) => return Err(err),
+        }
+        for damage_type in DamageType::get_all() {
+            match section.get_parsed(&format!("resist_{}", damage_type.get_name()), 0) {
+                Ok(0) => (),
+                Ok(percent) => bonus = bonus.with_resistance(damage_type, percent),
+                Err(err) => return Err(err),
+            }
+        }
+        let heal = match section.get_parsed("heal", 0) {
+            Ok(heal) => heal,
+            Err(err) => return Err(err),
+        };
+        if weight < 0.0 {
+            return Err(ConfigErr::Value(section.get_line(), "weight".to_string(), weight.to_string()));
+        }
+        if heal < 0 {
+            return Err(ConfigErr::Value(section.get_line(), "heal".to_string(), heal.to_string()));
+        }
+        if slot.is_none() && bonus != StatBonus::new() {
+            return Err(ConfigErr::Value(section.get_line(), "slot".to_string(), String::new()));
+        }
+        Ok(ItemDef {
+            name: section.get_name().to_string(),
+            weight: weight,
+            slot: slot,
+            bonus: bonus,
+            heal: heal,
+            sprite: section.get("sprite").map(|sprite| sprite.to_string()),
+        })
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_weight(&self) -> f32 {
+        self.weight
+    }
+
+    // Where it is worn, None for things that can't be equipped
+    pub fn get_slot(&self) -> Option<EquipSlot> {
+        self.slot
+    }
+
+    // Added to the wearer's combat stats while equipped
+    pub fn get_bonus(&self) -> &StatBonus {
+        &self.bonus
+    }
+
+    // Health restored when used up, 0 for things that can't be used
+    pub fn get_heal(&self) -> i32 {
+        self.heal
+    }
+
+    pub fn is_usable(&self) -> bool {
+        self.heal > 0
+    }
+
+    pub fn get_sprite(&self) -> Option<&str> {
+        self.sprite.as_ref().map(|sprite| sprite.as_str())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ItemRegistry {
+    defs: Vec<ItemDef>,
+}
+
+impl ItemRegistry {
+    pub fn load(path: &str) -> Result<ItemRegistry, ItemRegistryErr> {
+        match Config::load(path) {
+            Ok(config) => ItemRegistry::from_config(&config),
+            Err(err) => Err(ItemRegistryErr::Config("Config Load Path", err)),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<ItemRegistry, ItemRegistryErr> {
+        match Config::parse(text) {
+            Ok(config) => ItemRegistry::from_config(&config),
+            Err(err) => Err(ItemRegistryErr::Config("Config Parse Text", err)),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Result<ItemRegistry, ItemRegistryErr> {
+        let mut defs = vec!();
+        for section in config.get_sections() {
+            match ItemDef::from_section(section) {
+                Ok(item_def) => defs.push(item_def),
+                Err(err) => return Err(ItemRegistryErr::Config("Item Def From Section", err)),
+            }
+        }
+        Ok(ItemRegistry {
+            defs: defs,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.defs.len()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ItemDef> {
+        self.defs.iter().find(|item_def| item_def.get_name() == name)
+    }
+
+    // In file order
+    pub fn get_defs(&self) -> &Vec<ItemDef> {
+        &self.defs
+    }
+}
+
+#[derive(Debug)]
+pub enum ItemRegistryErr {
+    Config(&'static str, ConfigErr),
+}
+
+impl fmt::Display for ItemRegistryErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ItemRegistryErr::Config(_, ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for ItemRegistryErr {
+    fn description(&self) -> &str {
+        match *self {
+            ItemRegistryErr::Config(_, ref err) => err.description(),
+        }
+    }
+}
@@ -1,7 +1,35 @@
+pub mod actor;
 pub mod block;
 pub mod block_coords;
 pub mod block_map;
+pub mod block_registry;
+pub mod camera;
+pub mod chunk;
+pub mod chunk_view;
+pub mod combat;
+pub mod field_of_view;
+pub mod inventory;
+pub mod item;
+pub mod item_map;
+pub mod item_registry;
+pub mod monster_registry;
+pub mod sprite;
+pub mod voxel_volume;
 
+pub use self::actor::{Actor, ACTION_COST, NORMAL_SPEED};
 pub use self::block::{Block, BlockErr, BlockType};
 pub use self::block_coords::{BlockCoords};
 pub use self::block_map::{BlockMap};
+pub use self::block_registry::{BlockRegistry, BlockRegistryErr, BlockDef, BLOCKS_PATH};
+pub use self::camera::{Camera, CameraErr};
+pub use self::chunk::{Chunk, ChunkCoords, CHUNK_SIZE, CHUNK_VOLUME};
+pub use self::chunk_view::{ChunkView, ChunkViewErr};
+pub use self::combat::{Combat, DamageType, AttackResult, StatBonus};
+pub use self::field_of_view::{FieldOfView, FieldOfViewErr, Sight, SIGHT_RADIUS};
+pub use self::inventory::{Inventory, InventoryErr};
+pub use self::item::{Item};
+pub use self::item_map::{ItemMap};
+pub use self::item_registry::{ItemRegistry, ItemRegistryErr, ItemDef, EquipSlot, ITEMS_PATH};
+pub use self::monster_registry::{MonsterRegistry, MonsterRegistryErr, MonsterDef, MONSTERS_PATH};
+pub use self::sprite::{Sprite, SpriteSheet};
+pub use self::voxel_volume::{VoxelVolume};
@@ -0,0 +1,284 @@
+use std::error::Error;
+use std::fmt;
+
+use core::{Config, ConfigErr, ConfigSection, BlockCoordSize};
+use components::{Combat, DamageType, NORMAL_SPEED};
+
+pub static MONSTERS_PATH: &'static str = "assets/monsters.cfg";
+
+#[derive(Clone, Debug)]
+pub struct MonsterDef {
+    name: String,
+    speed: i32,
+    sight: BlockCoordSize,
+    memory: u32,
+    wander_chance: f64,
+    wander_radius: BlockCoordSize,
+    leash: BlockCoordSize,
+    timid: bool,
+    opens_doors: bool,
+    sprite: Option<String>,
+    health: i32,
+    damage: (i32, i32),
+    damage_type: DamageType,
+    accuracy: i32,
+    defense: i32,
+    crit_chance: f64,
+    crit_multiplier: f32,
+    range: BlockCoordSize,
+    resistances: Vec<(DamageType, i32)>,
+    flee_health: f64,
+}
+
+impl MonsterDef {
+    fn from_section(section: &ConfigSection) -> Result<MonsterDef, ConfigErr> {
+        let speed = match section.get_parsed("speed", NORMAL_SPEED) {
+            Ok(speed) => speed,
+            Err(err) => return Err(err),
+        };
+        let sight = match section.get_parsed("sight", 8) {
+            Ok(sight) => sight,
+            Err(err) => return Err(err),
+        };
+        let memory = match section.get_parsed("memory", 5) {
+            Ok(memory) => memory,
+            Err(err) => return Err(err),
+        };
+        let wander_chance = match section.get_parsed("wander_chance", 0.0) {
+            Ok(wander_chance) => wander_chance,
+            Err(err) => return Err(err),
+        };
+        let wander_radius = match section.get_parsed("wander_radius", 4) {
+            Ok(wander_radius) => wander_radius,
+            Err(err) => return Err(err),
+        };
+        let leash = match section.get_parsed("leash", 16) {
+            Ok(leash) => leash,
+            Err(err) => return Err(err),
+        };
+        let timid = match section.get_parsed("timid", false) {
+            Ok(timid) => timid,
+            Err(err) => return Err(err),
+        };
+        let opens_doors = match section.get_parsed("opens_doors", false) {
+            Ok(opens_doors) => opens_doors,
+            Err(err) => return Err(err),
+        };
+        let health = match section.get_parsed("health", 10) {
+            Ok(health) => health,
+            Err(err) => return Err(err),
+        };
+        let damage = match section.get_entry("damage") {
+            Some(entry) => match entry.parse_list::<i32>() {
+                Ok(ref damage) if damage.len() == 1 && damage[0] >= 0 => (damage[0], damage[0]),
+                Ok(ref damage) if damage.len() == 2 && damage[0] >= 0 && damage[0] <= damage[1] => (damage[0], damage[1]),
+                Ok(_) => return Err(ConfigErr::Value(entry.get_line(), entry.get_key().to_string(), entry.get_value().to_string())),
+                Err(err) => return Err(err),
+            },
+            None => (1, 3),
+        };
+        let damage_type = match section.get_entry("damage_type") {
+            Some(entry) => match entry.get_value().parse() {
+                Ok(damage_type) => damage_type,
+                Err(()) => return Err(ConfigErr::Value(entry.get_line(), entry.get_key().to_string(), entry.get_value().to_string())),
+            },
+            None => DamageType::Physical,
+        };
+        let accuracy = match section.get_parsed("accuracy", 10) {
+            Ok(accuracy) => accuracy,
+            Err(err) => return Err(err),
+        };
+        let defense = match section.get_parsed("defense", 0) {
+            Ok(defense) => defense,
+            Err(err) => return Err(err),
+        };
+        let crit_chance = match section.get_parsed("crit_chance", 0.05) {
+            Ok(crit_chance) => crit_chance,
+            Err(err) => return Err(err),
+        };
+        let crit_multiplier = match section.get_parsed("crit_multiplier", 2.0) {
+            Ok(crit_multiplier) => crit_multiplier,
+            Err(err) => return Err(err),
+        };
+        let range = match section.get_parsed("range", 0) {
+            Ok(range) => range,
+            Err(err) => return Err(err),
+        };
+        let mut resistances = vec!();
+        for damage_type in DamageType::get_all() {
+            match section.get_parsed(&format!("resist_{}", damage_type.get_name()), 0) {
+                Ok(0) => (),
+                Ok(percent) => resistances.push((damage_type, percent)),
+                Err(err) => return Err(err),
+            }
+        }
+        let flee_health = match section.get_parsed("flee_health", 0.0) {
+            Ok(flee_health) => flee_health,
+            Err(err) => return Err(err),
+        };
+        if health <= 0 {
+            return Err(ConfigErr::Value(section.get_line(), "health".to_string(), health.to_string()));
+        }
+        if speed <= 0 {
+            return Err(ConfigErr::Value(section.get_line(), "speed".to_string(), speed.to_string()));
+        }
+        if wander_chance < 0.0 || wander_chance > 1.0 {
+            return Err(ConfigErr::Value(section.get_line(), "wander_chance".to_string(), wander_chance.to_string()));
+        }
+        Ok(MonsterDef {
+            name: section.get_name().to_string(),
+            speed: speed,
+            sight: sight,
+            memory: memory,
+            wander_chance: wander_chance,
+            wander_radius: wander_radius,
+            leash: leash,
+            timid: timid,
+            opens_doors: opens_doors,
+            sprite: section.get("sprite").map(|sprite| sprite.to_string()),
+            health: health,
+            damage: damage,
+            damage_type: damage_type,
+            accuracy: accuracy,
+            defense: defense,
+            crit_chance: crit_chance,
+            crit_multiplier: crit_multiplier,
+            range: range,
+            resistances: resistances,
+            flee_health: flee_health,
+        })
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_speed(&self) -> i32 {
+        self.speed
+    }
+
+    // How far it sees the player
+    pub fn get_sight(&self) -> BlockCoordSize {
+        self.sight
+    }
+
+    // Turns it keeps after the player out of sight before giving up
+    pub fn get_memory(&self) -> u32 {
+        self.memory
+    }
+
+    // Chance each idle turn of setting off somewhere near home
+    pub fn get_wander_chance(&self) -> f64 {
+        self.wander_chance
+    }
+
+    pub fn get_wander_radius(&self) -> BlockCoordSize {
+        self.wander_radius
+    }
+
+    // How far from home it follows the player before heading back
+    pub fn get_leash(&self) -> BlockCoordSize {
+        self.leash
+    }
+
+    // Runs from the player instead of hunting it
+    pub fn is_timid(&self) -> bool {
+        self.timid
+    }
+
+    pub fn opens_doors(&self) -> bool {
+        self.opens_doors
+    }
+
+    pub fn get_sprite(&self) -> Option<&str> {
+        self.sprite.as_ref().map(|sprite| sprite.as_str())
+    }
+
+    // Fraction of its health below which it runs instead of fighting
+    pub fn get_flee_health(&self) -> f64 {
+        self.flee_health
+    }
+
+    // Fresh combat stats for one monster of this type
+    pub fn new_combat(&self, seed: u64) -> Combat {
+        let mut combat = Combat::new(self.health, seed)
+            .with_damage(self.damage.0, self.damage.1)
+            .with_damage_type(self.damage_type)
+            .with_accuracy(self.accuracy)
+            .with_defense(self.defense)
+            .with_critical(self.crit_chance, self.crit_multiplier)
+            .with_range(self.range);
+        for &(damage_type, percent) in self.resistances.iter() {
+            combat = combat.with_resistance(damage_type, percent);
+        }
+        combat
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MonsterRegistry {
+    defs: Vec<MonsterDef>,
+}
+
+impl MonsterRegistry {
+    pub fn load(path: &str) -> Result<MonsterRegistry, MonsterRegistryErr> {
+        match Config::load(path) {
+            Ok(config) => MonsterRegistry::from_config(&config),
+            Err(err) => Err(MonsterRegistryErr::Config("Config Load Path", err)),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<MonsterRegistry, MonsterRegistryErr> {
+        match Config::parse(text) {
+            Ok(config) => MonsterRegistry::from_config(&config),
+            Err(err) => Err(MonsterRegistryErr::Config("Config Parse Text", err)),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Result<MonsterRegistry, MonsterRegistryErr> {
+        let mut defs = vec!();
+        for section in config.get_sections() {
+            match MonsterDef::from_section(section) {
+                Ok(monster_def) => defs.push(monster_def),
+                Err(err) => return Err(MonsterRegistryErr::Config("Monster Def From Section", err)),
+            }
+        }
+        Ok(MonsterRegistry {
+            defs: defs,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.defs.len()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&MonsterDef> {
+        self.defs.iter().find(|monster_def| monster_def.get_name() == name)
+    }
+
+    // In file order
+    pub fn get_defs(&self) -> &Vec<MonsterDef> {
+        &self.defs
+    }
+}
+
+#[derive(Debug)]
+pub enum MonsterRegistryErr {
+    Config(&'static str, ConfigErr),
+}
+
+impl fmt::Display for MonsterRegistryErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MonsterRegistryErr::Config(_, ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for MonsterRegistryErr {
+    fn description(&self) -> &str {
+        match *self {
+            MonsterRegistryErr::Config(_, ref err) => err.description(),
+        }
+    }
+}
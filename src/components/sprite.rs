@@ -0,0 +1,197 @@
+use std::sync::{Arc};
+
+use dorp::{Id, IdManager, IdType, TickCount};
+use image::{RgbaImage};
+
+use components::{BlockCoords};
+use core::{Direction};
+use graphics::{BillboardView, ScreenFacing, UvRect};
+
+// Frames of an animation run along a row. A sheet with four rows holds one row per
+// ScreenFacing in declaration order, two rows hold facing toward and away from the camera
+// and are mirrored for beings facing left, any other sheet mirrors its first row
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SpriteSheet {
+    columns: u32,
+    rows: u32,
+}
+
+impl SpriteSheet {
+    pub fn new(columns: u32, rows: u32) -> SpriteSheet {
+        SpriteSheet {
+            columns: columns.max(1),
+            rows: rows.max(1),
+        }
+    }
+
+    pub fn get_columns(&self) -> u32 {
+        self.columns
+    }
+
+    pub fn get_rows(&self) -> u32 {
+        self.rows
+    }
+
+    pub fn get_frame_rect(&self, column: u32, row: u32) -> UvRect {
+        let column = column % self.columns;
+        let row = row % self.rows;
+        let whole = UvRect::new([0.0, 0.0], [1.0, 1.0]);
+        let min = whole.get_uv(column as f32 / self.columns as f32, row as f32 / self.rows as f32);
+        let max = whole.get_uv((column + 1) as f32 / self.columns as f32, (row + 1) as f32 / self.rows as f32);
+        UvRect::new(min, max)
+    }
+}
+
+pub struct Sprite {
+    image_name: String,
+    image: Arc<RgbaImage>,
+    sheet: SpriteSheet,
+    frame: u32,
+    ticks_per_frame: TickCount,
+    facing: Direction,
+    block_coords: Option<BlockCoords>,
+    view: Option<BillboardView>,
+    scale: [f32; 3],
+    dirty: bool,
+    vertex_id: Id,
+    texture_id: Id,
+    model_id: Id,
+}
+
+impl Sprite {
+    pub fn new(image_name: &str, image: Arc<RgbaImage>, sheet: SpriteSheet, manager: &mut IdManager) -> Sprite {
+        Sprite {
+            image_name: image_name.to_string(),
+            image: image,
+            sheet: sheet,
+            frame: 0,
+            ticks_per_frame: 8,
+            facing: Direction::South,
+            block_coords: None,
+            view: None,
+            scale: [1.0, 1.0, 1.0],
+            dirty: true,
+            vertex_id: Id::new(manager, IdType::Vertex),
+            texture_id: Id::new(manager, IdType::Texture),
+            model_id: Id::new(manager, IdType::Matrix),
+        }
+    }
+
+    pub fn with_ticks_per_frame(mut self, ticks_per_frame: TickCount) -> Sprite {
+        self.ticks_per_frame = ticks_per_frame.max(1);
+        self
+    }
+
+    // Size of one block on screen, baked into the quad as the sprite has no Transform of its own
+    pub fn with_scale(mut self, scale: [f32; 3]) -> Sprite {
+        self.scale = scale;
+        self
+    }
+
+    pub fn get_scale(&self) -> [f32; 3] {
+        self.scale
+    }
+
+    pub fn get_block_coords(&self) -> Option<BlockCoords> {
+        self.block_coords
+    }
+
+    pub fn set_block_coords(&mut self, block_coords: BlockCoords) {
+        if self.block_coords != Some(block_coords) {
+            self.block_coords = Some(block_coords);
+            self.dirty = true;
+        }
+    }
+
+    pub fn get_view(&self) -> Option<BillboardView> {
+        self.view
+    }
+
+    pub fn set_view(&mut self, view: BillboardView) {
+        if self.view != Some(view) {
+            self.view = Some(view);
+            self.dirty = true;
+        }
+    }
+
+    pub fn get_image_name(&self) -> &str {
+        &self.image_name
+    }
+
+    pub fn get_image(&self) -> Arc<RgbaImage> {
+        self.image.clone()
+    }
+
+    pub fn get_sheet(&self) -> SpriteSheet {
+        self.sheet
+    }
+
+    pub fn get_frame(&self) -> u32 {
+        self.frame
+    }
+
+    pub fn get_facing(&self) -> Direction {
+        self.facing
+    }
+
+    pub fn set_facing(&mut self, facing: Direction) {
+        if self.facing != facing {
+            self.facing = facing;
+            self.dirty = true;
+        }
+    }
+
+    pub fn tick(&mut self, tick_count: TickCount) {
+        let frame = ((tick_count / self.ticks_per_frame) % self.sheet.get_columns() as TickCount) as u32;
+        if frame != self.frame {
+            self.frame = frame;
+            self.dirty = true;
+        }
+    }
+
+    // Set whenever the frame, facing, cell or view changes so the renderable knows to rebuild
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    pub fn set_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    pub fn set_clean(&mut self) {
+        self.dirty = false;
+    }
+
+    // Current frame within the sprite's image and whether to mirror it
+    pub fn get_frame_rect(&self, view: &BillboardView) -> (UvRect, bool) {
+        let screen_facing = view.get_screen_facing(self.facing);
+        match self.sheet.get_rows() {
+            4 => {
+                let row = match screen_facing {
+                    ScreenFacing::TowardLeft => 0,
+                    ScreenFacing::TowardRight => 1,
+                    ScreenFacing::AwayLeft => 2,
+                    ScreenFacing::AwayRight => 3,
+                };
+                (self.sheet.get_frame_rect(self.frame, row), false)
+            },
+            2 => {
+                let row = if screen_facing.is_away() { 1 } else { 0 };
+                (self.sheet.get_frame_rect(self.frame, row), screen_facing.is_left())
+            },
+            _ => (self.sheet.get_frame_rect(self.frame, 0), screen_facing.is_left()),
+        }
+    }
+
+    pub fn get_vertex_id(&self) -> Id {
+        self.vertex_id
+    }
+
+    pub fn get_texture_id(&self) -> Id {
+        self.texture_id
+    }
+
+    pub fn get_model_id(&self) -> Id {
+        self.model_id
+    }
+}
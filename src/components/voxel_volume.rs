@@ -0,0 +1,156 @@
+use core::{BlockCoordSize};
+use components::{BlockCoords, BlockType, BlockRegistry, ChunkCoords, CHUNK_SIZE, CHUNK_VOLUME};
+
+#[derive(Clone)]
+pub struct VoxelVolume {
+    origin: BlockCoords,
+    width: usize,
+    height: usize,
+    depth: usize,
+    blocks: Vec<BlockType>,
+}
+
+impl VoxelVolume {
+    pub fn new(origin: BlockCoords, width: usize, height: usize, depth: usize) -> VoxelVolume {
+        VoxelVolume {
+            origin: origin,
+            width: width,
+            height: height,
+            depth: depth,
+            blocks: vec!(BlockType::AIR; width * height * depth),
+        }
+    }
+
+    pub fn get_origin(&self) -> BlockCoords {
+        self.origin
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get_depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn get_len(&self) -> usize {
+        self.blocks.len()
+    }
+
+    // Last block coords inside the volume on every axis
+    pub fn get_max(&self) -> BlockCoords {
+        self.origin.offset(self.width as BlockCoordSize - 1, self.height as BlockCoordSize - 1, self.depth as BlockCoordSize - 1)
+    }
+
+    pub fn contains(&self, block_coords: &BlockCoords) -> bool {
+        self.get_index(block_coords).is_some()
+    }
+
+    pub fn get_index(&self, block_coords: &BlockCoords) -> Option<usize> {
+        let x = block_coords.get_x() - self.origin.get_x();
+        let y = block_coords.get_y() - self.origin.get_y();
+        let z = block_coords.get_z() - self.origin.get_z();
+        if x < 0 || y < 0 || z < 0 || x >= self.width as BlockCoordSize || y >= self.height as BlockCoordSize || z >= self.depth as BlockCoordSize {
+            return None;
+        }
+        Some((z as usize * self.height + y as usize) * self.width + x as usize)
+    }
+
+    pub fn get_coords(&self, index: usize) -> BlockCoords {
+        let x = index % self.width;
+        let y = (index / self.width) % self.height;
+        let z = index / (self.width * self.height);
+        self.origin.offset(x as BlockCoordSize, y as BlockCoordSize, z as BlockCoordSize)
+    }
+
+    // Cells outside the volume read as air
+    pub fn get(&self, block_coords: &BlockCoords) -> BlockType {
+        match self.get_index(block_coords) {
+            Some(index) => self.blocks[index],
+            None => BlockType::AIR,
+        }
+    }
+
+    pub fn set(&mut self, block_coords: &BlockCoords, block_type: BlockType) -> Option<BlockType> {
+        match self.get_index(block_coords) {
+            Some(index) => {
+                let old = self.blocks[index];
+                self.blocks[index] = block_type;
+                Some(old)
+            },
+            None => None,
+        }
+    }
+
+    // Fills the inclusive box between min and max, clipped to the volume
+    pub fn fill(&mut self, min: &BlockCoords, max: &BlockCoords, block_type: BlockType) {
+        for z in min.get_z()..max.get_z() + 1 {
+            for y in min.get_y()..max.get_y() + 1 {
+                for x in min.get_x()..max.get_x() + 1 {
+                    self.set(&BlockCoords::new(x, y, z), block_type);
+                }
+            }
+        }
+    }
+
+    pub fn count(&self, block_type: BlockType) -> usize {
+        self.blocks.iter().filter(|block| **block == block_type).count()
+    }
+
+    pub fn get_blocks(&self) -> &Vec<BlockType> {
+        &self.blocks
+    }
+
+    pub fn get_special_blocks(&self, block_registry: &BlockRegistry) -> Vec<(BlockCoords, BlockType)> {
+        self.blocks.iter().enumerate()
+            .filter(|&(_, block_type)| block_registry.is_special(*block_type))
+            .map(|(index, block_type)| (self.get_coords(index), *block_type))
+            .collect()
+    }
+
+    // Every chunk the volume overlaps, z slowest then y then x
+    pub fn get_chunks(&self) -> Vec<ChunkCoords> {
+        let min = self.origin.get_chunk_coords();
+        let max = self.get_max().get_chunk_coords();
+        let mut chunks = vec!();
+        for z in min.get_z()..max.get_z() + 1 {
+            for y in min.get_y()..max.get_y() + 1 {
+                for x in min.get_x()..max.get_x() + 1 {
+                    chunks.push(ChunkCoords::new(x, y, z));
+                }
+            }
+        }
+        chunks
+    }
+
+    // Blocks of one chunk in the order Chunk uses, air where the chunk hangs over the edge
+    pub fn get_chunk(&self, chunk_coords: &ChunkCoords) -> Vec<BlockType> {
+        let size = CHUNK_SIZE as usize;
+        let origin = chunk_coords.get_origin();
+        let mut blocks = Vec::with_capacity(CHUNK_VOLUME);
+        for z in 0..size {
+            for y in 0..size {
+                for x in 0..size {
+                    blocks.push(self.get(&origin.offset(x as BlockCoordSize, y as BlockCoordSize, z as BlockCoordSize)));
+                }
+            }
+        }
+        blocks
+    }
+
+    // Writes blocks laid out like get_chunk back, dropping the ones outside the volume
+    pub fn set_chunk(&mut self, chunk_coords: &ChunkCoords, blocks: &[BlockType]) {
+        let size = CHUNK_SIZE as usize;
+        let origin = chunk_coords.get_origin();
+        for (index, block_type) in blocks.iter().enumerate().take(CHUNK_VOLUME) {
+            let x = index % size;
+            let y = (index / size) % size;
+            let z = index / (size * size);
+            self.set(&origin.offset(x as BlockCoordSize, y as BlockCoordSize, z as BlockCoordSize), *block_type);
+        }
+    }
+}
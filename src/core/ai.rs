@@ -0,0 +1,455 @@
+use std::collections::{HashSet};
+use std::error::Error;
+use std::fmt;
+use std::str::{FromStr};
+
+use dorp::{Id, EntityData};
+
+use core::{
+    RogueWorld, Rng, Direction, BlockCoordSize, AttackErr, BLOCK_REGISTRY_NAME, VOXEL_VOLUME_NAME, PLAYER_NAME, trace_shot
+};
+use components::{BlockCoords, BlockType, FieldOfView, FieldOfViewErr, MonsterDef, Combat};
+use pathing::{NavGrid, DijkstraMap, FLEE_FACTOR, find_path};
+
+// Tries at finding somewhere reachable to wander to before staying put
+static WANDER_TRIES: usize = 8;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AiState {
+    Idle,
+    Wander,
+    Hunt,
+    Flee,
+    ReturnHome,
+}
+
+impl AiState {
+    pub fn get_all() -> Vec<AiState> {
+        vec!(AiState::Idle, AiState::Wander, AiState::Hunt, AiState::Flee, AiState::ReturnHome)
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match *self {
+            AiState::Idle => "idle",
+            AiState::Wander => "wander",
+            AiState::Hunt => "hunt",
+            AiState::Flee => "flee",
+            AiState::ReturnHome => "return_home",
+        }
+    }
+}
+
+impl FromStr for AiState {
+    type Err = ();
+
+    fn from_str(name: &str) -> Result<AiState, ()> {
+        match AiState::get_all().into_iter().find(|state| state.get_name() == name) {
+            Some(state) => Ok(state),
+            None => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AiAction {
+    Wait,
+    Move(BlockCoords),
+    Attack(Id),
+    Fire(BlockCoords),
+}
+
+// Drives a monster on its turns. Seeing the player starts a hunt, or a flight for timid or badly
+// hurt monsters, that lasts until the player has been out of sight for the monster's memory or it
+// strays past its leash, then it walks home and idles, now and then wandering nearby
+pub struct Ai {
+    monster_def: MonsterDef,
+    state: AiState,
+    home: BlockCoords,
+    facing: Direction,
+    last_seen: Option<BlockCoords>,
+    forget_in: u32,
+    // Steps still to take, the next one last
+    path: Vec<BlockCoords>,
+    path_goal: Option<BlockCoords>,
+    last_action: Option<AiAction>,
+    rng: Rng,
+}
+
+impl Ai {
+    pub fn new(monster_def: MonsterDef, home: BlockCoords, seed: u64) -> Ai {
+        Ai {
+            monster_def: monster_def,
+            state: AiState::Idle,
+            home: home,
+            facing: Direction::South,
+            last_seen: None,
+            forget_in: 0,
+            path: vec!(),
+            path_goal: None,
+            last_action: None,
+            rng: Rng::new(seed),
+        }
+    }
+
+    // The rest restore a saved monster's state of mind
+    pub fn with_state(mut self, state: AiState) -> Ai {
+        self.state = state;
+        self
+    }
+
+    pub fn with_facing(mut self, facing: Direction) -> Ai {
+        self.facing = facing;
+        self
+    }
+
+    pub fn with_last_seen(mut self, last_seen: Option<BlockCoords>, forget_in: u32) -> Ai {
+        self.last_seen = last_seen;
+        self.forget_in = forget_in;
+        self
+    }
+
+    pub fn with_path(mut self, path: Vec<BlockCoords>, path_goal: Option<BlockCoords>) -> Ai {
+        self.path = path;
+        self.path_goal = path_goal;
+        self
+    }
+
+    pub fn with_rng(mut self, rng: Rng) -> Ai {
+        self.rng = rng;
+        self
+    }
+
+    pub fn get_monster_def(&self) -> &MonsterDef {
+        &self.monster_def
+    }
+
+    pub fn get_state(&self) -> AiState {
+        self.state
+    }
+
+    pub fn get_home(&self) -> BlockCoords {
+        self.home
+    }
+
+    pub fn get_facing(&self) -> Direction {
+        self.facing
+    }
+
+    pub fn get_last_seen(&self) -> Option<BlockCoords> {
+        self.last_seen
+    }
+
+    pub fn get_forget_in(&self) -> u32 {
+        self.forget_in
+    }
+
+    // Steps still to take, the next one last
+    pub fn get_path(&self) -> &Vec<BlockCoords> {
+        &self.path
+    }
+
+    pub fn get_path_goal(&self) -> Option<BlockCoords> {
+        self.path_goal
+    }
+
+    pub fn get_rng(&self) -> &Rng {
+        &self.rng
+    }
+
+    pub fn get_last_action(&self) -> Option<AiAction> {
+        self.last_action
+    }
+
+    // Takes the monster's turn, moving it when it decides to. Monsters always use up their turn,
+    // attacks and shots are left to the caller
+    pub fn act(&mut self, block_coords: &mut BlockCoords, field_of_view: &mut FieldOfView, combat: Option<&Combat>, actors: &Vec<Id>, world: &RogueWorld) -> Result<AiAction, AiErr> {
+        let block_registry = match world.get_entity_by_name(BLOCK_REGISTRY_NAME) {
+            Some(entity) => match entity.get_block_registry() {
+                Some(block_registry) => block_registry,
+                None => return Err(AiErr::Get("Block Registry Entity Get Block Registry")),
+            },
+            None => return Err(AiErr::Get("World Get Entity By Name Block Registry Name")),
+        };
+        let voxel_volume = match world.get_entity_by_name(VOXEL_VOLUME_NAME) {
+            Some(entity) => match entity.get_voxel_volume() {
+                Some(voxel_volume) => voxel_volume,
+                None => return Err(AiErr::Get("Voxel Volume Entity Get Voxel Volume")),
+            },
+            None => return Err(AiErr::Get("World Get Entity By Name Voxel Volume Name")),
+        };
+        let mut nav_grid = NavGrid::new(&voxel_volume, &block_registry);
+        if self.monster_def.opens_doors() {
+            nav_grid = nav_grid.with_passable(BlockType::DOOR);
+        }
+
+        let mut occupied = HashSet::new();
+        for id in actors.iter() {
+            match world.get_entity_by_id(*id).and_then(|entity| entity.get_block_coords()) {
+                Some(other) => {
+                    occupied.insert(*other);
+                },
+                None => (),
+            }
+        }
+
+        let player = match world.get_entity_by_name(PLAYER_NAME) {
+            Some(entity) => match entity.get_block_coords() {
+                Some(player_coords) => Some((entity.get_id(), *player_coords)),
+                None => return Err(AiErr::Get("Player Entity Get Block Coords")),
+            },
+            None => None,
+        };
+        // Only look when the player is close enough to be seen, sight is the costly part
+        let seen = match player {
+            Some((id, player_coords)) if column_distance(block_coords, &player_coords) <= self.monster_def.get_sight() => {
+                match field_of_view.tick_mut(block_coords, world) {
+                    Ok(()) => (),
+                    Err(err) => return Err(AiErr::FieldOfView("Field Of View Tick Mut", err)),
+                }
+                if field_of_view.is_visible(&player_coords) {
+                    Some((id, player_coords))
+                } else {
+                    None
+                }
+            },
+            _ => None,
+        };
+
+        let range = combat.map_or(0, |combat| combat.get_range());
+        let clear_shot = match seen {
+            Some((id, player_coords)) if range > 0 && !is_adjacent(block_coords, &player_coords) => {
+                match trace_shot(block_coords, &player_coords, range, actors, world) {
+                    Ok(shot) => shot.get_target() == Some(id),
+                    Err(err) => return Err(AiErr::Attack("Trace Shot", Box::new(err))),
+                }
+            },
+            _ => false,
+        };
+        let hurt = combat.map_or(false, |combat| {
+            (combat.get_health() as f64) < combat.get_max_health() as f64 * self.monster_def.get_flee_health()
+        });
+
+        let action = self.decide(block_coords, seen, clear_shot, hurt, &nav_grid, &occupied);
+        if let AiAction::Move(next) = action {
+            match get_direction(block_coords, &next) {
+                Some(direction) => self.facing = direction,
+                None => (),
+            }
+            *block_coords = next;
+        }
+        self.last_action = Some(action);
+        Ok(action)
+    }
+
+    fn perceive(&mut self, seen: Option<(Id, BlockCoords)>, hurt: bool) {
+        match seen {
+            Some((_, player_coords)) => {
+                self.last_seen = Some(player_coords);
+                self.forget_in = self.monster_def.get_memory();
+                if self.monster_def.is_timid() || hurt {
+                    self.set_state(AiState::Flee);
+                } else if self.state != AiState::ReturnHome || column_distance(&self.home, &player_coords) <= self.monster_def.get_leash() {
+                    self.set_state(AiState::Hunt);
+                }
+            },
+            None => if self.state == AiState::Hunt || self.state == AiState::Flee {
+                if self.forget_in == 0 {
+                    self.last_seen = None;
+                    self.set_state(AiState::ReturnHome);
+                } else {
+                    self.forget_in -= 1;
+                }
+            },
+        }
+    }
+
+    fn decide(&mut self, here: &BlockCoords, seen: Option<(Id, BlockCoords)>, clear_shot: bool, hurt: bool, nav_grid: &NavGrid, occupied: &HashSet<BlockCoords>) -> AiAction {
+        self.perceive(seen, hurt);
+        if self.state == AiState::Hunt && column_distance(here, &self.home) > self.monster_def.get_leash() {
+            self.last_seen = None;
+            self.set_state(AiState::ReturnHome);
+        }
+        match self.state {
+            AiState::Idle => {
+                let wander_chance = self.monster_def.get_wander_chance();
+                if wander_chance > 0.0 && self.rng.chance(wander_chance) {
+                    match self.pick_wander(here, nav_grid) {
+                        Some(destination) => {
+                            self.set_state(AiState::Wander);
+                            return self.step_towards(here, &destination, nav_grid, occupied);
+                        },
+                        None => (),
+                    }
+                }
+                AiAction::Wait
+            },
+            AiState::Wander => match self.path_goal {
+                Some(destination) if destination != *here => self.step_towards(here, &destination, nav_grid, occupied),
+                _ => {
+                    self.set_state(AiState::Idle);
+                    AiAction::Wait
+                },
+            },
+            AiState::Hunt => {
+                match seen {
+                    Some((id, player_coords)) if is_adjacent(here, &player_coords) => return AiAction::Attack(id),
+                    Some((_, player_coords)) if clear_shot => return AiAction::Fire(player_coords),
+                    _ => (),
+                }
+                match self.last_seen {
+                    Some(last_seen) if last_seen != *here => self.step_towards(here, &last_seen, nav_grid, occupied),
+                    _ => AiAction::Wait,
+                }
+            },
+            AiState::Flee => match seen {
+                Some((_, player_coords)) => self.step_away(here, &player_coords, nav_grid, occupied),
+                None => AiAction::Wait,
+            },
+            AiState::ReturnHome => {
+                if *here == self.home {
+                    self.set_state(AiState::Idle);
+                    return AiAction::Wait;
+                }
+                let home = self.home;
+                let action = self.step_towards(here, &home, nav_grid, occupied);
+                // Nowhere to go, so settle where it is
+                if action == AiAction::Wait && self.path_goal.is_none() {
+                    self.set_state(AiState::Idle);
+                }
+                action
+            },
+        }
+    }
+
+    fn set_state(&mut self, state: AiState) {
+        if self.state != state {
+            self.state = state;
+            self.path.clear();
+            self.path_goal = None;
+        }
+    }
+
+    // Follows the current path, planning again when the goal moved or the way is gone
+    fn step_towards(&mut self, here: &BlockCoords, goal: &BlockCoords, nav_grid: &NavGrid, occupied: &HashSet<BlockCoords>) -> AiAction {
+        let on_path = match self.path.last() {
+            Some(next) => nav_grid.get_steps(here).iter().any(|&(step, _)| step == *next),
+            None => false,
+        };
+        if self.path_goal != Some(*goal) || !on_path {
+            self.path.clear();
+            self.path_goal = None;
+            match find_path(nav_grid, here, goal) {
+                Some((mut path, _)) => {
+                    path.reverse();
+                    path.pop();
+                    self.path = path;
+                    self.path_goal = Some(*goal);
+                },
+                None => return AiAction::Wait,
+            }
+        }
+        match self.path.last().cloned() {
+            Some(next) => if occupied.contains(&next) {
+                // Someone is in the way, try another way next turn
+                self.path.clear();
+                AiAction::Wait
+            } else {
+                self.path.pop();
+                AiAction::Move(next)
+            },
+            None => AiAction::Wait,
+        }
+    }
+
+    fn step_away(&mut self, here: &BlockCoords, threat: &BlockCoords, nav_grid: &NavGrid, occupied: &HashSet<BlockCoords>) -> AiAction {
+        let reach = self.monster_def.get_sight() * 4;
+        let chase = DijkstraMap::new(nav_grid, &[*threat], reach);
+        let flee = DijkstraMap::flee(nav_grid, &chase, FLEE_FACTOR);
+        let mut best = match flee.get(here) {
+            Some(value) => value,
+            None => return AiAction::Wait,
+        };
+        let mut away = AiAction::Wait;
+        for (next, _) in nav_grid.get_steps(here) {
+            if occupied.contains(&next) {
+                continue;
+            }
+            match flee.get(&next) {
+                Some(value) if value < best => {
+                    best = value;
+                    away = AiAction::Move(next);
+                },
+                _ => (),
+            }
+        }
+        away
+    }
+
+    fn pick_wander(&mut self, here: &BlockCoords, nav_grid: &NavGrid) -> Option<BlockCoords> {
+        let radius = self.monster_def.get_wander_radius();
+        for _ in 0..WANDER_TRIES {
+            let x = self.home.get_x() + self.rng.range(-radius, radius + 1);
+            let z = self.home.get_z() + self.rng.range(-radius, radius + 1);
+            let top = here.get_y() + 1;
+            let bottom = here.get_y() - nav_grid.get_max_fall();
+            let mut y = top;
+            while y >= bottom {
+                let destination = BlockCoords::new(x, y, z);
+                if nav_grid.can_stand(&destination) {
+                    if destination != *here && find_path(nav_grid, here, &destination).is_some() {
+                        return Some(destination);
+                    }
+                    break;
+                }
+                y -= 1;
+            }
+        }
+        None
+    }
+}
+
+fn column_distance(a: &BlockCoords, b: &BlockCoords) -> BlockCoordSize {
+    (a.get_x() - b.get_x()).abs() + (a.get_z() - b.get_z()).abs()
+}
+
+// Next to each other and close enough in height to reach
+fn is_adjacent(a: &BlockCoords, b: &BlockCoords) -> bool {
+    column_distance(a, b) == 1 && (a.get_y() - b.get_y()).abs() <= 1
+}
+
+fn get_direction(from: &BlockCoords, to: &BlockCoords) -> Option<Direction> {
+    match (to.get_x() - from.get_x(), to.get_z() - from.get_z()) {
+        (0, -1) => Some(Direction::North),
+        (0, 1) => Some(Direction::South),
+        (1, 0) => Some(Direction::East),
+        (-1, 0) => Some(Direction::West),
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+pub enum AiErr {
+    FieldOfView(&'static str, FieldOfViewErr),
+    Attack(&'static str, Box<AttackErr>),
+    Get(&'static str),
+}
+
+impl fmt::Display for AiErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AiErr::FieldOfView(_, ref err) => err.fmt(f),
+            AiErr::Attack(_, ref err) => err.fmt(f),
+            AiErr::Get(_) => write!(f, "Get was None"),
+        }
+    }
+}
+
+impl Error for AiErr {
+    fn description(&self) -> &str {
+        match *self {
+            AiErr::FieldOfView(_, ref err) => err.description(),
+            AiErr::Attack(_, ref err) => err.description(),
+            AiErr::Get(_) => "Get was None",
+        }
+    }
+}
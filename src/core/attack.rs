@@ -0,0 +1,178 @@
+use std::error::Error;
+use std::fmt;
+
+use dorp::{Id, OptErr, WorldErr};
+
+use core::{RogueWorld, RogueDataErr, PlayerErr, BlockCoordSize, BLOCK_MAP_NAME, BLOCK_REGISTRY_NAME, VOXEL_VOLUME_NAME};
+use components::{BlockCoords, BlockType, Combat, AttackResult};
+use fov::{trace_line};
+
+// Where a ranged attack went: the cells it crossed and whoever it ran into
+pub struct Shot {
+    path: Vec<BlockCoords>,
+    target: Option<Id>,
+}
+
+impl Shot {
+    pub fn get_path(&self) -> &Vec<BlockCoords> {
+        &self.path
+    }
+
+    pub fn get_end(&self) -> Option<BlockCoords> {
+        self.path.last().cloned()
+    }
+
+    pub fn get_target(&self) -> Option<Id> {
+        self.target
+    }
+}
+
+// Follows the line from one cell towards another for up to range cells, stopping short of the
+// first block that stops shots or on the first actor that can be hurt
+pub fn trace_shot(from: &BlockCoords, to: &BlockCoords, range: BlockCoordSize, actors: &Vec<Id>, world: &RogueWorld) -> Result<Shot, AttackErr> {
+    let mut shot = Shot {
+        path: vec!(),
+        target: None,
+    };
+    for cell in trace_line(from, to).into_iter().skip(1).take(range.max(0) as usize) {
+        match blocks_shot(&cell, world) {
+            Ok(false) => (),
+            Ok(true) => break,
+            Err(err) => return Err(err),
+        }
+        shot.path.push(cell);
+        for id in actors.iter() {
+            match world.get_entity_by_id(*id) {
+                Some(entity) => if entity.get_combat().is_some() && entity.get_block_coords().map_or(false, |other| *other == cell) {
+                    shot.target = Some(*id);
+                    return Ok(shot);
+                },
+                None => (),
+            }
+        }
+    }
+    Ok(shot)
+}
+
+// Whether a shot stops at block_coords: anything solid or opaque in the voxel volume or the
+// block map. Unlike walking there is no way through a closed door
+pub fn blocks_shot(block_coords: &BlockCoords, world: &RogueWorld) -> Result<bool, AttackErr> {
+    let block_registry = match world.get_entity_by_name(BLOCK_REGISTRY_NAME) {
+        Some(block_registry_entity) => match block_registry_entity.get_block_registry() {
+            Some(block_registry) => Some(block_registry),
+            None => return Err(AttackErr::Get("Block Registry Entity Get Block Registry")),
+        },
+        None => None,
+    };
+    // Without a registry only air lets a shot through
+    let is_blocking = |block_type: BlockType| match block_registry {
+        Some(ref block_registry) => block_registry.is_solid(block_type) || block_registry.is_opaque(block_type),
+        None => block_type != BlockType::AIR,
+    };
+    match world.get_entity_by_name(VOXEL_VOLUME_NAME) {
+        Some(voxel_volume_entity) => match voxel_volume_entity.get_voxel_volume() {
+            Some(voxel_volume) => {
+                if is_blocking(voxel_volume.get(block_coords)) {
+                    return Ok(true);
+                }
+            },
+            None => return Err(AttackErr::Get("Voxel Volume Entity Get Voxel Volume")),
+        },
+        None => (),
+    }
+    let block_map = match world.get_entity_by_name(BLOCK_MAP_NAME) {
+        Some(block_map_entity) => match block_map_entity.get_block_map() {
+            Some(block_map) => block_map,
+            None => return Err(AttackErr::Get("Block Map Entity Get Block Map")),
+        },
+        None => return Err(AttackErr::Get("World Get Entity By Name Block Map Name")),
+    };
+    let block_id = match block_map.get(block_coords.get_x(), block_coords.get_y(), block_coords.get_z()) {
+        Some(block_id) => block_id,
+        None => return Ok(false),
+    };
+    match world.get_entity_by_id(block_id) {
+        Some(block_entity) => match block_entity.get_block() {
+            Some(block) => Ok(is_blocking(block.get_block_type())),
+            None => Err(AttackErr::Get("Block Entity Get Block")),
+        },
+        None => Err(AttackErr::Get("World Get Entity By Id Block Id")),
+    }
+}
+
+// Rolls the attack on target and applies it, taking the target out of the world when it dies
+// unless it is the player, whose death the scheduler ends the game on. None when there is
+// nothing there that can be hurt
+pub fn strike(attacker: &mut Combat, target: Id, world: &mut RogueWorld) -> Result<Option<AttackResult>, AttackErr> {
+    let attack_result = match world.get_mut_entity_by_id(target) {
+        OptErr::Full(entity) => match entity.get_mut_combat() {
+            OptErr::Full(defender) => {
+                let rolled = attacker.roll_attack(defender);
+                defender.take_attack(rolled)
+            },
+            OptErr::Empty => return Ok(None),
+            OptErr::Error(err) => return Err(AttackErr::RogueData("Entity Get Mut Combat", Box::new(err))),
+        },
+        OptErr::Empty => return Ok(None),
+        OptErr::Error(err) => return Err(AttackErr::World("World Get Mut Entity By Id", err)),
+    };
+    let killed = match attack_result {
+        AttackResult::Hit { killed, .. } => killed,
+        AttackResult::Miss => false,
+    };
+    let is_player = world.get_entity_by_id(target).map_or(false, |entity| entity.get_player().is_some());
+    if killed && !is_player {
+        match world.take_entity_by_id(target) {
+            OptErr::Full(_) => (),
+            OptErr::Empty => (),
+            OptErr::Error(err) => return Err(AttackErr::World("World Take Entity By Id", err)),
+        }
+    }
+    Ok(Some(attack_result))
+}
+
+// A ranged attack from one cell aimed at another, as far as the attacker's range
+pub fn fire(attacker: &mut Combat, from: &BlockCoords, to: &BlockCoords, actors: &Vec<Id>, world: &mut RogueWorld) -> Result<(Shot, Option<AttackResult>), AttackErr> {
+    let shot = match trace_shot(from, to, attacker.get_range(), actors, world) {
+        Ok(shot) => shot,
+        Err(err) => return Err(err),
+    };
+    let attack_result = match shot.get_target() {
+        Some(target) => match strike(attacker, target, world) {
+            Ok(attack_result) => attack_result,
+            Err(err) => return Err(err),
+        },
+        None => None,
+    };
+    Ok((shot, attack_result))
+}
+
+#[derive(Debug)]
+pub enum AttackErr {
+    Get(&'static str),
+    Player(&'static str, PlayerErr),
+    RogueData(&'static str, Box<RogueDataErr>),
+    World(&'static str, WorldErr),
+}
+
+impl fmt::Display for AttackErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AttackErr::Get(_) => write!(f, "Get was None"),
+            AttackErr::Player(_, ref err) => err.fmt(f),
+            AttackErr::RogueData(_, ref err) => err.fmt(f),
+            AttackErr::World(_, ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for AttackErr {
+    fn description(&self) -> &str {
+        match *self {
+            AttackErr::Get(_) => "Get was None",
+            AttackErr::Player(_, ref err) => err.description(),
+            AttackErr::RogueData(_, ref err) => err.description(),
+            AttackErr::World(_, ref err) => err.description(),
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use std::fs::{File};
+use std::io::{self, Read};
+use std::str::{FromStr};
+use std::error::Error;
+use std::fmt;
+
+// Plain text data files: '[name]' opens a section, 'key = value' sets an entry in it and
+// '#' starts a comment. Line numbers are kept so errors can point designers at the line
+pub struct Config {
+    sections: Vec<ConfigSection>,
+}
+
+pub struct ConfigSection {
+    name: String,
+    line: usize,
+    entries: Vec<ConfigEntry>,
+}
+
+pub struct ConfigEntry {
+    key: String,
+    value: String,
+    line: usize,
+}
+
+impl Config {
+    pub fn load(path: &str) -> Result<Config, ConfigErr> {
+        let mut text = String::new();
+        match File::open(path) {
+            Ok(mut file) => match file.read_to_string(&mut text) {
+                Ok(_) => (),
+                Err(err) => return Err(ConfigErr::Io(path.to_string(), err)),
+            },
+            Err(err) => return Err(ConfigErr::Io(path.to_string(), err)),
+        }
+        Config::parse(&text)
+    }
+
+    pub fn parse(text: &str) -> Result<Config, ConfigErr> {
+        let mut sections: Vec<ConfigSection> = vec!();
+        for (index, raw) in text.lines().enumerate() {
+            let line = index + 1;
+            let content = match raw.find('#') {
+                Some(comment) => &raw[..comment],
+                None => raw,
+            }.trim();
+            if content.is_empty() {
+                continue;
+            }
+            if content.starts_with('[') {
+                if !content.ends_with(']') || content.len() < 3 {
+                    return Err(ConfigErr::Syntax(line, "Section header must look like [name]"));
+                }
+                let name = content[1..content.len() - 1].trim().to_string();
+                if sections.iter().any(|section| section.name == name) {
+                    return Err(ConfigErr::Duplicate(line, name));
+                }
+                sections.push(ConfigSection {
+                    name: name,
+                    line: line,
+                    entries: vec!(),
+                });
+                continue;
+            }
+            let (key, value) = match content.find('=') {
+                Some(equals) => (content[..equals].trim(), content[equals + 1..].trim()),
+                None => return Err(ConfigErr::Syntax(line, "Expected key = value")),
+            };
+            if key.is_empty() {
+                return Err(ConfigErr::Syntax(line, "Missing key before ="));
+            }
+            match sections.last_mut() {
+                Some(section) => {
+                    if section.entries.iter().any(|entry| entry.key == key) {
+                        return Err(ConfigErr::Duplicate(line, key.to_string()));
+                    }
+                    section.entries.push(ConfigEntry {
+                        key: key.to_string(),
+                        value: value.to_string(),
+                        line: line,
+                    });
+                },
+                None => return Err(ConfigErr::Syntax(line, "Entry before any [section]")),
+            }
+        }
+        Ok(Config {
+            sections: sections,
+        })
+    }
+
+    pub fn get_sections(&self) -> &Vec<ConfigSection> {
+        &self.sections
+    }
+
+    pub fn get_section(&self, name: &str) -> Option<&ConfigSection> {
+        self.sections.iter().find(|section| section.name == name)
+    }
+}
+
+impl ConfigSection {
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_line(&self) -> usize {
+        self.line
+    }
+
+    pub fn get_entries(&self) -> &Vec<ConfigEntry> {
+        &self.entries
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.iter().find(|entry| entry.key == key).map(|entry| entry.value.as_str())
+    }
+
+    pub fn get_entry(&self, key: &str) -> Option<&ConfigEntry> {
+        self.entries.iter().find(|entry| entry.key == key)
+    }
+
+    pub fn get_parsed<T: FromStr>(&self, key: &str, default: T) -> Result<T, ConfigErr> {
+        match self.get_entry(key) {
+            Some(entry) => entry.parse(),
+            None => Ok(default),
+        }
+    }
+
+    pub fn get_required<T: FromStr>(&self, key: &str) -> Result<T, ConfigErr> {
+        match self.get_entry(key) {
+            Some(entry) => entry.parse(),
+            None => Err(ConfigErr::Missing(self.line, key.to_string())),
+        }
+    }
+}
+
+impl ConfigEntry {
+    pub fn get_key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn get_value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn get_line(&self) -> usize {
+        self.line
+    }
+
+    pub fn parse<T: FromStr>(&self) -> Result<T, ConfigErr> {
+        match self.value.parse() {
+            Ok(value) => Ok(value),
+            Err(_) => Err(ConfigErr::Value(self.line, self.key.clone(), self.value.clone())),
+        }
+    }
+
+    // Whitespace separated list such as a color 'r g b a'
+    pub fn parse_list<T: FromStr>(&self) -> Result<Vec<T>, ConfigErr> {
+        let mut values = vec!();
+        for part in self.value.split_whitespace() {
+            match part.parse() {
+                Ok(value) => values.push(value),
+                Err(_) => return Err(ConfigErr::Value(self.line, self.key.clone(), self.value.clone())),
+            }
+        }
+        Ok(values)
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigErr {
+    Io(String, io::Error),
+    Syntax(usize, &'static str),
+    Duplicate(usize, String),
+    Missing(usize, String),
+    Value(usize, String, String),
+}
+
+impl fmt::Display for ConfigErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ConfigErr::Io(ref path, ref err) => write!(f, "{}: {}", path, err),
+            ConfigErr::Syntax(line, message) => write!(f, "line {}: {}", line, message),
+            ConfigErr::Duplicate(line, ref name) => write!(f, "line {}: {} is defined twice", line, name),
+            ConfigErr::Missing(line, ref key) => write!(f, "line {}: section is missing {}", line, key),
+            ConfigErr::Value(line, ref key, ref value) => write!(f, "line {}: {} has invalid value '{}'", line, key, value),
+        }
+    }
+}
+
+impl Error for ConfigErr {
+    fn description(&self) -> &str {
+        match *self {
+            ConfigErr::Io(_, ref err) => err.description(),
+            ConfigErr::Syntax(_, message) => message,
+            ConfigErr::Duplicate(_, _) => "Defined twice",
+            ConfigErr::Missing(_, _) => "Missing key",
+            ConfigErr::Value(_, _, _) => "Invalid value",
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::error::Error;
+use std::fmt;
+
+use dorp::{Id, OptErr, WorldErr};
+
+use core::{RogueWorld, RogueDataErr, ITEM_MAP_NAME};
+use components::{BlockCoords, Combat, Inventory, InventoryErr};
+
+// Picks up the first item on the cell that fits in the inventory, None when nothing does
+pub fn pick_up(inventory: &mut Inventory, block_coords: &BlockCoords, world: &mut RogueWorld) -> Result<Option<Id>, LootErr> {
+    let ids = match world.get_entity_by_name(ITEM_MAP_NAME) {
+        Some(entity) => match entity.get_item_map() {
+            Some(item_map) => item_map.get_at(block_coords),
+            None => return Err(LootErr::Get("Item Map Entity Get Item Map")),
+        },
+        None => return Ok(None),
+    };
+    for id in ids {
+        let item = match world.get_entity_by_id(id).and_then(|entity| entity.get_item()) {
+            Some(item) => (*item).clone(),
+            None => continue,
+        };
+        if inventory.check_add(&item).is_err() {
+            continue;
+        }
+        match set_item_coords(id, block_coords, None, world) {
+            Ok(()) => (),
+            Err(err) => return Err(err),
+        }
+        return match inventory.add(id, item) {
+            Ok(()) => Ok(Some(id)),
+            Err(err) => Err(LootErr::Inventory("Inventory Add", err)),
+        };
+    }
+    Ok(None)
+}
+
+// Puts a carried item down on the cell, false when it isn't carried
+pub fn drop_item(inventory: &mut Inventory, id: Id, block_coords: &BlockCoords, world: &mut RogueWorld) -> Result<bool, LootErr> {
+    match inventory.remove(id) {
+        Ok(_) => (),
+        Err(InventoryErr::NotCarried(_)) => return Ok(false),
+        Err(err) => return Err(LootErr::Inventory("Inventory Remove", err)),
+    }
+    match set_item_coords(id, block_coords, Some(*block_coords), world) {
+        Ok(()) => Ok(true),
+        Err(err) => Err(err),
+    }
+}
+
+// Uses up a carried item, taking it out of the world. False when it isn't carried or does nothing
+pub fn use_item(inventory: &mut Inventory, combat: &mut Combat, id: Id, world: &mut RogueWorld) -> Result<bool, LootErr> {
+    let heal = match inventory.get(id) {
+        Some(item) if item.get_item_def().is_usable() => item.get_item_def().get_heal(),
+        _ => return Ok(false),
+    };
+    match inventory.remove(id) {
+        Ok(_) => (),
+        Err(err) => return Err(LootErr::Inventory("Inventory Remove", err)),
+    }
+    combat.heal(heal);
+    match world.take_entity_by_id(id) {
+        OptErr::Full(_) => Ok(true),
+        OptErr::Empty => Ok(true),
+        OptErr::Error(err) => Err(LootErr::World("World Take Entity By Id", err)),
+    }
+}
+
+// Moves an item entity between the floor at block_coords and being carried
+fn set_item_coords(id: Id, block_coords: &BlockCoords, on_floor: Option<BlockCoords>, world: &mut RogueWorld) -> Result<(), LootErr> {
+    match world.get_mut_entity_by_id(id) {
+        OptErr::Full(entity) => entity.set_block_coords(on_floor),
+        OptErr::Empty => return Err(LootErr::Get("World Get Mut Entity By Id")),
+        OptErr::Error(err) => return Err(LootErr::World("World Get Mut Entity By Id", err)),
+    }
+    match world.get_mut_entity_by_name(ITEM_MAP_NAME) {
+        OptErr::Full(entity) => match entity.get_mut_item_map() {
+            OptErr::Full(item_map) => {
+                match on_floor {
+                    Some(_) => item_map.insert(block_coords, id),
+                    None => {
+                        item_map.remove(block_coords, id);
+                    },
+                }
+                Ok(())
+            },
+            OptErr::Empty => Err(LootErr::Get("Item Map Entity Get Mut Item Map")),
+            OptErr::Error(err) => Err(LootErr::RogueData("Item Map Entity Get Mut Item Map", Box::new(err))),
+        },
+        OptErr::Empty => Err(LootErr::Get("World Get Mut Entity By Name Item Map Name")),
+        OptErr::Error(err) => Err(LootErr::World("World Get Mut Entity By Name Item Map Name", err)),
+    }
+}
+
+#[derive(Debug)]
+pub enum LootErr {
+    Inventory(&'static str, InventoryErr),
+    RogueData(&'static str, Box<RogueDataErr>),
+    World(&'static str, WorldErr),
+    Get(&'static str),
+}
+
+impl fmt::Display for LootErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LootErr::Inventory(_, ref err) => err.fmt(f),
+            LootErr::RogueData(_, ref err) => err.fmt(f),
+            LootErr::World(_, ref err) => err.fmt(f),
+            LootErr::Get(_) => write!(f, "Get was None"),
+        }
+    }
+}
+
+impl Error for LootErr {
+    fn description(&self) -> &str {
+        match *self {
+            LootErr::Inventory(_, ref err) => err.description(),
+            LootErr::RogueData(_, ref err) => err.description(),
+            LootErr::World(_, ref err) => err.description(),
+            LootErr::Get(_) => "Get was None",
+        }
+    }
+}
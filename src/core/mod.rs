@@ -1,6 +1,29 @@
 pub mod rogue_data;
+pub mod config;
 pub mod utils;
 pub mod player;
+pub mod scheduler;
+pub mod ai;
+pub mod attack;
+pub mod loot;
+pub mod rng;
+pub mod scene;
+pub mod headless;
 
 pub use self::rogue_data::{RogueData, RogueDataErr};
-pub use self::utils::{BLOCK_MAP_NAME, BlockCoordSize, RogueWorld};
+pub use self::config::{Config, ConfigSection, ConfigEntry, ConfigErr};
+pub use self::utils::{
+    BLOCK_MAP_NAME, BLOCK_REGISTRY_NAME, PLAYER_NAME, VOXEL_VOLUME_NAME, CAMERA_NAME, INPUT_NAME, SCHEDULER_NAME,
+    ITEM_MAP_NAME, BEING_SPRITE, ITEM_SPRITE, BlockCoordSize, RogueWorld
+};
+pub use self::player::{Player, PlayerErr, PlayerAction, Direction};
+pub use self::scheduler::{Scheduler, SchedulerErr};
+pub use self::ai::{Ai, AiErr, AiState, AiAction};
+pub use self::attack::{Shot, AttackErr, trace_shot, blocks_shot, strike, fire};
+pub use self::loot::{LootErr, pick_up, drop_item, use_item};
+pub use self::rng::{Rng};
+pub use self::scene::{
+    SceneErr, new_scene, build_scene, build_generated_scene, new_level_file_scene, build_level_file, build_populated_level, build_level, get_volume_scale, new_being_sprite, new_item_sprite,
+    pick_monster_spawns, pick_item_spawns, new_textured_chunk_renderable, new_sprite_renderable
+};
+pub use self::headless::{HeadlessLevel, HeadlessReport, HeadlessErr, VoxelBenchReport, run_headless, run_voxel_bench, tick_mut_entity};
@@ -0,0 +1,318 @@
+use std::fmt;
+use std::error::Error;
+use std::str::{FromStr};
+
+use dorp::{Id};
+
+use core::{RogueWorld, BlockCoordSize, BLOCK_MAP_NAME, BLOCK_REGISTRY_NAME, VOXEL_VOLUME_NAME, INPUT_NAME};
+use components::{BlockCoords, BlockType, EquipSlot, SIGHT_RADIUS};
+use input::{Action, InputState};
+use pathing::{NavGrid};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl Direction {
+    pub fn get_all() -> [Direction; 4] {
+        [Direction::North, Direction::East, Direction::South, Direction::West]
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match *self {
+            Direction::North => "north",
+            Direction::South => "south",
+            Direction::East => "east",
+            Direction::West => "west",
+        }
+    }
+
+    pub fn get_offset(&self) -> (BlockCoordSize, BlockCoordSize, BlockCoordSize) {
+        match *self {
+            Direction::North => (0, 0, -1),
+            Direction::South => (0, 0, 1),
+            Direction::East => (1, 0, 0),
+            Direction::West => (-1, 0, 0),
+        }
+    }
+
+    pub fn step(&self, block_coords: &BlockCoords) -> BlockCoords {
+        let (x, y, z) = self.get_offset();
+        BlockCoords::new(block_coords.get_x() + x, block_coords.get_y() + y, block_coords.get_z() + z)
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ();
+
+    fn from_str(name: &str) -> Result<Direction, ()> {
+        match Direction::get_all().iter().find(|direction| direction.get_name() == name) {
+            Some(direction) => Ok(*direction),
+            None => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PlayerAction {
+    Move(Direction),
+    Wait,
+    Attack(Id),
+    Fire(BlockCoords),
+    PickUp,
+    // No keys yet, these come from code driving the player with set_next_action
+    Drop(Id),
+    Use(Id),
+    Equip(Id),
+    Unequip(EquipSlot),
+}
+
+pub struct Player {
+    next_action: Option<PlayerAction>,
+    facing: Direction,
+    fire_facing: bool,
+    input_frame: Option<u64>,
+}
+
+impl Player {
+    pub fn new() -> Player {
+        Player {
+            next_action: None,
+            facing: Direction::South,
+            fire_facing: false,
+            input_frame: None,
+        }
+    }
+
+    pub fn with_facing(mut self, facing: Direction) -> Player {
+        self.facing = facing;
+        self
+    }
+
+    pub fn set_next_action(&mut self, player_action: PlayerAction) {
+        self.next_action = Some(player_action);
+        self.fire_facing = false;
+    }
+
+    // Shoots as far as it can in the facing direction on the next turn
+    pub fn set_next_fire_facing(&mut self) {
+        self.next_action = None;
+        self.fire_facing = true;
+    }
+
+    pub fn get_next_action(&self) -> Option<PlayerAction> {
+        self.next_action
+    }
+
+    pub fn set_next_move(&mut self, direction: Direction) {
+        self.set_next_action(PlayerAction::Move(direction));
+    }
+
+    pub fn get_next_move(&self) -> Option<Direction> {
+        match self.next_action {
+            Some(PlayerAction::Move(direction)) => Some(direction),
+            _ => None,
+        }
+    }
+
+    pub fn get_facing(&self) -> Direction {
+        self.facing
+    }
+
+    // Takes the first move, wait, fire or pick up pressed this frame, once per frame however often the player ticks
+    pub fn read_input(&mut self, input_state: &InputState) {
+        if self.input_frame == Some(input_state.get_frame()) {
+            return;
+        }
+        self.input_frame = Some(input_state.get_frame());
+        for action in input_state.get_pressed() {
+            match action.get_direction() {
+                Some(direction) => {
+                    self.set_next_move(direction);
+                    return;
+                },
+                None => match *action {
+                    Action::Wait => {
+                        self.set_next_action(PlayerAction::Wait);
+                        return;
+                    },
+                    Action::Fire => {
+                        self.set_next_fire_facing();
+                        return;
+                    },
+                    Action::PickUp => {
+                        self.set_next_action(PlayerAction::PickUp);
+                        return;
+                    },
+                    _ => (),
+                },
+            }
+        }
+    }
+
+    // Input is only gathered here, the scheduler carries it out on the player's turn
+    pub fn tick_mut(&mut self, world: &RogueWorld) -> Result<(), PlayerErr> {
+        match world.get_entity_by_name(INPUT_NAME) {
+            Some(input_entity) => match input_entity.get_input_state() {
+                Some(input_state) => self.read_input(&input_state),
+                None => return Err(PlayerErr::Get("Input Entity Get Input State")),
+            },
+            None => (),
+        }
+        Ok(())
+    }
+
+    // The action taken this turn, None leaves the turn with the player. Moves go where get_step
+    // says and moving into someone who can be hurt attacks them. Walking into a wall or anyone
+    // else, or attacking someone out of reach, takes nothing. Attacks, shots and inventory
+    // actions are carried out by the caller
+    pub fn act(&mut self, block_coords: &mut BlockCoords, actors: &Vec<Id>, world: &RogueWorld) -> Result<Option<PlayerAction>, PlayerErr> {
+        if self.fire_facing {
+            self.fire_facing = false;
+            let (x, y, z) = self.facing.get_offset();
+            let reach = SIGHT_RADIUS;
+            return Ok(Some(PlayerAction::Fire(block_coords.offset(x * reach, y * reach, z * reach))));
+        }
+        let direction = match self.next_action.take() {
+            Some(PlayerAction::Move(direction)) => direction,
+            Some(PlayerAction::Attack(target)) => {
+                // Next to the player and no more than a block up or down, like monsters attack
+                let reachable = match world.get_entity_by_id(target).and_then(|entity| entity.get_block_coords()) {
+                    Some(target_coords) => Direction::get_all().iter().any(|direction| {
+                        let beside = direction.step(block_coords);
+                        beside.get_x() == target_coords.get_x() && beside.get_z() == target_coords.get_z() && (beside.get_y() - target_coords.get_y()).abs() <= 1
+                    }),
+                    None => false,
+                };
+                return Ok(if reachable { Some(PlayerAction::Attack(target)) } else { None });
+            },
+            Some(player_action) => return Ok(Some(player_action)),
+            None => return Ok(None),
+        };
+        self.facing = direction;
+        let target = match Player::get_step(block_coords, direction, world) {
+            Ok(Some(target)) => target,
+            Ok(None) => return Ok(None),
+            Err(err) => return Err(PlayerErr::Player("Player Get Step", Box::new(err))),
+        };
+        for id in actors.iter() {
+            match world.get_entity_by_id(*id) {
+                Some(entity) => if entity.get_block_coords().map_or(false, |other| *other == target) {
+                    return Ok(if entity.get_combat().is_some() { Some(PlayerAction::Attack(*id)) } else { None });
+                },
+                None => (),
+            }
+        }
+        *block_coords = target;
+        Ok(Some(PlayerAction::Move(direction)))
+    }
+
+    // Where a step in direction takes the player, by the rules monsters path with: along the
+    // floor, up one block or down a drop of up to MAX_FALL, with doors opening for the player.
+    // Without a voxel volume and registry to stand in it is a flat step. None when blocked
+    pub fn get_step(block_coords: &BlockCoords, direction: Direction, world: &RogueWorld) -> Result<Option<BlockCoords>, PlayerErr> {
+        let block_registry = match world.get_entity_by_name(BLOCK_REGISTRY_NAME) {
+            Some(block_registry_entity) => match block_registry_entity.get_block_registry() {
+                Some(block_registry) => Some(block_registry),
+                None => return Err(PlayerErr::Get("Block Registry Entity Get Block Registry")),
+            },
+            None => None,
+        };
+        let voxel_volume = match world.get_entity_by_name(VOXEL_VOLUME_NAME) {
+            Some(voxel_volume_entity) => match voxel_volume_entity.get_voxel_volume() {
+                Some(voxel_volume) => Some(voxel_volume),
+                None => return Err(PlayerErr::Get("Voxel Volume Entity Get Voxel Volume")),
+            },
+            None => None,
+        };
+        let target = match (voxel_volume, block_registry) {
+            (Some(voxel_volume), Some(block_registry)) => {
+                let nav_grid = NavGrid::new(&voxel_volume, &block_registry).with_passable(BlockType::DOOR);
+                match nav_grid.get_step(block_coords, direction) {
+                    Some((target, _)) => target,
+                    None => return Ok(None),
+                }
+            },
+            _ => direction.step(block_coords),
+        };
+        // Block map entities can stand in the way where the volume is open
+        match Player::can_enter(&target, world) {
+            Ok(true) => Ok(Some(target)),
+            Ok(false) => Ok(None),
+            Err(err) => Err(PlayerErr::Player("Player Can Enter Target", Box::new(err))),
+        }
+    }
+
+    pub fn can_enter(block_coords: &BlockCoords, world: &RogueWorld) -> Result<bool, PlayerErr> {
+        let block_registry = match world.get_entity_by_name(BLOCK_REGISTRY_NAME) {
+            Some(block_registry_entity) => match block_registry_entity.get_block_registry() {
+                Some(block_registry) => Some(block_registry),
+                None => return Err(PlayerErr::Get("Block Registry Entity Get Block Registry")),
+            },
+            None => None,
+        };
+        // Without a registry only air can be walked through, and the player opens any door
+        let is_passable = |block_type: BlockType| block_type == BlockType::DOOR || match block_registry {
+            Some(ref block_registry) => !block_registry.is_solid(block_type),
+            None => block_type == BlockType::AIR,
+        };
+        match world.get_entity_by_name(VOXEL_VOLUME_NAME) {
+            Some(voxel_volume_entity) => match voxel_volume_entity.get_voxel_volume() {
+                Some(voxel_volume) => {
+                    if !is_passable(voxel_volume.get(block_coords)) {
+                        return Ok(false);
+                    }
+                },
+                None => return Err(PlayerErr::Get("Voxel Volume Entity Get Voxel Volume")),
+            },
+            None => (),
+        }
+        let block_map_entity = match world.get_entity_by_name(BLOCK_MAP_NAME) {
+            Some(entity) => entity,
+            None => return Err(PlayerErr::Get("World Get Entity By Name Block Map Name")),
+        };
+        let block_map = match block_map_entity.get_block_map() {
+            Some(block_map) => block_map,
+            None => return Err(PlayerErr::Get("Block Map Entity Get Block Map")),
+        };
+        let block_id = match block_map.get(block_coords.get_x(), block_coords.get_y(), block_coords.get_z()) {
+            Some(block_id) => block_id,
+            None => return Ok(true),
+        };
+        match world.get_entity_by_id(block_id) {
+            Some(block_entity) => match block_entity.get_block() {
+                Some(block) => Ok(is_passable(block.get_block_type())),
+                None => Err(PlayerErr::Get("Block Entity Get Block")),
+            },
+            None => Err(PlayerErr::Get("World Get Entity By Id Block Id")),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum PlayerErr {
+    Player(&'static str, Box<PlayerErr>),
+    Get(&'static str),
+}
+
+impl fmt::Display for PlayerErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PlayerErr::Player(_, ref err) => err.fmt(f),
+            PlayerErr::Get(_) => write!(f, "Get was None"),
+        }
+    }
+}
+
+impl Error for PlayerErr {
+    fn description(&self) -> &str {
+        match *self {
+            PlayerErr::Player(_, ref err) => err.description(),
+            PlayerErr::Get(_) => "Get was None",
+        }
+    }
+}
@@ -0,0 +1,58 @@
+// xorshift64* generator; small, seedable and its whole state is one u64 so it can be saved
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // Zero is a fixed point of xorshift, so mix the seed before using it
+        let mut state = seed.wrapping_add(0x9E3779B97F4A7C15);
+        state = (state ^ (state >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        state = (state ^ (state >> 27)).wrapping_mul(0x94D049BB133111EB);
+        state = state ^ (state >> 31);
+        if state == 0 {
+            state = 0x9E3779B97F4A7C15;
+        }
+        Rng {
+            state: state,
+        }
+    }
+
+    pub fn from_state(state: u64) -> Rng {
+        Rng {
+            state: if state == 0 { 0x9E3779B97F4A7C15 } else { state },
+        }
+    }
+
+    pub fn get_state(&self) -> u64 {
+        self.state
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545F4914F6CDD1D)
+    }
+
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // Uniform in min..max, max exclusive; returns min when the range is empty
+    pub fn range(&mut self, min: i64, max: i64) -> i64 {
+        if max <= min {
+            return min;
+        }
+        min + (self.next_u64() % (max - min) as u64) as i64
+    }
+
+    pub fn range_usize(&mut self, min: usize, max: usize) -> usize {
+        self.range(min as i64, max as i64) as usize
+    }
+
+    pub fn chance(&mut self, probability: f64) -> bool {
+        self.next_f64() < probability
+    }
+}
@@ -4,10 +4,19 @@ use std::fmt;
 
 use dorp::{
     EntityData, World, IdManager, Window, SyncData, Renderers, Id, Renderable, Named, Transform,
-    RenderableErr, TransformErr, Map3d, Scene, OptErr, TickCount
+    RenderableErr, TransformErr, Scene, OptErr, TickCount
 };
 
-use components::{Block, BlockMap, BlockCoords};
+use core::{
+    Ai, AiErr, AiAction, Player, PlayerErr, PlayerAction, Scheduler, SchedulerErr, AttackErr, LootErr, CAMERA_NAME, PLAYER_NAME, strike, fire,
+    pick_up, drop_item, use_item, new_sprite_renderable, new_textured_chunk_renderable
+};
+use components::{
+    Actor, Block, BlockErr, BlockMap, BlockCoords, VoxelVolume, BlockRegistry, Sprite, Camera, CameraErr, ChunkView,
+    ChunkViewErr, FieldOfView, FieldOfViewErr, Combat, AttackResult, Item, ItemMap, Inventory
+};
+use input::{Action, InputState};
+use save::{save_world, SAVE_PATH};
 
 pub struct RogueData {
     renderable: Option<Arc<Renderable>>,
@@ -17,6 +26,25 @@ pub struct RogueData {
     block_map: Option<Arc<BlockMap>>,
     block_coords: Option<Arc<BlockCoords>>,
     block: Option<Arc<Block>>,
+    player: Option<Arc<Player>>,
+    voxel_volume: Option<Arc<VoxelVolume>>,
+    block_registry: Option<Arc<BlockRegistry>>,
+    sprite: Option<Arc<Sprite>>,
+    camera: Option<Arc<Camera>>,
+    // Camera version last applied to the renderable
+    camera_version: Option<u64>,
+    // Input frame the game was last saved on, so a press saves once
+    saved_frame: Option<u64>,
+    chunk_view: Option<Arc<ChunkView>>,
+    input_state: Option<Arc<InputState>>,
+    actor: Option<Arc<Actor>>,
+    scheduler: Option<Arc<Scheduler>>,
+    field_of_view: Option<Arc<FieldOfView>>,
+    ai: Option<Arc<Ai>>,
+    combat: Option<Arc<Combat>>,
+    item: Option<Arc<Item>>,
+    item_map: Option<Arc<ItemMap>>,
+    inventory: Option<Arc<Inventory>>,
     id: Id,
 }
 
@@ -30,6 +58,23 @@ impl RogueData {
             scene: None,
             block: None,
             block_coords: None,
+            player: None,
+            voxel_volume: None,
+            block_registry: None,
+            sprite: None,
+            camera: None,
+            camera_version: None,
+            saved_frame: None,
+            chunk_view: None,
+            input_state: None,
+            actor: None,
+            scheduler: None,
+            field_of_view: None,
+            ai: None,
+            combat: None,
+            item: None,
+            item_map: None,
+            inventory: None,
             id: id,
         }
     }
@@ -77,6 +122,96 @@ impl RogueData {
     }
 
 
+    pub fn with_player(mut self, player: Player) -> RogueData {
+        self.player = Some(Arc::new(player));
+        self
+    }
+
+
+    pub fn with_voxel_volume(mut self, voxel_volume: VoxelVolume) -> RogueData {
+        self.voxel_volume = Some(Arc::new(voxel_volume));
+        self
+    }
+
+
+    pub fn with_block_registry(mut self, block_registry: BlockRegistry) -> RogueData {
+        self.block_registry = Some(Arc::new(block_registry));
+        self
+    }
+
+
+    pub fn with_sprite(mut self, sprite: Sprite) -> RogueData {
+        self.sprite = Some(Arc::new(sprite));
+        self
+    }
+
+
+    pub fn with_camera(mut self, camera: Camera) -> RogueData {
+        self.camera = Some(Arc::new(camera));
+        self
+    }
+
+
+    pub fn with_chunk_view(mut self, chunk_view: ChunkView) -> RogueData {
+        self.chunk_view = Some(Arc::new(chunk_view));
+        self
+    }
+
+
+    pub fn with_input_state(mut self, input_state: InputState) -> RogueData {
+        self.input_state = Some(Arc::new(input_state));
+        self
+    }
+
+
+    pub fn with_actor(mut self, actor: Actor) -> RogueData {
+        self.actor = Some(Arc::new(actor));
+        self
+    }
+
+
+    pub fn with_scheduler(mut self, scheduler: Scheduler) -> RogueData {
+        self.scheduler = Some(Arc::new(scheduler));
+        self
+    }
+
+
+    pub fn with_field_of_view(mut self, field_of_view: FieldOfView) -> RogueData {
+        self.field_of_view = Some(Arc::new(field_of_view));
+        self
+    }
+
+
+    pub fn with_ai(mut self, ai: Ai) -> RogueData {
+        self.ai = Some(Arc::new(ai));
+        self
+    }
+
+
+    pub fn with_combat(mut self, combat: Combat) -> RogueData {
+        self.combat = Some(Arc::new(combat));
+        self
+    }
+
+
+    pub fn with_item(mut self, item: Item) -> RogueData {
+        self.item = Some(Arc::new(item));
+        self
+    }
+
+
+    pub fn with_item_map(mut self, item_map: ItemMap) -> RogueData {
+        self.item_map = Some(Arc::new(item_map));
+        self
+    }
+
+
+    pub fn with_inventory(mut self, inventory: Inventory) -> RogueData {
+        self.inventory = Some(Arc::new(inventory));
+        self
+    }
+
+
     pub fn get_block_map(&self) -> Option<Arc<BlockMap>> {
         self.block_map.clone()
     }
@@ -91,6 +226,66 @@ impl RogueData {
         self.block_coords.clone()
     }
 
+    pub fn get_player(&self) -> Option<Arc<Player>> {
+        self.player.clone()
+    }
+
+    pub fn get_voxel_volume(&self) -> Option<Arc<VoxelVolume>> {
+        self.voxel_volume.clone()
+    }
+
+    pub fn get_block_registry(&self) -> Option<Arc<BlockRegistry>> {
+        self.block_registry.clone()
+    }
+
+    pub fn get_sprite(&self) -> Option<Arc<Sprite>> {
+        self.sprite.clone()
+    }
+
+    pub fn get_camera(&self) -> Option<Arc<Camera>> {
+        self.camera.clone()
+    }
+
+    pub fn get_chunk_view(&self) -> Option<Arc<ChunkView>> {
+        self.chunk_view.clone()
+    }
+
+    pub fn get_input_state(&self) -> Option<Arc<InputState>> {
+        self.input_state.clone()
+    }
+
+    pub fn get_actor(&self) -> Option<Arc<Actor>> {
+        self.actor.clone()
+    }
+
+    pub fn get_scheduler(&self) -> Option<Arc<Scheduler>> {
+        self.scheduler.clone()
+    }
+
+    pub fn get_field_of_view(&self) -> Option<Arc<FieldOfView>> {
+        self.field_of_view.clone()
+    }
+
+    pub fn get_ai(&self) -> Option<Arc<Ai>> {
+        self.ai.clone()
+    }
+
+    pub fn get_combat(&self) -> Option<Arc<Combat>> {
+        self.combat.clone()
+    }
+
+    pub fn get_item(&self) -> Option<Arc<Item>> {
+        self.item.clone()
+    }
+
+    pub fn get_item_map(&self) -> Option<Arc<ItemMap>> {
+        self.item_map.clone()
+    }
+
+    pub fn get_inventory(&self) -> Option<Arc<Inventory>> {
+        self.inventory.clone()
+    }
+
     pub fn get_mut_block_map(&mut self) -> OptErr<&mut BlockMap, RogueDataErr> {
         match self.block_map.as_mut() {
             Some(map_3d) => {
@@ -114,6 +309,379 @@ impl RogueData {
             None => return OptErr::Empty,
         }
     }
+
+    pub fn get_mut_block_coords(&mut self) -> OptErr<&mut BlockCoords, RogueDataErr> {
+        match self.block_coords.as_mut() {
+            Some(block_coords) => {
+                match Arc::get_mut(block_coords) {
+                    Some(block_coords) => return OptErr::Full(block_coords),
+                    None => return OptErr::Error(RogueDataErr::GetMut("Arc Get Mut Block Coords")),
+                }
+            },
+            None => return OptErr::Empty,
+        }
+    }
+
+    // Items lose their cell while they are carried
+    pub fn set_block_coords(&mut self, block_coords: Option<BlockCoords>) {
+        self.block_coords = block_coords.map(|block_coords| Arc::new(block_coords));
+    }
+
+    pub fn get_mut_player(&mut self) -> OptErr<&mut Player, RogueDataErr> {
+        match self.player.as_mut() {
+            Some(player) => {
+                match Arc::get_mut(player) {
+                    Some(player) => return OptErr::Full(player),
+                    None => return OptErr::Error(RogueDataErr::GetMut("Arc Get Mut Player")),
+                }
+            },
+            None => return OptErr::Empty,
+        }
+    }
+
+    pub fn get_mut_voxel_volume(&mut self) -> OptErr<&mut VoxelVolume, RogueDataErr> {
+        match self.voxel_volume.as_mut() {
+            Some(voxel_volume) => {
+                match Arc::get_mut(voxel_volume) {
+                    Some(voxel_volume) => return OptErr::Full(voxel_volume),
+                    None => return OptErr::Error(RogueDataErr::GetMut("Arc Get Mut Voxel Volume")),
+                }
+            },
+            None => return OptErr::Empty,
+        }
+    }
+
+    pub fn get_mut_block_registry(&mut self) -> OptErr<&mut BlockRegistry, RogueDataErr> {
+        match self.block_registry.as_mut() {
+            Some(block_registry) => {
+                match Arc::get_mut(block_registry) {
+                    Some(block_registry) => return OptErr::Full(block_registry),
+                    None => return OptErr::Error(RogueDataErr::GetMut("Arc Get Mut Block Registry")),
+                }
+            },
+            None => return OptErr::Empty,
+        }
+    }
+
+    pub fn get_mut_sprite(&mut self) -> OptErr<&mut Sprite, RogueDataErr> {
+        match self.sprite.as_mut() {
+            Some(sprite) => {
+                match Arc::get_mut(sprite) {
+                    Some(sprite) => return OptErr::Full(sprite),
+                    None => return OptErr::Error(RogueDataErr::GetMut("Arc Get Mut Sprite")),
+                }
+            },
+            None => return OptErr::Empty,
+        }
+    }
+
+    pub fn get_mut_camera(&mut self) -> OptErr<&mut Camera, RogueDataErr> {
+        match self.camera.as_mut() {
+            Some(camera) => {
+                match Arc::get_mut(camera) {
+                    Some(camera) => return OptErr::Full(camera),
+                    None => return OptErr::Error(RogueDataErr::GetMut("Arc Get Mut Camera")),
+                }
+            },
+            None => return OptErr::Empty,
+        }
+    }
+
+    pub fn get_mut_chunk_view(&mut self) -> OptErr<&mut ChunkView, RogueDataErr> {
+        match self.chunk_view.as_mut() {
+            Some(chunk_view) => {
+                match Arc::get_mut(chunk_view) {
+                    Some(chunk_view) => return OptErr::Full(chunk_view),
+                    None => return OptErr::Error(RogueDataErr::GetMut("Arc Get Mut Chunk View")),
+                }
+            },
+            None => return OptErr::Empty,
+        }
+    }
+
+    pub fn get_mut_input_state(&mut self) -> OptErr<&mut InputState, RogueDataErr> {
+        match self.input_state.as_mut() {
+            Some(input_state) => {
+                match Arc::get_mut(input_state) {
+                    Some(input_state) => return OptErr::Full(input_state),
+                    None => return OptErr::Error(RogueDataErr::GetMut("Arc Get Mut Input State")),
+                }
+            },
+            None => return OptErr::Empty,
+        }
+    }
+
+    pub fn get_mut_actor(&mut self) -> OptErr<&mut Actor, RogueDataErr> {
+        match self.actor.as_mut() {
+            Some(actor) => {
+                match Arc::get_mut(actor) {
+                    Some(actor) => return OptErr::Full(actor),
+                    None => return OptErr::Error(RogueDataErr::GetMut("Arc Get Mut Actor")),
+                }
+            },
+            None => return OptErr::Empty,
+        }
+    }
+
+    // Runs this actor's turn for the scheduler, returning whether it took the turn. Only the
+    // player can hold on to its turn, monsters always take theirs and anything else just waits.
+    // The acting entity is out of the world, so actors lists everyone else that might be in the way
+    pub fn act(&mut self, actors: &Vec<Id>, world: &mut World<RogueData>) -> Result<bool, RogueDataErr> {
+        let ai_action = match self.ai.as_mut() {
+            Some(ai) => {
+                match Arc::get_mut(ai) {
+                    Some(ai) => {
+                        let block_coords = match self.block_coords.as_mut() {
+                            Some(block_coords) => match Arc::get_mut(block_coords) {
+                                Some(block_coords) => block_coords,
+                                None => return Err(RogueDataErr::GetMut("Arc Get Mut Block Coords")),
+                            },
+                            None => return Err(RogueDataErr::Get("Ai Entity Block Coords")),
+                        };
+                        let field_of_view = match self.field_of_view.as_mut() {
+                            Some(field_of_view) => match Arc::get_mut(field_of_view) {
+                                Some(field_of_view) => field_of_view,
+                                None => return Err(RogueDataErr::GetMut("Arc Get Mut Field Of View")),
+                            },
+                            None => return Err(RogueDataErr::Get("Ai Entity Field Of View")),
+                        };
+                        let combat = self.combat.as_ref().map(|combat| &**combat);
+                        match ai.act(block_coords, field_of_view, combat, actors, world) {
+                            Ok(ai_action) => Some(ai_action),
+                            Err(err) => return Err(RogueDataErr::Ai("Ai Act", err)),
+                        }
+                    },
+                    None => return Err(RogueDataErr::GetMut("Arc Get Mut Ai")),
+                }
+            },
+            None => None,
+        };
+        match ai_action {
+            Some(AiAction::Attack(target)) => {
+                return match self.strike_target(target, world) {
+                    Ok(_) => Ok(true),
+                    Err(err) => Err(err),
+                };
+            },
+            Some(AiAction::Fire(aim)) => {
+                return match self.fire_at(&aim, actors, world) {
+                    Ok(_) => Ok(true),
+                    Err(err) => Err(err),
+                };
+            },
+            Some(_) => return Ok(true),
+            None => (),
+        }
+        let player_action = match self.player.as_mut() {
+            Some(player) => {
+                match Arc::get_mut(player) {
+                    Some(player) => {
+                        match self.block_coords.as_mut() {
+                            Some(block_coords) => {
+                                match Arc::get_mut(block_coords) {
+                                    Some(block_coords) => match player.act(block_coords, actors, world) {
+                                        Ok(player_action) => player_action,
+                                        Err(err) => return Err(RogueDataErr::Player("Player Act", err)),
+                                    },
+                                    None => return Err(RogueDataErr::GetMut("Arc Get Mut Block Coords")),
+                                }
+                            },
+                            None => return Err(RogueDataErr::Get("Player Entity Block Coords")),
+                        }
+                    },
+                    None => return Err(RogueDataErr::GetMut("Arc Get Mut Player")),
+                }
+            },
+            None => return Ok(true),
+        };
+        match player_action {
+            Some(PlayerAction::Attack(target)) => match self.strike_target(target, world) {
+                Ok(_) => Ok(true),
+                Err(err) => Err(err),
+            },
+            Some(PlayerAction::Fire(aim)) => self.fire_at(&aim, actors, world),
+            Some(player_action @ PlayerAction::PickUp) |
+            Some(player_action @ PlayerAction::Drop(_)) |
+            Some(player_action @ PlayerAction::Use(_)) |
+            Some(player_action @ PlayerAction::Equip(_)) |
+            Some(player_action @ PlayerAction::Unequip(_)) => self.handle_loot(player_action, world),
+            Some(_) => Ok(true),
+            None => Ok(false),
+        }
+    }
+
+    // Inventory actions, false when they come to nothing and the turn is kept. Equipment changes
+    // carry over to the combat stats
+    fn handle_loot(&mut self, player_action: PlayerAction, world: &mut World<RogueData>) -> Result<bool, RogueDataErr> {
+        let block_coords = match self.block_coords.as_ref() {
+            Some(block_coords) => **block_coords,
+            None => return Err(RogueDataErr::Get("Carrier Block Coords")),
+        };
+        let inventory = match self.inventory.as_mut() {
+            Some(inventory) => match Arc::get_mut(inventory) {
+                Some(inventory) => inventory,
+                None => return Err(RogueDataErr::GetMut("Arc Get Mut Inventory")),
+            },
+            None => return Ok(false),
+        };
+        let took_turn = match player_action {
+            PlayerAction::PickUp => match pick_up(inventory, &block_coords, world) {
+                Ok(picked) => picked.is_some(),
+                Err(err) => return Err(RogueDataErr::Loot("Pick Up", err)),
+            },
+            PlayerAction::Drop(id) => match drop_item(inventory, id, &block_coords, world) {
+                Ok(dropped) => dropped,
+                Err(err) => return Err(RogueDataErr::Loot("Drop Item", err)),
+            },
+            PlayerAction::Use(id) => match self.combat.as_mut() {
+                Some(combat) => match Arc::get_mut(combat) {
+                    Some(combat) => match use_item(inventory, combat, id, world) {
+                        Ok(used) => used,
+                        Err(err) => return Err(RogueDataErr::Loot("Use Item", err)),
+                    },
+                    None => return Err(RogueDataErr::GetMut("Arc Get Mut Combat")),
+                },
+                None => false,
+            },
+            PlayerAction::Equip(id) => inventory.equip(id).is_ok(),
+            PlayerAction::Unequip(equip_slot) => inventory.unequip(equip_slot).is_some(),
+            _ => false,
+        };
+        let bonus = inventory.get_bonus();
+        match self.combat.as_mut() {
+            Some(combat) => match Arc::get_mut(combat) {
+                Some(combat) => combat.set_bonus(bonus),
+                None => return Err(RogueDataErr::GetMut("Arc Get Mut Combat")),
+            },
+            None => (),
+        }
+        Ok(took_turn)
+    }
+
+    // Melee attack on target, None when this entity can't fight or there is nothing to hurt
+    fn strike_target(&mut self, target: Id, world: &mut World<RogueData>) -> Result<Option<AttackResult>, RogueDataErr> {
+        match self.combat.as_mut() {
+            Some(combat) => {
+                match Arc::get_mut(combat) {
+                    Some(combat) => match strike(combat, target, world) {
+                        Ok(attack_result) => Ok(attack_result),
+                        Err(err) => Err(RogueDataErr::Attack("Strike", err)),
+                    },
+                    None => Err(RogueDataErr::GetMut("Arc Get Mut Combat")),
+                }
+            },
+            None => Ok(None),
+        }
+    }
+
+    // Ranged attack towards aim, false without a ranged attack to make
+    fn fire_at(&mut self, aim: &BlockCoords, actors: &Vec<Id>, world: &mut World<RogueData>) -> Result<bool, RogueDataErr> {
+        let from = match self.block_coords.as_ref() {
+            Some(block_coords) => **block_coords,
+            None => return Err(RogueDataErr::Get("Attacker Block Coords")),
+        };
+        match self.combat.as_mut() {
+            Some(combat) => {
+                match Arc::get_mut(combat) {
+                    Some(combat) => {
+                        if combat.get_range() == 0 {
+                            return Ok(false);
+                        }
+                        match fire(combat, &from, aim, actors, world) {
+                            Ok(_) => Ok(true),
+                            Err(err) => Err(RogueDataErr::Attack("Fire", err)),
+                        }
+                    },
+                    None => Err(RogueDataErr::GetMut("Arc Get Mut Combat")),
+                }
+            },
+            None => Ok(false),
+        }
+    }
+
+    pub fn get_mut_scheduler(&mut self) -> OptErr<&mut Scheduler, RogueDataErr> {
+        match self.scheduler.as_mut() {
+            Some(scheduler) => {
+                match Arc::get_mut(scheduler) {
+                    Some(scheduler) => return OptErr::Full(scheduler),
+                    None => return OptErr::Error(RogueDataErr::GetMut("Arc Get Mut Scheduler")),
+                }
+            },
+            None => return OptErr::Empty,
+        }
+    }
+
+    pub fn get_mut_field_of_view(&mut self) -> OptErr<&mut FieldOfView, RogueDataErr> {
+        match self.field_of_view.as_mut() {
+            Some(field_of_view) => {
+                match Arc::get_mut(field_of_view) {
+                    Some(field_of_view) => return OptErr::Full(field_of_view),
+                    None => return OptErr::Error(RogueDataErr::GetMut("Arc Get Mut Field Of View")),
+                }
+            },
+            None => return OptErr::Empty,
+        }
+    }
+
+    pub fn get_mut_ai(&mut self) -> OptErr<&mut Ai, RogueDataErr> {
+        match self.ai.as_mut() {
+            Some(ai) => {
+                match Arc::get_mut(ai) {
+                    Some(ai) => return OptErr::Full(ai),
+                    None => return OptErr::Error(RogueDataErr::GetMut("Arc Get Mut Ai")),
+                }
+            },
+            None => return OptErr::Empty,
+        }
+    }
+
+    pub fn get_mut_combat(&mut self) -> OptErr<&mut Combat, RogueDataErr> {
+        match self.combat.as_mut() {
+            Some(combat) => {
+                match Arc::get_mut(combat) {
+                    Some(combat) => return OptErr::Full(combat),
+                    None => return OptErr::Error(RogueDataErr::GetMut("Arc Get Mut Combat")),
+                }
+            },
+            None => return OptErr::Empty,
+        }
+    }
+
+    pub fn get_mut_item(&mut self) -> OptErr<&mut Item, RogueDataErr> {
+        match self.item.as_mut() {
+            Some(item) => {
+                match Arc::get_mut(item) {
+                    Some(item) => return OptErr::Full(item),
+                    None => return OptErr::Error(RogueDataErr::GetMut("Arc Get Mut Item")),
+                }
+            },
+            None => return OptErr::Empty,
+        }
+    }
+
+    pub fn get_mut_item_map(&mut self) -> OptErr<&mut ItemMap, RogueDataErr> {
+        match self.item_map.as_mut() {
+            Some(item_map) => {
+                match Arc::get_mut(item_map) {
+                    Some(item_map) => return OptErr::Full(item_map),
+                    None => return OptErr::Error(RogueDataErr::GetMut("Arc Get Mut Item Map")),
+                }
+            },
+            None => return OptErr::Empty,
+        }
+    }
+
+    pub fn get_mut_inventory(&mut self) -> OptErr<&mut Inventory, RogueDataErr> {
+        match self.inventory.as_mut() {
+            Some(inventory) => {
+                match Arc::get_mut(inventory) {
+                    Some(inventory) => return OptErr::Full(inventory),
+                    None => return OptErr::Error(RogueDataErr::GetMut("Arc Get Mut Inventory")),
+                }
+            },
+            None => return OptErr::Empty,
+        }
+    }
 }
 
 impl EntityData<RogueData> for RogueData {
@@ -137,10 +705,200 @@ impl EntityData<RogueData> for RogueData {
             },
             None => (),
         }
+        // Saving only reads the world, so the input entity can do it. A failed save is reported
+        // and play goes on
+        match self.input_state.as_ref() {
+            Some(input_state) if input_state.was_pressed(Action::Save) && self.saved_frame != Some(input_state.get_frame()) => {
+                self.saved_frame = Some(input_state.get_frame());
+                match save_world(world).and_then(|save_file| save_file.write(SAVE_PATH)) {
+                    Ok(()) => println!("Saved to {}", SAVE_PATH),
+                    Err(err) => println!("Save Failed: {}", err),
+                }
+            },
+            _ => (),
+        }
+        match self.block.as_mut() {
+            Some(block) => {
+                match Arc::get_mut(block) {
+                    Some(block) => {
+                        match self.block_coords.clone() {
+                            Some(block_coords) => match block.tick_mut(&block_coords, world) {
+                                Ok(()) => (),
+                                Err(err) => return Err(Box::new(RogueDataErr::Block("Block Tick Mut", Box::new(err)))),
+                            },
+                            None => return Err(Box::new(RogueDataErr::Get("Block Entity Block Coords"))),
+                        }
+                    },
+                    None => return Err(Box::new(RogueDataErr::GetMut("Arc Get Mut Block"))),
+                }
+            },
+            None => (),
+        }
+        match self.player.as_mut() {
+            Some(player) => {
+                match Arc::get_mut(player) {
+                    Some(player) => match player.tick_mut(world) {
+                        Ok(()) => (),
+                        Err(err) => return Err(Box::new(RogueDataErr::Player("Player Tick Mut", err))),
+                    },
+                    None => return Err(Box::new(RogueDataErr::GetMut("Arc Get Mut Player"))),
+                }
+            },
+            None => (),
+        }
+        match self.scheduler.as_mut() {
+            Some(scheduler) => {
+                match Arc::get_mut(scheduler) {
+                    Some(scheduler) => {
+                        let was_over = scheduler.is_game_over();
+                        match scheduler.tick_mut(world) {
+                            Ok(()) => (),
+                            Err(err) => return Err(Box::new(RogueDataErr::Scheduler("Scheduler Tick Mut", err))),
+                        }
+                        if scheduler.is_game_over() && !was_over {
+                            println!("You died on turn {}", scheduler.get_turn());
+                        }
+                    },
+                    None => return Err(Box::new(RogueDataErr::GetMut("Arc Get Mut Scheduler"))),
+                }
+            },
+            None => (),
+        }
+        // Monsters only look on their own turns, when the player is close enough to matter
+        match self.field_of_view.as_mut() {
+            Some(_) if self.ai.is_some() => (),
+            Some(field_of_view) => {
+                match Arc::get_mut(field_of_view) {
+                    Some(field_of_view) => match self.block_coords.as_ref() {
+                        Some(block_coords) => match field_of_view.tick_mut(block_coords, world) {
+                            Ok(()) => (),
+                            Err(err) => return Err(Box::new(RogueDataErr::FieldOfView("Field Of View Tick Mut", err))),
+                        },
+                        None => return Err(Box::new(RogueDataErr::Get("Field Of View Entity Block Coords"))),
+                    },
+                    None => return Err(Box::new(RogueDataErr::GetMut("Arc Get Mut Field Of View"))),
+                }
+            },
+            None => (),
+        }
+        match self.camera.as_mut() {
+            Some(camera) => {
+                match Arc::get_mut(camera) {
+                    Some(camera) => match camera.tick_mut(world) {
+                        Ok(()) => (),
+                        Err(err) => return Err(Box::new(RogueDataErr::Camera("Camera Tick Mut", err))),
+                    },
+                    None => return Err(Box::new(RogueDataErr::GetMut("Arc Get Mut Camera"))),
+                }
+            },
+            None => (),
+        }
+        let camera = match world.get_entity_by_name(CAMERA_NAME) {
+            Some(camera_entity) => camera_entity.get_camera(),
+            None => None,
+        };
+        let camera = match camera {
+            Some(camera) => camera,
+            None => Arc::new(Camera::new(world.get_aspect_ratio())),
+        };
+        // Carried items are out of sight until they are put down again, and everything but the
+        // player is only drawn while the player can see it
+        let carried = self.item.is_some() && self.block_coords.is_none();
+        let player_sight = match world.get_entity_by_name(PLAYER_NAME) {
+            Some(player_entity) => player_entity.get_field_of_view(),
+            None => None,
+        };
+        let unseen = self.player.is_none() && match (player_sight, self.block_coords.as_ref()) {
+            (Some(field_of_view), Some(block_coords)) => !field_of_view.is_visible(block_coords),
+            _ => false,
+        };
+        match self.sprite.as_mut() {
+            Some(sprite) if carried || unseen => {
+                self.renderable = None;
+                match Arc::get_mut(sprite) {
+                    Some(sprite) => sprite.set_dirty(),
+                    None => return Err(Box::new(RogueDataErr::GetMut("Arc Get Mut Sprite"))),
+                }
+            },
+            Some(sprite) => {
+                match Arc::get_mut(sprite) {
+                    Some(sprite) => {
+                        sprite.tick(tick_count);
+                        match self.player.as_ref() {
+                            Some(player) => sprite.set_facing(player.get_facing()),
+                            None => match self.ai.as_ref() {
+                                Some(ai) => sprite.set_facing(ai.get_facing()),
+                                None => (),
+                            },
+                        }
+                        sprite.set_view(camera.get_billboard_view());
+                        match self.block_coords.as_ref() {
+                            Some(block_coords) => sprite.set_block_coords(**block_coords),
+                            None => return Err(Box::new(RogueDataErr::Get("Sprite Entity Block Coords"))),
+                        }
+                        if sprite.is_dirty() {
+                            match sprite.get_block_coords() {
+                                Some(block_coords) => {
+                                    self.renderable = Some(Arc::new(new_sprite_renderable(sprite, &block_coords, &camera, manager)));
+                                    self.camera_version = Some(camera.get_version());
+                                    sprite.set_clean();
+                                },
+                                None => return Err(Box::new(RogueDataErr::Get("Sprite Get Block Coords"))),
+                            }
+                        }
+                    },
+                    None => return Err(Box::new(RogueDataErr::GetMut("Arc Get Mut Sprite"))),
+                }
+            },
+            None => (),
+        }
+        match self.chunk_view.as_mut() {
+            Some(chunk_view) => {
+                match Arc::get_mut(chunk_view) {
+                    Some(chunk_view) => match chunk_view.tick_mut(&camera.get_cutaway(), world) {
+                        Ok(Some(chunk_mesh)) => {
+                            self.renderable = Some(Arc::new(new_textured_chunk_renderable(&chunk_mesh, chunk_view, &camera, manager)));
+                            self.camera_version = Some(camera.get_version());
+                        },
+                        Ok(None) => (),
+                        Err(err) => return Err(Box::new(RogueDataErr::ChunkView("Chunk View Tick Mut", err))),
+                    },
+                    None => return Err(Box::new(RogueDataErr::GetMut("Arc Get Mut Chunk View"))),
+                }
+            },
+            None => (),
+        }
+        if self.camera_version != Some(camera.get_version()) {
+            match self.renderable.as_mut() {
+                Some(renderable) => {
+                    match Arc::get_mut(renderable) {
+                        Some(renderable) => {
+                            renderable.set_perspective(camera.get_projection());
+                            renderable.set_view(camera.get_view());
+                            self.camera_version = Some(camera.get_version());
+                        },
+                        None => return Err(Box::new(RogueDataErr::GetMut("Arc Get Mut Renderable"))),
+                    }
+                },
+                None => (),
+            }
+        }
         Ok(())
     }
 
     fn render(&mut self, window: &mut Window, sync_data: &mut SyncData, renderers: &mut Renderers) -> Result<(), Box<Error>> {
+        match self.input_state.as_mut() {
+            Some(input_state) => {
+                match Arc::get_mut(input_state) {
+                    Some(input_state) => {
+                        input_state.poll_window(window);
+                        input_state.update();
+                    },
+                    None => return Err(Box::new(RogueDataErr::GetMut("Arc Get Mut Input State"))),
+                }
+            },
+            None => (),
+        }
         match self.renderable.as_mut() {
             Some(renderable) => {
                 match Arc::get_mut(renderable) {
@@ -196,6 +954,16 @@ pub enum RogueDataErr {
     Renderable(&'static str, RenderableErr),
     Transform(&'static str, TransformErr),
     Scene(&'static str, Box<Error>),
+    Player(&'static str, PlayerErr),
+    Block(&'static str, Box<BlockErr>),
+    Camera(&'static str, CameraErr),
+    Scheduler(&'static str, SchedulerErr),
+    ChunkView(&'static str, ChunkViewErr),
+    FieldOfView(&'static str, FieldOfViewErr),
+    Ai(&'static str, AiErr),
+    Attack(&'static str, AttackErr),
+    Loot(&'static str, LootErr),
+    Get(&'static str),
     GetMut(&'static str),
 }
 
@@ -205,6 +973,16 @@ impl fmt::Display for RogueDataErr {
             RogueDataErr::Renderable(_, ref err) => err.fmt(f),
             RogueDataErr::Transform(_, ref err) => err.fmt(f),
             RogueDataErr::Scene(_, ref err) => err.fmt(f),
+            RogueDataErr::Player(_, ref err) => err.fmt(f),
+            RogueDataErr::Block(_, ref err) => err.fmt(f),
+            RogueDataErr::Camera(_, ref err) => err.fmt(f),
+            RogueDataErr::Scheduler(_, ref err) => err.fmt(f),
+            RogueDataErr::ChunkView(_, ref err) => err.fmt(f),
+            RogueDataErr::FieldOfView(_, ref err) => err.fmt(f),
+            RogueDataErr::Ai(_, ref err) => err.fmt(f),
+            RogueDataErr::Attack(_, ref err) => err.fmt(f),
+            RogueDataErr::Loot(_, ref err) => err.fmt(f),
+            RogueDataErr::Get(_) => write!(f, "Get was None"),
             RogueDataErr::GetMut(_) => write!(f, "Get Mut was None"),
         }
     }
@@ -216,6 +994,16 @@ impl Error for RogueDataErr {
             RogueDataErr::Renderable(_, ref err) => err.description(),
             RogueDataErr::Transform(_, ref err) => err.description(),
             RogueDataErr::Scene(_, ref err) => err.description(),
+            RogueDataErr::Player(_, ref err) => err.description(),
+            RogueDataErr::Block(_, ref err) => err.description(),
+            RogueDataErr::Camera(_, ref err) => err.description(),
+            RogueDataErr::Scheduler(_, ref err) => err.description(),
+            RogueDataErr::ChunkView(_, ref err) => err.description(),
+            RogueDataErr::FieldOfView(_, ref err) => err.description(),
+            RogueDataErr::Ai(_, ref err) => err.description(),
+            RogueDataErr::Attack(_, ref err) => err.description(),
+            RogueDataErr::Loot(_, ref err) => err.description(),
+            RogueDataErr::Get(_) => "Get was None",
             RogueDataErr::GetMut(_) => "Get Mut was None",
         }
     }
@@ -0,0 +1,412 @@
+use std::fmt;
+use std::error::{Error};
+use std::sync::{Arc};
+
+use dorp::{
+    Renderable, Transform, IdManager, Id, IdType, Mat4, Vec3, RenderableTexture2d, Scene,
+    WorldErr
+};
+use dorp::graphics::{texture2d};
+
+use core::{RogueData, RogueWorld, Scheduler, Rng, BEING_SPRITE, ITEM_SPRITE, PLAYER_NAME};
+use components::{
+    BlockRegistry, BlockCoords, BlockType, VoxelVolume, Sprite, SpriteSheet, Camera, ChunkView, MonsterRegistry, MonsterDef,
+    ItemRegistry, ItemDef
+};
+use graphics::{
+    TexturedChunkMesh, Atlas, AtlasErr, mesh_volume_textured, get_quad_tex_coords
+};
+use assets::{AssetManifest, AssetLoader, AssetErr};
+use generation::{LevelKind, LevelFile};
+use pathing::{NavGrid, DijkstraMap};
+use save::{load_level_chunks};
+use entities::{
+    new_player_entity, PlayerEntityErr, new_block_map_entity, BlockMapEntityErr, new_block_entity,
+    BlockEntityErr, new_voxel_volume_entity, VoxelVolumeEntityErr, new_block_registry_entity,
+    BlockRegistryEntityErr, new_camera_entity, CameraEntityErr, new_scheduler_entity, SchedulerEntityErr,
+    new_monster_entity, MonsterEntityErr, new_item_entity, ItemEntityErr, new_item_map_entity, ItemMapEntityErr
+};
+
+// How bright remembered blocks are drawn next to visible ones
+static REMEMBERED_BRIGHTNESS: f32 = 0.45;
+// Monsters spawned per level, at least a walk of MONSTER_MIN_COST from the player
+static MONSTER_COUNT: usize = 6;
+static MONSTER_MIN_COST: i64 = 10;
+static MONSTER_MAX_COST: i64 = 400;
+// Items lying around per level, anywhere the player can walk to that isn't underfoot
+static ITEM_COUNT: usize = 8;
+static ITEM_MIN_COST: i64 = 2;
+// How much smaller items are drawn than beings
+static ITEM_SCALE: f32 = 0.6;
+
+// With a chunk path the level's blocks are kept there between runs, see load_level_chunks
+pub fn new_scene(level_kind: LevelKind, block_registry: BlockRegistry, monster_registry: MonsterRegistry, item_registry: ItemRegistry, asset_manifest: AssetManifest, seed: u64, chunk_path: Option<String>) -> Scene<RogueData> {
+    Scene::new(Box::new(move |manager, world| {
+        let (voxel_volume, spawn) = match chunk_path {
+            Some(ref chunk_path) => match load_level_chunks(chunk_path, &level_kind, seed, &block_registry) {
+                Ok((voxel_volume, spawn, chunk_load)) => {
+                    println!("Chunks: {}", chunk_load);
+                    (voxel_volume, spawn)
+                },
+                Err(err) => return Err(Box::new(err)),
+            },
+            None => level_kind.build_volume(seed),
+        };
+        match build_generated_scene(voxel_volume, spawn, &block_registry, &monster_registry, &item_registry, &asset_manifest, seed, manager, world) {
+            Ok(_) => {
+                println!("Scene Loaded");
+                Ok(())
+            },
+            Err(err) => Err(Box::new(err)),
+        }
+    }))
+}
+
+pub fn build_scene(level_kind: &LevelKind, block_registry: &BlockRegistry, monster_registry: &MonsterRegistry, item_registry: &ItemRegistry, asset_manifest: &AssetManifest, seed: u64, manager: &mut IdManager, world: &mut RogueWorld) -> Result<Vec<Id>, SceneErr> {
+    let (voxel_volume, spawn) = level_kind.build_volume(seed);
+    build_generated_scene(voxel_volume, spawn, block_registry, monster_registry, item_registry, asset_manifest, seed, manager, world)
+}
+
+// The level, player, monsters and items for blocks that came out of level generation, whether
+// just now or from a chunk file. Spawns are picked from the blocks and seed alone so both match
+pub fn build_generated_scene(voxel_volume: VoxelVolume, spawn: BlockCoords, block_registry: &BlockRegistry, monster_registry: &MonsterRegistry, item_registry: &ItemRegistry, asset_manifest: &AssetManifest, seed: u64, manager: &mut IdManager, world: &mut RogueWorld) -> Result<Vec<Id>, SceneErr> {
+    let monster_spawns = pick_monster_spawns(&voxel_volume, block_registry, monster_registry, &spawn, MONSTER_COUNT, seed);
+    let item_spawns = pick_item_spawns(&voxel_volume, block_registry, item_registry, &spawn, ITEM_COUNT, seed);
+    build_populated_level(voxel_volume, spawn, monster_spawns, item_spawns, block_registry, asset_manifest, manager, world)
+}
+
+pub fn new_level_file_scene(level_file: LevelFile, block_registry: BlockRegistry, asset_manifest: AssetManifest, seed: u64) -> Scene<RogueData> {
+    Scene::new(Box::new(move |manager, world| {
+        match build_level_file(&level_file, &block_registry, &asset_manifest, seed, manager, world) {
+            Ok(_) => {
+                println!("Level Loaded");
+                Ok(())
+            },
+            Err(err) => Err(Box::new(err)),
+        }
+    }))
+}
+
+// A hand drawn level with everything where it was drawn. The seed only drives the monsters' rolls
+pub fn build_level_file(level_file: &LevelFile, block_registry: &BlockRegistry, asset_manifest: &AssetManifest, seed: u64, manager: &mut IdManager, world: &mut RogueWorld) -> Result<Vec<Id>, SceneErr> {
+    let mut rng = Rng::new(seed ^ 0x6D6F6E7374657273);
+    let monster_spawns = level_file.get_monsters().iter()
+        .map(|&(block_coords, ref monster_def)| (block_coords, monster_def, rng.next_u64()))
+        .collect();
+    let item_spawns = level_file.get_items().iter()
+        .map(|&(block_coords, ref item_def)| (block_coords, item_def))
+        .collect();
+    build_populated_level(level_file.get_voxel_volume().clone(), level_file.get_spawn(), monster_spawns, item_spawns, block_registry, asset_manifest, manager, world)
+}
+
+// The level with a fresh scheduler and item map, the items, the player at spawn and the monsters,
+// each monster with the seed for its own rolls
+pub fn build_populated_level(voxel_volume: VoxelVolume, spawn: BlockCoords, monster_spawns: Vec<(BlockCoords, &MonsterDef, u64)>, item_spawns: Vec<(BlockCoords, &ItemDef)>, block_registry: &BlockRegistry, asset_manifest: &AssetManifest, manager: &mut IdManager, world: &mut RogueWorld) -> Result<Vec<Id>, SceneErr> {
+    let scale = get_volume_scale(&voxel_volume);
+
+    let mut asset_loader = AssetLoader::new(asset_manifest.clone());
+    let mut ids = match build_level(voxel_volume, spawn, block_registry, &mut asset_loader, manager, world) {
+        Ok(ids) => ids,
+        Err(err) => return Err(err),
+    };
+
+    match new_scheduler_entity(Scheduler::new(), manager, world) {
+        Ok(id) => ids.push(id),
+        Err(err) => return Err(SceneErr::SchedulerEntity("New Scheduler Entity", err)),
+    }
+
+    match new_item_map_entity(manager, world) {
+        Ok(id) => ids.push(id),
+        Err(err) => return Err(SceneErr::ItemMapEntity("New Item Map Entity", err)),
+    }
+
+    for (block_coords, item_def) in item_spawns {
+        let sprite = match new_item_sprite(item_def, scale, &mut asset_loader, manager) {
+            Ok(sprite) => sprite,
+            Err(err) => return Err(err),
+        };
+        match new_item_entity(item_def, block_coords, Some(sprite), manager, world) {
+            Ok(id) => ids.push(id),
+            Err(err) => return Err(SceneErr::ItemEntity("New Item Entity", err)),
+        }
+    }
+
+    let sprite = match new_being_sprite(BEING_SPRITE, scale, &mut asset_loader, manager) {
+        Ok(sprite) => sprite,
+        Err(err) => return Err(err),
+    };
+    match new_player_entity(spawn, Some(sprite), manager, world) {
+        Ok(id) => ids.push(id),
+        Err(err) => return Err(SceneErr::PlayerEntity("New Player Entity", err)),
+    }
+
+    for (block_coords, monster_def, monster_seed) in monster_spawns {
+        let sprite = match new_being_sprite(monster_def.get_sprite().unwrap_or(BEING_SPRITE), scale, &mut asset_loader, manager) {
+            Ok(sprite) => sprite,
+            Err(err) => return Err(err),
+        };
+        match new_monster_entity(monster_def, block_coords, Some(sprite), monster_seed, manager, world) {
+            Ok(id) => ids.push(id),
+            Err(err) => return Err(SceneErr::MonsterEntity("New Monster Entity", err)),
+        }
+    }
+    Ok(ids)
+}
+
+// Everything that comes from the voxel volume alone: the registry and block map entities, block
+// entities for special blocks, the chunk views and the camera looking at focus. Generated and
+// loaded levels share this, the actors and items on top are up to the caller
+pub fn build_level(voxel_volume: VoxelVolume, focus: BlockCoords, block_registry: &BlockRegistry, asset_loader: &mut AssetLoader, manager: &mut IdManager, world: &mut RogueWorld) -> Result<Vec<Id>, SceneErr> {
+    let mut ids = vec!();
+
+    match new_block_registry_entity(block_registry.clone(), manager, world) {
+        Ok(id) => ids.push(id),
+        Err(err) => return Err(SceneErr::BlockRegistryEntity("New Block Registry Entity", err)),
+    }
+
+    match new_block_map_entity(manager, world) {
+        Ok(id) => ids.push(id),
+        Err(err) => return Err(SceneErr::BlockMapEntity("New Block Map Entity", err)),
+    }
+
+    for (block_coords, block_type) in voxel_volume.get_special_blocks(block_registry) {
+        match new_block_entity(block_type, block_coords, manager, world) {
+            Ok(id) => ids.push(id),
+            Err(err) => return Err(SceneErr::BlockEntity("New Block Entity", err)),
+        }
+    }
+
+    let atlas = match Atlas::for_blocks(block_registry, asset_loader) {
+        Ok(atlas) => Arc::new(atlas.with_dimmed(REMEMBERED_BRIGHTNESS)),
+        Err(err) => return Err(SceneErr::Atlas("Atlas For Blocks", err)),
+    };
+    let texture_id = Id::new(manager, IdType::Texture);
+
+    let scale = Vec3::from(get_volume_scale(&voxel_volume));
+    let camera = Camera::new(world.get_aspect_ratio())
+        .with_scale(scale.get_vals())
+        .with_focus([focus.get_x() as f32 + 0.5, focus.get_y() as f32, focus.get_z() as f32 + 0.5])
+        .with_follow(PLAYER_NAME);
+    let cutaway = camera.get_cutaway().with_focus(focus);
+    for chunk_mesh in mesh_volume_textured(&voxel_volume, block_registry, &atlas, &cutaway, None) {
+        let id = Id::new(manager, IdType::Entity);
+        let chunk_view = ChunkView::new(
+            chunk_mesh.get_chunk_coords(),
+            atlas.clone(),
+            cutaway,
+            texture_id,
+            Id::new(manager, IdType::Vertex),
+            Id::new(manager, IdType::Matrix)
+        );
+        let renderable = new_textured_chunk_renderable(&chunk_mesh, &chunk_view, &camera, manager);
+        let mut transform = Transform::new();
+        transform.set_scalation(scale);
+        match world.add_entity(RogueData::new(id)
+            .with_renderable(renderable)
+            .with_transform(transform)
+            .with_chunk_view(chunk_view)
+        ) {
+            Ok(()) => ids.push(id),
+            Err(err) => return Err(SceneErr::World("World Add Entity", err)),
+        }
+    }
+
+    match new_voxel_volume_entity(voxel_volume, manager, world) {
+        Ok(id) => ids.push(id),
+        Err(err) => return Err(SceneErr::VoxelVolumeEntity("New Voxel Volume Entity", err)),
+    }
+
+    match new_camera_entity(camera, manager, world) {
+        Ok(id) => ids.push(id),
+        Err(err) => return Err(SceneErr::CameraEntity("New Camera Entity", err)),
+    }
+    Ok(ids)
+}
+
+// Squeezes the whole volume into the unit cube the camera looks at
+pub fn get_volume_scale(voxel_volume: &VoxelVolume) -> [f32; 3] {
+    [
+        1.0 / voxel_volume.get_width() as f32,
+        1.0 / voxel_volume.get_height() as f32,
+        1.0 / voxel_volume.get_depth() as f32
+    ]
+}
+
+pub fn new_being_sprite(image_name: &str, scale: [f32; 3], asset_loader: &mut AssetLoader, manager: &mut IdManager) -> Result<Sprite, SceneErr> {
+    match asset_loader.load_image(image_name) {
+        Ok(image) => Ok(Sprite::new(image_name, image, SpriteSheet::new(1, 1), manager).with_scale(scale)),
+        Err(err) => Err(SceneErr::Asset("Asset Loader Load Image Being Sprite", err)),
+    }
+}
+
+// Items are drawn smaller than beings
+pub fn new_item_sprite(item_def: &ItemDef, scale: [f32; 3], asset_loader: &mut AssetLoader, manager: &mut IdManager) -> Result<Sprite, SceneErr> {
+    let image_name = item_def.get_sprite().unwrap_or(ITEM_SPRITE);
+    match asset_loader.load_image(image_name) {
+        Ok(image) => Ok(Sprite::new(image_name, image, SpriteSheet::new(1, 1), manager)
+            .with_scale([scale[0] * ITEM_SCALE, scale[1] * ITEM_SCALE, scale[2] * ITEM_SCALE])),
+        Err(err) => Err(SceneErr::Asset("Asset Loader Load Image Item Sprite", err)),
+    }
+}
+
+// Where count monsters go, each with the seed for its ai. Spots are standing cells the player
+// can walk to through doors without starting next to it, monster types are picked evenly
+pub fn pick_monster_spawns<'a>(voxel_volume: &VoxelVolume, block_registry: &BlockRegistry, monster_registry: &'a MonsterRegistry, spawn: &BlockCoords, count: usize, seed: u64) -> Vec<(BlockCoords, &'a MonsterDef, u64)> {
+    let monster_defs = monster_registry.get_defs();
+    if monster_defs.is_empty() {
+        return vec!();
+    }
+    let mut spots = get_reachable_spots(voxel_volume, block_registry, spawn, MONSTER_MIN_COST);
+    let mut rng = Rng::new(seed ^ 0x6D6F6E7374657273);
+    let mut picked = vec!();
+    while picked.len() < count && !spots.is_empty() {
+        let index = rng.range_usize(0, spots.len());
+        let block_coords = spots.swap_remove(index);
+        let monster_def = &monster_defs[rng.range_usize(0, monster_defs.len())];
+        picked.push((block_coords, monster_def, rng.next_u64()));
+    }
+    picked
+}
+
+// Where count items go, picked evenly from the item types, all on cells the player can walk to
+pub fn pick_item_spawns<'a>(voxel_volume: &VoxelVolume, block_registry: &BlockRegistry, item_registry: &'a ItemRegistry, spawn: &BlockCoords, count: usize, seed: u64) -> Vec<(BlockCoords, &'a ItemDef)> {
+    let item_defs = item_registry.get_defs();
+    if item_defs.is_empty() {
+        return vec!();
+    }
+    let mut spots = get_reachable_spots(voxel_volume, block_registry, spawn, ITEM_MIN_COST);
+    let mut rng = Rng::new(seed ^ 0x6974656D73);
+    let mut picked = vec!();
+    while picked.len() < count && !spots.is_empty() {
+        let index = rng.range_usize(0, spots.len());
+        let block_coords = spots.swap_remove(index);
+        picked.push((block_coords, &item_defs[rng.range_usize(0, item_defs.len())]));
+    }
+    picked
+}
+
+// Standing cells the player can walk to, at least min_cost away. The nav grid with doors open
+// takes the same steps as Player::get_step, climbs and drops included
+fn get_reachable_spots(voxel_volume: &VoxelVolume, block_registry: &BlockRegistry, spawn: &BlockCoords, min_cost: i64) -> Vec<BlockCoords> {
+    let nav_grid = NavGrid::new(voxel_volume, block_registry).with_passable(BlockType::DOOR);
+    let reach = DijkstraMap::new(&nav_grid, &[*spawn], MONSTER_MAX_COST);
+    let mut spots: Vec<BlockCoords> = reach.get_values().iter()
+        .filter(|&(_, cost)| *cost >= min_cost)
+        .map(|(block_coords, _)| *block_coords)
+        .collect();
+    // Map order is random, sort so the same seed gives the same level
+    spots.sort_by_key(|block_coords| (block_coords.get_x(), block_coords.get_y(), block_coords.get_z()));
+    spots
+}
+
+// Every chunk names the atlas by the same texture id, but each new mesh still copies the atlas
+// pixels into its renderable since set_texture takes them by value. Vertex and model ids stay
+// the chunk's own across meshes
+pub fn new_textured_chunk_renderable(chunk_mesh: &TexturedChunkMesh, chunk_view: &ChunkView, camera: &Camera, manager: &mut IdManager) -> Renderable {
+    let atlas = chunk_view.get_atlas();
+    let vertices = chunk_mesh.get_vertices().iter()
+        .map(|vertex| texture2d::Vertex::new(vertex.get_position(), vertex.get_tex_coord()))
+        .collect();
+    let indices = chunk_mesh.get_indices().clone();
+
+    let mut texture = RenderableTexture2d::new(manager);
+    texture.set_indices(indices);
+    texture.set_vertex_id(chunk_view.get_vertex_id());
+    texture.set_vertices(vertices);
+    texture.set_texture_id(chunk_view.get_texture_id());
+    texture.set_texture(atlas.get_image().clone().into_raw(), atlas.get_image().dimensions());
+    texture.set_draw_method(texture2d::DrawMethod::Both(texture2d::DepthTestMethod::IfLess, texture2d::CullingMethod::CounterClockwise));
+    texture.set_perspective(camera.get_projection());
+    texture.set_view(camera.get_view());
+    texture.set_model_id(chunk_view.get_model_id());
+    texture.set_model(Mat4::identity());
+
+    let mut renderable = Renderable::new();
+    renderable.set_texture2d(texture);
+    renderable
+}
+
+pub fn new_sprite_renderable(sprite: &Sprite, block_coords: &BlockCoords, camera: &Camera, manager: &mut IdManager) -> Renderable {
+    let scale = sprite.get_scale();
+    let view = &camera.get_billboard_view();
+    let (frame, flip) = sprite.get_frame_rect(view);
+    let corners = view.get_quad(block_coords, 1.0, 1.0);
+    let tex_coords = get_quad_tex_coords(&frame, flip);
+    let vertices = corners.iter().zip(tex_coords.iter())
+        .map(|(corner, tex_coord)| texture2d::Vertex::new([corner[0] * scale[0], corner[1] * scale[1], corner[2] * scale[2]], *tex_coord))
+        .collect();
+    let image = sprite.get_image();
+
+    let mut texture = RenderableTexture2d::new(manager);
+    texture.set_indices(vec!(0, 1, 2, 2, 3, 0));
+    texture.set_vertex_id(sprite.get_vertex_id());
+    texture.set_vertices(vertices);
+    texture.set_texture_id(sprite.get_texture_id());
+    texture.set_texture((*image).clone().into_raw(), image.dimensions());
+    texture.set_draw_method(texture2d::DrawMethod::Both(texture2d::DepthTestMethod::IfLess, texture2d::CullingMethod::CounterClockwise));
+    texture.set_perspective(camera.get_projection());
+    texture.set_view(camera.get_view());
+    texture.set_model_id(sprite.get_model_id());
+    texture.set_model(Mat4::identity());
+
+    let mut renderable = Renderable::new();
+    renderable.set_texture2d(texture);
+    renderable
+}
+
+#[derive(Debug)]
+pub enum SceneErr {
+    World(&'static str, WorldErr),
+    Atlas(&'static str, AtlasErr),
+    Asset(&'static str, AssetErr),
+    BlockMapEntity(&'static str, BlockMapEntityErr),
+    BlockRegistryEntity(&'static str, BlockRegistryEntityErr),
+    CameraEntity(&'static str, CameraEntityErr),
+    SchedulerEntity(&'static str, SchedulerEntityErr),
+    BlockEntity(&'static str, BlockEntityErr),
+    VoxelVolumeEntity(&'static str, VoxelVolumeEntityErr),
+    PlayerEntity(&'static str, PlayerEntityErr),
+    MonsterEntity(&'static str, MonsterEntityErr),
+    ItemMapEntity(&'static str, ItemMapEntityErr),
+    ItemEntity(&'static str, ItemEntityErr),
+}
+
+impl fmt::Display for SceneErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SceneErr::World(_, ref err) => err.fmt(f),
+            SceneErr::Atlas(_, ref err) => err.fmt(f),
+            SceneErr::Asset(_, ref err) => err.fmt(f),
+            SceneErr::BlockMapEntity(_, ref err) => err.fmt(f),
+            SceneErr::BlockRegistryEntity(_, ref err) => err.fmt(f),
+            SceneErr::CameraEntity(_, ref err) => err.fmt(f),
+            SceneErr::SchedulerEntity(_, ref err) => err.fmt(f),
+            SceneErr::BlockEntity(_, ref err) => err.fmt(f),
+            SceneErr::VoxelVolumeEntity(_, ref err) => err.fmt(f),
+            SceneErr::PlayerEntity(_, ref err) => err.fmt(f),
+            SceneErr::MonsterEntity(_, ref err) => err.fmt(f),
+            SceneErr::ItemMapEntity(_, ref err) => err.fmt(f),
+            SceneErr::ItemEntity(_, ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for SceneErr {
+    fn description(&self) -> &str {
+        match *self {
+            SceneErr::World(_, ref err) => err.description(),
+            SceneErr::Atlas(_, ref err) => err.description(),
+            SceneErr::Asset(_, ref err) => err.description(),
+            SceneErr::BlockMapEntity(_, ref err) => err.description(),
+            SceneErr::BlockRegistryEntity(_, ref err) => err.description(),
+            SceneErr::CameraEntity(_, ref err) => err.description(),
+            SceneErr::SchedulerEntity(_, ref err) => err.description(),
+            SceneErr::BlockEntity(_, ref err) => err.description(),
+            SceneErr::VoxelVolumeEntity(_, ref err) => err.description(),
+            SceneErr::PlayerEntity(_, ref err) => err.description(),
+            SceneErr::MonsterEntity(_, ref err) => err.description(),
+            SceneErr::ItemMapEntity(_, ref err) => err.description(),
+            SceneErr::ItemEntity(_, ref err) => err.description(),
+        }
+    }
+}
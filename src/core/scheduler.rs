@@ -0,0 +1,245 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc};
+
+use dorp::{Id, OptErr, WorldErr};
+
+use core::{RogueWorld, RogueDataErr, SCHEDULER_NAME, PLAYER_NAME};
+use components::{ACTION_COST};
+
+// Turns run per tick at most, so a world without a player can't stall the frame
+static MAX_TURNS_PER_TICK: u64 = 16;
+
+// Game turns are independent of ticks and frames. Each turn every actor gains its speed in
+// energy, then those with enough act, most energy first and ties in the order they joined.
+// When the player's turn comes up without an action the world waits for input, and once the
+// player is dead nobody acts again
+pub struct Scheduler {
+    turn: u64,
+    actors: Vec<Id>,
+    ready: Vec<Id>,
+    acted: Vec<Id>,
+    waiting: bool,
+    game_over: bool,
+}
+
+impl Scheduler {
+    pub fn new() -> Scheduler {
+        Scheduler {
+            turn: 0,
+            actors: vec!(),
+            ready: vec!(),
+            acted: vec!(),
+            waiting: false,
+            game_over: false,
+        }
+    }
+
+    pub fn with_turn(mut self, turn: u64) -> Scheduler {
+        self.turn = turn;
+        self
+    }
+
+    pub fn get_turn(&self) -> u64 {
+        self.turn
+    }
+
+    pub fn get_actors(&self) -> &Vec<Id> {
+        &self.actors
+    }
+
+    // Actors still to act this turn, the next one first
+    pub fn get_ready(&self) -> &Vec<Id> {
+        &self.ready
+    }
+
+    // Picks a turn up part way through, ids that aren't actors are left out
+    pub fn set_ready(&mut self, ready: Vec<Id>) {
+        self.ready = ready.into_iter().filter(|id| self.actors.contains(id)).collect();
+    }
+
+    // Actors in the order they acted during the last tick
+    pub fn get_acted(&self) -> &Vec<Id> {
+        &self.acted
+    }
+
+    pub fn is_waiting(&self) -> bool {
+        self.waiting
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.game_over
+    }
+
+    pub fn register(&mut self, id: Id) {
+        if !self.actors.contains(&id) {
+            self.actors.push(id);
+        }
+    }
+
+    pub fn unregister(&mut self, id: Id) {
+        self.actors.retain(|actor| *actor != id);
+        self.ready.retain(|actor| *actor != id);
+    }
+
+    // Adds id to the named scheduler entity's actors
+    pub fn register_entity(id: Id, world: &mut RogueWorld) -> Result<(), SchedulerErr> {
+        let scheduler_entity = match world.get_mut_entity_by_name(SCHEDULER_NAME) {
+            OptErr::Full(entity) => entity,
+            OptErr::Empty => return Err(SchedulerErr::Get("World Get Mut Entity By Name Scheduler Name")),
+            OptErr::Error(err) => return Err(SchedulerErr::World("World Get Mut Entity By Name Scheduler Name", err)),
+        };
+        match scheduler_entity.get_mut_scheduler() {
+            OptErr::Full(scheduler) => {
+                scheduler.register(id);
+                Ok(())
+            },
+            OptErr::Empty => Err(SchedulerErr::Get("Scheduler Entity Get Mut Scheduler")),
+            OptErr::Error(err) => Err(SchedulerErr::RogueData("Scheduler Entity Get Mut Scheduler", Box::new(err))),
+        }
+    }
+
+    fn start_turn(&mut self, world: &mut RogueWorld) -> Result<(), SchedulerErr> {
+        self.turn += 1;
+        let mut ready = vec!();
+        let mut gone = vec!();
+        for id in self.actors.iter() {
+            let entity = match world.get_mut_entity_by_id(*id) {
+                OptErr::Full(entity) => entity,
+                OptErr::Empty => {
+                    gone.push(*id);
+                    continue;
+                },
+                OptErr::Error(err) => return Err(SchedulerErr::World("World Get Mut Entity By Id", err)),
+            };
+            match entity.get_mut_actor() {
+                OptErr::Full(actor) => {
+                    actor.gain_energy();
+                    if actor.can_act() {
+                        ready.push((*id, actor.get_energy()));
+                    }
+                },
+                OptErr::Empty => gone.push(*id),
+                OptErr::Error(err) => return Err(SchedulerErr::RogueData("Entity Get Mut Actor", Box::new(err))),
+            }
+        }
+        for id in gone {
+            self.unregister(id);
+        }
+        // Stable, so equal energy keeps registration order
+        ready.sort_by(|a, b| b.1.cmp(&a.1));
+        self.ready = ready.into_iter().map(|(id, _)| id).collect();
+        Ok(())
+    }
+
+    // Runs one ready actor, returning false when it is the player and has no action yet
+    fn run_actor(&mut self, id: Id, world: &mut RogueWorld) -> Result<bool, SchedulerErr> {
+        let mut entity = match world.take_entity_by_id(id) {
+            OptErr::Full(entity) => entity,
+            OptErr::Empty => {
+                self.unregister(id);
+                return Ok(true);
+            },
+            OptErr::Error(err) => return Err(SchedulerErr::World("World Take Entity By Id", err)),
+        };
+        let result = match Arc::get_mut(&mut entity) {
+            Some(entity) => match entity.act(&self.actors, world) {
+                Ok(true) => match entity.get_mut_actor() {
+                    OptErr::Full(actor) => {
+                        actor.spend_energy(ACTION_COST);
+                        Ok(Some(actor.can_act()))
+                    },
+                    OptErr::Empty => Err(SchedulerErr::Get("Entity Get Mut Actor")),
+                    OptErr::Error(err) => Err(SchedulerErr::RogueData("Entity Get Mut Actor", Box::new(err))),
+                },
+                Ok(false) => Ok(None),
+                Err(err) => Err(SchedulerErr::RogueData("Entity Act", Box::new(err))),
+            },
+            None => Err(SchedulerErr::GetMut("Arc Get Mut Entity")),
+        };
+        world.add_entity_arc(entity);
+        match result {
+            Ok(Some(again)) => {
+                self.ready.remove(0);
+                self.acted.push(id);
+                // Fast actors go round again after everyone else this turn
+                if again {
+                    self.ready.push(id);
+                }
+                Ok(true)
+            },
+            Ok(None) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn tick_mut(&mut self, world: &mut RogueWorld) -> Result<(), SchedulerErr> {
+        self.acted.clear();
+        self.waiting = false;
+        let mut turns = 0;
+        loop {
+            if self.game_over || is_player_dead(world) {
+                self.game_over = true;
+                self.ready.clear();
+                return Ok(());
+            }
+            if self.ready.is_empty() {
+                if self.actors.is_empty() || turns == MAX_TURNS_PER_TICK {
+                    return Ok(());
+                }
+                match self.start_turn(world) {
+                    Ok(()) => turns += 1,
+                    Err(err) => return Err(err),
+                }
+                continue;
+            }
+            let id = self.ready[0];
+            match self.run_actor(id, world) {
+                Ok(true) => (),
+                Ok(false) => {
+                    self.waiting = true;
+                    return Ok(());
+                },
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+// A killed player is left in the world, so the level stays as they last saw it
+fn is_player_dead(world: &RogueWorld) -> bool {
+    match world.get_entity_by_name(PLAYER_NAME) {
+        Some(entity) => entity.get_combat().map_or(false, |combat| combat.is_dead()),
+        None => false,
+    }
+}
+
+#[derive(Debug)]
+pub enum SchedulerErr {
+    Get(&'static str),
+    GetMut(&'static str),
+    World(&'static str, WorldErr),
+    RogueData(&'static str, Box<RogueDataErr>),
+}
+
+impl fmt::Display for SchedulerErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SchedulerErr::Get(_) => write!(f, "Get was None"),
+            SchedulerErr::GetMut(_) => write!(f, "Get Mut was None"),
+            SchedulerErr::World(_, ref err) => err.fmt(f),
+            SchedulerErr::RogueData(_, ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for SchedulerErr {
+    fn description(&self) -> &str {
+        match *self {
+            SchedulerErr::Get(_) => "Get was None",
+            SchedulerErr::GetMut(_) => "Get Mut was None",
+            SchedulerErr::World(_, ref err) => err.description(),
+            SchedulerErr::RogueData(_, ref err) => err.description(),
+        }
+    }
+}
@@ -5,3 +5,12 @@ use core::{RogueData};
 pub type BlockCoordSize = i64;
 pub type RogueWorld = World<RogueData>;
 pub static BLOCK_MAP_NAME: &'static str = "BlockMap";
+pub static PLAYER_NAME: &'static str = "Player";
+pub static VOXEL_VOLUME_NAME: &'static str = "VoxelVolume";
+pub static BLOCK_REGISTRY_NAME: &'static str = "BlockRegistry";
+pub static CAMERA_NAME: &'static str = "Camera";
+pub static INPUT_NAME: &'static str = "Input";
+pub static SCHEDULER_NAME: &'static str = "Scheduler";
+pub static BEING_SPRITE: &'static str = "sprite.being";
+pub static ITEM_SPRITE: &'static str = "sprite.item";
+pub static ITEM_MAP_NAME: &'static str = "ItemMap";
@@ -1,7 +1,7 @@
 use std::error::Error;
 use std::fmt;
 
-use dorp::{World, WorldErr, IdManager, Id, IdType};
+use dorp::{World, WorldErr, IdManager, Id, IdType, OptErr, EntityData};
 
 use components::{Block, BlockErr, BlockType, BlockCoords};
 use core::{RogueData, RogueDataErr, BLOCK_MAP_NAME};
@@ -10,17 +10,36 @@ use entities::{new_block_map_entity, BlockMapEntityErr};
 pub fn new_block_entity(block_type: BlockType, block_coords: BlockCoords, manager: &mut IdManager, world: &mut World<RogueData>) -> Result<Id, BlockEntityErr> {
     let id = Id::new(manager, IdType::Entity);
 
-    let block_map_entity = match world.get_entity_by_name(BLOCK_MAP_NAME) {
-        Some(entity) => entity,
+    let block_map_id = match world.get_entity_by_name(BLOCK_MAP_NAME) {
+        Some(entity) => entity.get_id(),
         None => match new_block_map_entity(manager, world) {
-            Ok(id) => match world.get_entity_by_id(id) {
-                Some(block_map) => block_map,
-                None => return Err(BlockEntityErr::Get("World Get Entity by id")),
-            },
+            Ok(id) => id,
             Err(err) => return Err(BlockEntityErr::BlockMapEntityErr("new block map entity", err)),
         },
     };
 
+    let block = match world.get_mut_entity_by_id(block_map_id) {
+        OptErr::Full(block_map_entity) => match block_map_entity.get_mut_block_map() {
+            OptErr::Full(block_map) => Block::new_with_block_map(block_type, id, &block_coords, block_map, block_map_id),
+            OptErr::Empty => return Err(BlockEntityErr::Get("Block Map Entity Get Mut Block Map")),
+            OptErr::Error(err) => return Err(BlockEntityErr::RogueData("Block Map Entity Get Mut Block Map", err)),
+        },
+        OptErr::Empty => return Err(BlockEntityErr::Get("World Get Mut Entity By Id Block Map Id")),
+        OptErr::Error(err) => return Err(BlockEntityErr::World("World Get Mut Entity By Id Block Map Id", err)),
+    };
+
+    match world.add_entity(RogueData::new(id)
+        .with_block_coords(
+            block_coords
+        )
+        .with_block(
+            block
+        )
+    ) {
+        Ok(()) => (),
+        Err(err) => return Err(BlockEntityErr::World("World Add Entity", err)),
+    }
+
     Ok(id)
 }
 
@@ -0,0 +1,54 @@
+use std::error::Error;
+use std::fmt;
+
+use dorp::{World, WorldErr, IdManager, Id, IdType, Named, NamedErr};
+
+use components::{BlockRegistry};
+use core::{RogueData, BLOCK_REGISTRY_NAME};
+
+pub fn new_block_registry_entity(block_registry: BlockRegistry, manager: &mut IdManager, world: &mut World<RogueData>) -> Result<Id, BlockRegistryEntityErr> {
+    let id = Id::new(manager, IdType::Entity);
+
+    let named = match Named::new(BLOCK_REGISTRY_NAME, id, world) {
+        Ok(named) => named,
+        Err(err) => return Err(BlockRegistryEntityErr::Named("Named New", err)),
+    };
+
+    match world.add_entity(RogueData::new(id)
+        .with_block_registry(
+            block_registry
+        )
+        .with_named(
+            named
+        )
+    ) {
+        Ok(()) => (),
+        Err(err) => return Err(BlockRegistryEntityErr::World("World Add Entity", err)),
+    }
+
+    Ok(id)
+}
+
+#[derive(Debug)]
+pub enum BlockRegistryEntityErr {
+    Named(&'static str, NamedErr),
+    World(&'static str, WorldErr),
+}
+
+impl fmt::Display for BlockRegistryEntityErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BlockRegistryEntityErr::Named(_, ref err) => err.fmt(f),
+            BlockRegistryEntityErr::World(_, ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for BlockRegistryEntityErr {
+    fn description(&self) -> &str {
+        match *self {
+            BlockRegistryEntityErr::Named(_, ref err) => err.description(),
+            BlockRegistryEntityErr::World(_, ref err) => err.description(),
+        }
+    }
+}
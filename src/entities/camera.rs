@@ -0,0 +1,54 @@
+use std::error::Error;
+use std::fmt;
+
+use dorp::{World, WorldErr, IdManager, Id, IdType, Named, NamedErr};
+
+use components::{Camera};
+use core::{RogueData, CAMERA_NAME};
+
+pub fn new_camera_entity(camera: Camera, manager: &mut IdManager, world: &mut World<RogueData>) -> Result<Id, CameraEntityErr> {
+    let id = Id::new(manager, IdType::Entity);
+
+    let named = match Named::new(CAMERA_NAME, id, world) {
+        Ok(named) => named,
+        Err(err) => return Err(CameraEntityErr::Named("Named New", err)),
+    };
+
+    match world.add_entity(RogueData::new(id)
+        .with_camera(
+            camera
+        )
+        .with_named(
+            named
+        )
+    ) {
+        Ok(()) => (),
+        Err(err) => return Err(CameraEntityErr::World("World Add Entity", err)),
+    }
+
+    Ok(id)
+}
+
+#[derive(Debug)]
+pub enum CameraEntityErr {
+    Named(&'static str, NamedErr),
+    World(&'static str, WorldErr),
+}
+
+impl fmt::Display for CameraEntityErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CameraEntityErr::Named(_, ref err) => err.fmt(f),
+            CameraEntityErr::World(_, ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for CameraEntityErr {
+    fn description(&self) -> &str {
+        match *self {
+            CameraEntityErr::Named(_, ref err) => err.description(),
+            CameraEntityErr::World(_, ref err) => err.description(),
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use std::error::Error;
+use std::fmt;
+
+use dorp::{World, WorldErr, IdManager, Id, IdType, Named, NamedErr};
+
+use input::{InputState};
+use core::{RogueData, INPUT_NAME};
+
+pub fn new_input_entity(input_state: InputState, manager: &mut IdManager, world: &mut World<RogueData>) -> Result<Id, InputEntityErr> {
+    let id = Id::new(manager, IdType::Entity);
+
+    let named = match Named::new(INPUT_NAME, id, world) {
+        Ok(named) => named,
+        Err(err) => return Err(InputEntityErr::Named("Named New", err)),
+    };
+
+    match world.add_entity(RogueData::new(id)
+        .with_input_state(
+            input_state
+        )
+        .with_named(
+            named
+        )
+    ) {
+        Ok(()) => (),
+        Err(err) => return Err(InputEntityErr::World("World Add Entity", err)),
+    }
+
+    Ok(id)
+}
+
+#[derive(Debug)]
+pub enum InputEntityErr {
+    Named(&'static str, NamedErr),
+    World(&'static str, WorldErr),
+}
+
+impl fmt::Display for InputEntityErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InputEntityErr::Named(_, ref err) => err.fmt(f),
+            InputEntityErr::World(_, ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for InputEntityErr {
+    fn description(&self) -> &str {
+        match *self {
+            InputEntityErr::Named(_, ref err) => err.description(),
+            InputEntityErr::World(_, ref err) => err.description(),
+        }
+    }
+}
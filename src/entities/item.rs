@@ -0,0 +1,79 @@
+use std::error::Error;
+use std::fmt;
+
+use dorp::{World, WorldErr, IdManager, Id, IdType, OptErr};
+
+use components::{BlockCoords, Sprite, Item, ItemDef};
+use core::{RogueData, RogueDataErr, ITEM_MAP_NAME};
+use entities::{new_item_map_entity, ItemMapEntityErr};
+
+// An item lying on block_coords
+pub fn new_item_entity(item_def: &ItemDef, block_coords: BlockCoords, sprite: Option<Sprite>, manager: &mut IdManager, world: &mut World<RogueData>) -> Result<Id, ItemEntityErr> {
+    let id = Id::new(manager, IdType::Entity);
+
+    match world.get_entity_by_name(ITEM_MAP_NAME) {
+        Some(_) => (),
+        None => match new_item_map_entity(manager, world) {
+            Ok(_) => (),
+            Err(err) => return Err(ItemEntityErr::ItemMapEntity("New Item Map Entity", err)),
+        },
+    }
+
+    let mut item = RogueData::new(id)
+        .with_block_coords(
+            block_coords
+        )
+        .with_item(
+            Item::new(item_def.clone())
+        );
+    if let Some(sprite) = sprite {
+        item = item.with_sprite(sprite);
+    }
+
+    match world.add_entity(item) {
+        Ok(()) => (),
+        Err(err) => return Err(ItemEntityErr::World("World Add Entity", err)),
+    }
+
+    match world.get_mut_entity_by_name(ITEM_MAP_NAME) {
+        OptErr::Full(entity) => match entity.get_mut_item_map() {
+            OptErr::Full(item_map) => item_map.insert(&block_coords, id),
+            OptErr::Empty => return Err(ItemEntityErr::Get("Item Map Entity Get Mut Item Map")),
+            OptErr::Error(err) => return Err(ItemEntityErr::RogueData("Item Map Entity Get Mut Item Map", err)),
+        },
+        OptErr::Empty => return Err(ItemEntityErr::Get("World Get Mut Entity By Name Item Map Name")),
+        OptErr::Error(err) => return Err(ItemEntityErr::World("World Get Mut Entity By Name Item Map Name", err)),
+    }
+
+    Ok(id)
+}
+
+#[derive(Debug)]
+pub enum ItemEntityErr {
+    ItemMapEntity(&'static str, ItemMapEntityErr),
+    RogueData(&'static str, RogueDataErr),
+    World(&'static str, WorldErr),
+    Get(&'static str),
+}
+
+impl fmt::Display for ItemEntityErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ItemEntityErr::ItemMapEntity(_, ref err) => err.fmt(f),
+            ItemEntityErr::RogueData(_, ref err) => err.fmt(f),
+            ItemEntityErr::World(_, ref err) => err.fmt(f),
+            ItemEntityErr::Get(_) => write!(f, "Get was None"),
+        }
+    }
+}
+
+impl Error for ItemEntityErr {
+    fn description(&self) -> &str {
+        match *self {
+            ItemEntityErr::ItemMapEntity(_, ref err) => err.description(),
+            ItemEntityErr::RogueData(_, ref err) => err.description(),
+            ItemEntityErr::World(_, ref err) => err.description(),
+            ItemEntityErr::Get(_) => "Get was None",
+        }
+    }
+}
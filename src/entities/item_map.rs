@@ -0,0 +1,64 @@
+use std::error::Error;
+use std::fmt;
+
+use dorp::{World, WorldErr, IdManager, Id, IdType, Named, NamedErr};
+
+use core::{RogueData, RogueDataErr, ITEM_MAP_NAME};
+use components::{ItemMap};
+
+pub fn new_item_map_entity(manager: &mut IdManager, world: &mut World<RogueData>) -> Result<Id, ItemMapEntityErr> {
+    let id = Id::new(manager, IdType::Entity);
+
+    let item_map = ItemMap::new();
+    let named = match Named::new(ITEM_MAP_NAME, id, world) {
+        Ok(named) => named,
+        Err(err) => return Err(ItemMapEntityErr::Named("Named New", err)),
+    };
+
+    match world.add_entity(RogueData::new(id)
+        .with_item_map(
+            item_map
+        )
+        .with_named(
+            named
+        )
+    ) {
+        Ok(()) => (),
+        Err(err) => return Err(ItemMapEntityErr::World("World Add Entity", err)),
+    };
+
+    Ok(id)
+}
+
+#[derive(Debug)]
+pub enum ItemMapEntityErr {
+    Named(&'static str, NamedErr),
+    World(&'static str, WorldErr),
+    RogueData(&'static str, RogueDataErr),
+    Get(&'static str),
+    GetMut(&'static str),
+}
+
+impl fmt::Display for ItemMapEntityErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ItemMapEntityErr::Named(_, ref err) => err.fmt(f),
+            ItemMapEntityErr::World(_, ref err) => err.fmt(f),
+            ItemMapEntityErr::RogueData(_, ref err) => err.fmt(f),
+            ItemMapEntityErr::Get(_) => write!(f, "Get was None"),
+            ItemMapEntityErr::GetMut(_) => write!(f, "Get Mut was None"),
+        }
+    }
+}
+
+impl Error for ItemMapEntityErr {
+    fn description(&self) -> &str {
+        match *self {
+            ItemMapEntityErr::Named(_, ref err) => err.description(),
+            ItemMapEntityErr::World(_, ref err) => err.description(),
+            ItemMapEntityErr::RogueData(_, ref err) => err.description(),
+            ItemMapEntityErr::Get(_) => "Get was None",
+            ItemMapEntityErr::GetMut(_) => "Get Mut was None",
+        }
+    }
+}
@@ -1,5 +1,23 @@
 mod block;
 mod block_map;
+mod block_registry;
+mod camera;
+mod input;
+mod item;
+mod item_map;
+mod monster;
+mod player;
+mod scheduler;
+mod voxel_volume;
 
 pub use self::block::{new_block_entity, BlockEntityErr};
 pub use self::block_map::{new_block_map_entity, BlockMapEntityErr};
+pub use self::block_registry::{new_block_registry_entity, BlockRegistryEntityErr};
+pub use self::camera::{new_camera_entity, CameraEntityErr};
+pub use self::input::{new_input_entity, InputEntityErr};
+pub use self::item::{new_item_entity, ItemEntityErr};
+pub use self::item_map::{new_item_map_entity, ItemMapEntityErr};
+pub use self::monster::{new_monster_entity, MonsterEntityErr};
+pub use self::player::{new_player_entity, PlayerEntityErr};
+pub use self::scheduler::{new_scheduler_entity, SchedulerEntityErr};
+pub use self::voxel_volume::{new_voxel_volume_entity, VoxelVolumeEntityErr};
@@ -0,0 +1,80 @@
+use std::error::Error;
+use std::fmt;
+
+use dorp::{World, WorldErr, IdManager, Id, IdType};
+
+use components::{BlockCoords, Sprite, Actor, FieldOfView, MonsterDef};
+use core::{RogueData, Ai, Scheduler, SchedulerErr, SCHEDULER_NAME};
+use entities::{new_scheduler_entity, SchedulerEntityErr};
+
+// Monsters call their spawn point home, seed drives their wandering
+pub fn new_monster_entity(monster_def: &MonsterDef, block_coords: BlockCoords, sprite: Option<Sprite>, seed: u64, manager: &mut IdManager, world: &mut World<RogueData>) -> Result<Id, MonsterEntityErr> {
+    let id = Id::new(manager, IdType::Entity);
+
+    match world.get_entity_by_name(SCHEDULER_NAME) {
+        Some(_) => (),
+        None => match new_scheduler_entity(Scheduler::new(), manager, world) {
+            Ok(_) => (),
+            Err(err) => return Err(MonsterEntityErr::SchedulerEntity("New Scheduler Entity", err)),
+        },
+    }
+
+    let mut monster = RogueData::new(id)
+        .with_block_coords(
+            block_coords
+        )
+        .with_ai(
+            Ai::new(monster_def.clone(), block_coords, seed)
+        )
+        .with_actor(
+            Actor::new(monster_def.get_speed())
+        )
+        .with_combat(
+            monster_def.new_combat(seed.wrapping_add(1))
+        )
+        .with_field_of_view(
+            FieldOfView::new(monster_def.get_sight())
+        );
+    if let Some(sprite) = sprite {
+        monster = monster.with_sprite(sprite);
+    }
+
+    match world.add_entity(monster) {
+        Ok(()) => (),
+        Err(err) => return Err(MonsterEntityErr::World("World Add Entity", err)),
+    }
+
+    match Scheduler::register_entity(id, world) {
+        Ok(()) => (),
+        Err(err) => return Err(MonsterEntityErr::Scheduler("Scheduler Register Entity", err)),
+    }
+
+    Ok(id)
+}
+
+#[derive(Debug)]
+pub enum MonsterEntityErr {
+    SchedulerEntity(&'static str, SchedulerEntityErr),
+    Scheduler(&'static str, SchedulerErr),
+    World(&'static str, WorldErr),
+}
+
+impl fmt::Display for MonsterEntityErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MonsterEntityErr::SchedulerEntity(_, ref err) => err.fmt(f),
+            MonsterEntityErr::Scheduler(_, ref err) => err.fmt(f),
+            MonsterEntityErr::World(_, ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for MonsterEntityErr {
+    fn description(&self) -> &str {
+        match *self {
+            MonsterEntityErr::SchedulerEntity(_, ref err) => err.description(),
+            MonsterEntityErr::Scheduler(_, ref err) => err.description(),
+            MonsterEntityErr::World(_, ref err) => err.description(),
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::error::Error;
+use std::fmt;
+
+use dorp::{World, WorldErr, IdManager, Id, IdType, Named, NamedErr};
+
+use components::{BlockCoords, Sprite, Actor, Combat, FieldOfView, Inventory, NORMAL_SPEED, SIGHT_RADIUS};
+use core::{RogueData, Player, Scheduler, SchedulerErr, BlockCoordSize, BLOCK_MAP_NAME, PLAYER_NAME, SCHEDULER_NAME};
+use entities::{new_block_map_entity, BlockMapEntityErr, new_scheduler_entity, SchedulerEntityErr};
+
+static PLAYER_HEALTH: i32 = 30;
+static PLAYER_RANGE: BlockCoordSize = 8;
+static PLAYER_SLOTS: usize = 12;
+static PLAYER_MAX_WEIGHT: f32 = 30.0;
+// The player's rolls, the same every run until saves keep the rng going
+static PLAYER_COMBAT_SEED: u64 = 0x706C61796572;
+
+pub fn new_player_entity(block_coords: BlockCoords, sprite: Option<Sprite>, manager: &mut IdManager, world: &mut World<RogueData>) -> Result<Id, PlayerEntityErr> {
+    let id = Id::new(manager, IdType::Entity);
+
+    match world.get_entity_by_name(BLOCK_MAP_NAME) {
+        Some(_) => (),
+        None => match new_block_map_entity(manager, world) {
+            Ok(_) => (),
+            Err(err) => return Err(PlayerEntityErr::BlockMapEntity("New Block Map Entity", err)),
+        },
+    }
+
+    match world.get_entity_by_name(SCHEDULER_NAME) {
+        Some(_) => (),
+        None => match new_scheduler_entity(Scheduler::new(), manager, world) {
+            Ok(_) => (),
+            Err(err) => return Err(PlayerEntityErr::SchedulerEntity("New Scheduler Entity", err)),
+        },
+    }
+
+    let named = match Named::new(PLAYER_NAME, id, world) {
+        Ok(named) => named,
+        Err(err) => return Err(PlayerEntityErr::Named("Named New", err)),
+    };
+
+    let mut player = RogueData::new(id)
+        .with_block_coords(
+            block_coords
+        )
+        .with_player(
+            Player::new()
+        )
+        .with_actor(
+            Actor::new(NORMAL_SPEED)
+        )
+        .with_combat(
+            Combat::new(PLAYER_HEALTH, PLAYER_COMBAT_SEED)
+                .with_damage(2, 5)
+                .with_accuracy(12)
+                .with_defense(2)
+                .with_range(PLAYER_RANGE)
+        )
+        .with_field_of_view(
+            FieldOfView::new(SIGHT_RADIUS)
+        )
+        .with_inventory(
+            Inventory::new(PLAYER_SLOTS, PLAYER_MAX_WEIGHT)
+        )
+        .with_named(
+            named
+        );
+    if let Some(sprite) = sprite {
+        player = player.with_sprite(sprite);
+    }
+
+    match world.add_entity(player) {
+        Ok(()) => (),
+        Err(err) => return Err(PlayerEntityErr::World("World Add Entity", err)),
+    }
+
+    match Scheduler::register_entity(id, world) {
+        Ok(()) => (),
+        Err(err) => return Err(PlayerEntityErr::Scheduler("Scheduler Register Entity", err)),
+    }
+
+    Ok(id)
+}
+
+#[derive(Debug)]
+pub enum PlayerEntityErr {
+    BlockMapEntity(&'static str, BlockMapEntityErr),
+    SchedulerEntity(&'static str, SchedulerEntityErr),
+    Scheduler(&'static str, SchedulerErr),
+    Named(&'static str, NamedErr),
+    World(&'static str, WorldErr),
+}
+
+impl fmt::Display for PlayerEntityErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PlayerEntityErr::BlockMapEntity(_, ref err) => err.fmt(f),
+            PlayerEntityErr::SchedulerEntity(_, ref err) => err.fmt(f),
+            PlayerEntityErr::Scheduler(_, ref err) => err.fmt(f),
+            PlayerEntityErr::Named(_, ref err) => err.fmt(f),
+            PlayerEntityErr::World(_, ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for PlayerEntityErr {
+    fn description(&self) -> &str {
+        match *self {
+            PlayerEntityErr::BlockMapEntity(_, ref err) => err.description(),
+            PlayerEntityErr::SchedulerEntity(_, ref err) => err.description(),
+            PlayerEntityErr::Scheduler(_, ref err) => err.description(),
+            PlayerEntityErr::Named(_, ref err) => err.description(),
+            PlayerEntityErr::World(_, ref err) => err.description(),
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use std::error::Error;
+use std::fmt;
+
+use dorp::{World, WorldErr, IdManager, Id, IdType, Named, NamedErr};
+
+use core::{RogueData, Scheduler, SCHEDULER_NAME};
+
+pub fn new_scheduler_entity(scheduler: Scheduler, manager: &mut IdManager, world: &mut World<RogueData>) -> Result<Id, SchedulerEntityErr> {
+    let id = Id::new(manager, IdType::Entity);
+
+    let named = match Named::new(SCHEDULER_NAME, id, world) {
+        Ok(named) => named,
+        Err(err) => return Err(SchedulerEntityErr::Named("Named New", err)),
+    };
+
+    match world.add_entity(RogueData::new(id)
+        .with_scheduler(
+            scheduler
+        )
+        .with_named(
+            named
+        )
+    ) {
+        Ok(()) => (),
+        Err(err) => return Err(SchedulerEntityErr::World("World Add Entity", err)),
+    }
+
+    Ok(id)
+}
+
+#[derive(Debug)]
+pub enum SchedulerEntityErr {
+    Named(&'static str, NamedErr),
+    World(&'static str, WorldErr),
+}
+
+impl fmt::Display for SchedulerEntityErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SchedulerEntityErr::Named(_, ref err) => err.fmt(f),
+            SchedulerEntityErr::World(_, ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for SchedulerEntityErr {
+    fn description(&self) -> &str {
+        match *self {
+            SchedulerEntityErr::Named(_, ref err) => err.description(),
+            SchedulerEntityErr::World(_, ref err) => err.description(),
+        }
+    }
+}
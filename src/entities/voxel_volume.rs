@@ -0,0 +1,54 @@
+use std::error::Error;
+use std::fmt;
+
+use dorp::{World, WorldErr, IdManager, Id, IdType, Named, NamedErr};
+
+use components::{VoxelVolume};
+use core::{RogueData, VOXEL_VOLUME_NAME};
+
+pub fn new_voxel_volume_entity(voxel_volume: VoxelVolume, manager: &mut IdManager, world: &mut World<RogueData>) -> Result<Id, VoxelVolumeEntityErr> {
+    let id = Id::new(manager, IdType::Entity);
+
+    let named = match Named::new(VOXEL_VOLUME_NAME, id, world) {
+        Ok(named) => named,
+        Err(err) => return Err(VoxelVolumeEntityErr::Named("Named New", err)),
+    };
+
+    match world.add_entity(RogueData::new(id)
+        .with_voxel_volume(
+            voxel_volume
+        )
+        .with_named(
+            named
+        )
+    ) {
+        Ok(()) => (),
+        Err(err) => return Err(VoxelVolumeEntityErr::World("World Add Entity", err)),
+    }
+
+    Ok(id)
+}
+
+#[derive(Debug)]
+pub enum VoxelVolumeEntityErr {
+    Named(&'static str, NamedErr),
+    World(&'static str, WorldErr),
+}
+
+impl fmt::Display for VoxelVolumeEntityErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            VoxelVolumeEntityErr::Named(_, ref err) => err.fmt(f),
+            VoxelVolumeEntityErr::World(_, ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for VoxelVolumeEntityErr {
+    fn description(&self) -> &str {
+        match *self {
+            VoxelVolumeEntityErr::Named(_, ref err) => err.description(),
+            VoxelVolumeEntityErr::World(_, ref err) => err.description(),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use std::collections::{HashSet};
+
+use core::{BlockCoordSize};
+use components::{BlockCoords, BlockRegistry, VoxelVolume};
+
+// Cells a 3D Bresenham line passes through from one cell to another, both ends included
+pub fn trace_line(from: &BlockCoords, to: &BlockCoords) -> Vec<BlockCoords> {
+    let start = [from.get_x(), from.get_y(), from.get_z()];
+    let delta = [to.get_x() - start[0], to.get_y() - start[1], to.get_z() - start[2]];
+    let steps = delta[0].abs().max(delta[1].abs()).max(delta[2].abs());
+    let mut cells = vec!(*from);
+    let mut cell = start;
+    let mut error = [0; 3];
+    for _ in 0..steps {
+        for axis in 0..3 {
+            error[axis] += delta[axis].abs() * 2;
+            if error[axis] >= steps {
+                cell[axis] += delta[axis].signum();
+                error[axis] -= steps * 2;
+            }
+        }
+        cells.push(BlockCoords::new(cell[0], cell[1], cell[2]));
+    }
+    cells
+}
+
+// Whether nothing opaque lies strictly between the two cells
+pub fn has_line_of_sight<F>(from: &BlockCoords, to: &BlockCoords, is_opaque: &F) -> bool
+    where F: Fn(&BlockCoords) -> bool
+{
+    let cells = trace_line(from, to);
+    cells.iter().skip(1).take(cells.len().saturating_sub(2)).all(|cell| !is_opaque(cell))
+}
+
+// 3D field of view. Open cells are seen along a clear line from the origin, and opaque cells
+// are seen when they touch a seen open cell, which lights floors, ceilings and walls alike
+pub fn line_of_sight<F>(origin: &BlockCoords, radius: BlockCoordSize, is_opaque: &F) -> HashSet<BlockCoords>
+    where F: Fn(&BlockCoords) -> bool
+{
+    let mut open = HashSet::new();
+    for dz in -radius..radius + 1 {
+        for dy in -radius..radius + 1 {
+            for dx in -radius..radius + 1 {
+                if dx * dx + dy * dy + dz * dz > radius * radius {
+                    continue;
+                }
+                let cell = origin.offset(dx, dy, dz);
+                if !is_opaque(&cell) && has_line_of_sight(origin, &cell, is_opaque) {
+                    open.insert(cell);
+                }
+            }
+        }
+    }
+    let mut visible = open.clone();
+    visible.insert(*origin);
+    for cell in open.iter() {
+        for &(dx, dy, dz) in [(1, 0, 0), (-1, 0, 0), (0, 1, 0), (0, -1, 0), (0, 0, 1), (0, 0, -1)].iter() {
+            let beside = cell.offset(dx, dy, dz);
+            if is_opaque(&beside) {
+                visible.insert(beside);
+            }
+        }
+    }
+    visible
+}
+
+pub fn line_of_sight_volume(voxel_volume: &VoxelVolume, block_registry: &BlockRegistry, origin: &BlockCoords, radius: BlockCoordSize) -> HashSet<BlockCoords> {
+    let is_opaque = |block_coords: &BlockCoords| block_registry.is_opaque(voxel_volume.get(block_coords));
+    line_of_sight(origin, radius, &is_opaque)
+}
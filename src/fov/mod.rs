@@ -0,0 +1,5 @@
+pub mod shadowcast;
+pub mod line_of_sight;
+
+pub use self::shadowcast::{shadowcast, shadowcast_layer};
+pub use self::line_of_sight::{trace_line, has_line_of_sight, line_of_sight, line_of_sight_volume};
@@ -0,0 +1,75 @@
+use std::collections::{HashSet};
+
+use core::{BlockCoordSize};
+use components::{BlockCoords, BlockRegistry, VoxelVolume};
+
+// Transforms from octant local (column, row) into world offsets, one column per octant
+static XX: [BlockCoordSize; 8] = [1, 0, 0, -1, -1, 0, 0, 1];
+static XY: [BlockCoordSize; 8] = [0, 1, -1, 0, 0, -1, 1, 0];
+static YX: [BlockCoordSize; 8] = [0, 1, 1, 0, 0, -1, -1, 0];
+static YY: [BlockCoordSize; 8] = [1, 0, 0, 1, -1, 0, 0, -1];
+
+// Recursive shadowcasting over a grid. Opaque cells are seen but stop the light behind them,
+// and the result includes the origin
+pub fn shadowcast<F>(origin: (BlockCoordSize, BlockCoordSize), radius: BlockCoordSize, is_opaque: &F) -> HashSet<(BlockCoordSize, BlockCoordSize)>
+    where F: Fn(BlockCoordSize, BlockCoordSize) -> bool
+{
+    let mut visible = HashSet::new();
+    visible.insert(origin);
+    for octant in 0..8 {
+        cast_light(origin, 1, 1.0, 0.0, radius, octant, is_opaque, &mut visible);
+    }
+    visible
+}
+
+fn cast_light<F>(origin: (BlockCoordSize, BlockCoordSize), row: BlockCoordSize, start: f32, end: f32, radius: BlockCoordSize, octant: usize, is_opaque: &F, visible: &mut HashSet<(BlockCoordSize, BlockCoordSize)>)
+    where F: Fn(BlockCoordSize, BlockCoordSize) -> bool
+{
+    if start < end {
+        return;
+    }
+    let mut start = start;
+    let mut next_start = 0.0;
+    for distance in row..radius + 1 {
+        let dy = -distance;
+        let mut blocked = false;
+        for dx in -distance..1 {
+            let x = origin.0 + dx * XX[octant] + dy * XY[octant];
+            let y = origin.1 + dx * YX[octant] + dy * YY[octant];
+            let left_slope = (dx as f32 - 0.5) / (dy as f32 + 0.5);
+            let right_slope = (dx as f32 + 0.5) / (dy as f32 - 0.5);
+            if start < right_slope {
+                continue;
+            } else if end > left_slope {
+                break;
+            }
+            if dx * dx + dy * dy <= radius * radius {
+                visible.insert((x, y));
+            }
+            if blocked {
+                if is_opaque(x, y) {
+                    next_start = right_slope;
+                } else {
+                    blocked = false;
+                    start = next_start;
+                }
+            } else if is_opaque(x, y) && distance < radius {
+                blocked = true;
+                cast_light(origin, distance + 1, start, left_slope, radius, octant, is_opaque, visible);
+                next_start = right_slope;
+            }
+        }
+        if blocked {
+            break;
+        }
+    }
+}
+
+// Shadowcasts the layer the origin stands in
+pub fn shadowcast_layer(voxel_volume: &VoxelVolume, block_registry: &BlockRegistry, origin: &BlockCoords, radius: BlockCoordSize) -> HashSet<BlockCoords> {
+    let y = origin.get_y();
+    let is_opaque = |x, z| block_registry.is_opaque(voxel_volume.get(&BlockCoords::new(x, y, z)));
+    shadowcast((origin.get_x(), origin.get_z()), radius, &is_opaque).into_iter()
+        .map(|(x, z)| BlockCoords::new(x, y, z))
+        .collect()
+}
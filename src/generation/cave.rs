@@ -0,0 +1,154 @@
+use core::{Rng};
+use generation::{Layout, Tile};
+
+#[derive(Clone, Debug)]
+pub struct CaveParams {
+    width: usize,
+    depth: usize,
+    fill_ratio: f64,
+    iterations: usize,
+    birth_limit: usize,
+    survival_limit: usize,
+}
+
+impl CaveParams {
+    pub fn new(width: usize, depth: usize) -> CaveParams {
+        CaveParams {
+            width: width,
+            depth: depth,
+            fill_ratio: 0.45,
+            iterations: 5,
+            birth_limit: 5,
+            survival_limit: 4,
+        }
+    }
+
+    // Chance that a tile starts out as wall before smoothing
+    pub fn with_fill_ratio(mut self, fill_ratio: f64) -> CaveParams {
+        self.fill_ratio = fill_ratio;
+        self
+    }
+
+    pub fn with_iterations(mut self, iterations: usize) -> CaveParams {
+        self.iterations = iterations;
+        self
+    }
+
+    // Floor becomes wall with at least birth_limit of its 8 neighbors walls, wall stays wall with at least survival_limit
+    pub fn with_limits(mut self, birth_limit: usize, survival_limit: usize) -> CaveParams {
+        self.birth_limit = birth_limit;
+        self.survival_limit = survival_limit;
+        self
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn get_fill_ratio(&self) -> f64 {
+        self.fill_ratio
+    }
+
+    pub fn get_iterations(&self) -> usize {
+        self.iterations
+    }
+}
+
+// Random fill smoothed by a cellular automaton, then every pocket not connected to the
+// largest open region is filled back in. The entrance is the first open tile in scan order
+// and the exit the open tile furthest from it
+pub fn generate_cave(params: &CaveParams, seed: u64) -> Layout {
+    let mut rng = Rng::new(seed);
+    let width = params.width;
+    let depth = params.depth;
+    let mut layout = Layout::new(width, depth);
+    if width < 3 || depth < 3 {
+        return layout;
+    }
+
+    let mut walls = vec!(true; width * depth);
+    for z in 1..depth - 1 {
+        for x in 1..width - 1 {
+            walls[z * width + x] = rng.chance(params.fill_ratio);
+        }
+    }
+
+    for _ in 0..params.iterations {
+        let mut next = walls.clone();
+        for z in 1..depth - 1 {
+            for x in 1..width - 1 {
+                let mut wall_neighbors = 0;
+                for dz in 0..3 {
+                    for dx in 0..3 {
+                        if (dx != 1 || dz != 1) && walls[(z + dz - 1) * width + x + dx - 1] {
+                            wall_neighbors += 1;
+                        }
+                    }
+                }
+                let index = z * width + x;
+                next[index] = if walls[index] {
+                    wall_neighbors >= params.survival_limit
+                } else {
+                    wall_neighbors >= params.birth_limit
+                };
+            }
+        }
+        walls = next;
+    }
+
+    for (index, wall) in walls.iter().enumerate() {
+        if !wall {
+            layout.set(index % width, index / width, Tile::Floor);
+        }
+    }
+
+    let mut best: Option<((usize, usize), usize)> = None;
+    let mut claimed = vec!(false; width * depth);
+    for index in 0..walls.len() {
+        if walls[index] || claimed[index] {
+            continue;
+        }
+        let start = (index % width, index / width);
+        let distances = layout.get_distances(start);
+        let mut size = 0;
+        for (other, distance) in distances.iter().enumerate() {
+            if distance.is_some() {
+                claimed[other] = true;
+                size += 1;
+            }
+        }
+        let larger = match best {
+            Some((_, best_size)) => size > best_size,
+            None => true,
+        };
+        if larger {
+            best = Some((start, size));
+        }
+    }
+    let entrance = match best {
+        Some((entrance, _)) => entrance,
+        None => return layout,
+    };
+
+    let distances = layout.get_distances(entrance);
+    let mut exit = entrance;
+    let mut furthest = 0;
+    for (index, distance) in distances.iter().enumerate() {
+        match *distance {
+            Some(distance) => {
+                if distance > furthest {
+                    furthest = distance;
+                    exit = (index % width, index / width);
+                }
+            },
+            None => layout.set(index % width, index / width, Tile::Wall),
+        }
+    }
+    layout.set_entrance(entrance);
+    layout.set_exit(exit);
+    layout
+}
@@ -0,0 +1,194 @@
+use core::{Rng};
+use generation::{Layout, Room, Tile};
+
+#[derive(Clone, Debug)]
+pub struct DungeonParams {
+    width: usize,
+    depth: usize,
+    min_leaf: usize,
+    min_room: usize,
+    max_depth: usize,
+}
+
+impl DungeonParams {
+    pub fn new(width: usize, depth: usize) -> DungeonParams {
+        DungeonParams {
+            width: width,
+            depth: depth,
+            min_leaf: 8,
+            min_room: 4,
+            max_depth: 5,
+        }
+    }
+
+    // Smallest BSP leaf along either axis; keep it above min_room so rooms leave a wall between leaves
+    pub fn with_min_leaf(mut self, min_leaf: usize) -> DungeonParams {
+        self.min_leaf = min_leaf;
+        self
+    }
+
+    pub fn with_min_room(mut self, min_room: usize) -> DungeonParams {
+        self.min_room = min_room;
+        self
+    }
+
+    pub fn with_max_depth(mut self, max_depth: usize) -> DungeonParams {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_depth(&self) -> usize {
+        self.depth
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Leaf {
+    x: usize,
+    z: usize,
+    width: usize,
+    depth: usize,
+}
+
+// Rooms and corridors from a binary space partition. Every split connects one room on each
+// side, so the result is one connected tree; the entrance is the first room and the exit is
+// the room centre furthest from it by walking distance
+pub fn generate_dungeon(params: &DungeonParams, seed: u64) -> Layout {
+    let mut rng = Rng::new(seed);
+    let mut layout = Layout::new(params.width, params.depth);
+    if params.width < 3 || params.depth < 3 {
+        return layout;
+    }
+    let root = Leaf {
+        x: 1,
+        z: 1,
+        width: params.width - 2,
+        depth: params.depth - 2,
+    };
+    split(&mut rng, &mut layout, params, root, 0);
+    place_doors(&mut layout);
+
+    let rooms = layout.get_rooms().clone();
+    let entrance = match rooms.first() {
+        Some(room) => room.get_center(),
+        None => return layout,
+    };
+    for room in rooms.iter() {
+        if !layout.is_connected(entrance, room.get_center()) {
+            layout.carve_corridor(entrance, room.get_center(), true);
+        }
+    }
+    let distances = layout.get_distances(entrance);
+    let mut exit = entrance;
+    let mut furthest = 0;
+    for room in rooms.iter() {
+        let (x, z) = room.get_center();
+        if let Some(distance) = distances[z * layout.get_width() + x] {
+            if distance > furthest {
+                furthest = distance;
+                exit = (x, z);
+            }
+        }
+    }
+    layout.set_entrance(entrance);
+    layout.set_exit(exit);
+    layout
+}
+
+fn split(rng: &mut Rng, layout: &mut Layout, params: &DungeonParams, leaf: Leaf, depth: usize) -> Vec<usize> {
+    let can_split_x = leaf.width >= params.min_leaf * 2;
+    let can_split_z = leaf.depth >= params.min_leaf * 2;
+    if depth >= params.max_depth || (!can_split_x && !can_split_z) {
+        return vec!(carve_room(rng, layout, params, leaf));
+    }
+    let split_x = if can_split_x && can_split_z {
+        if leaf.width * 4 > leaf.depth * 5 {
+            true
+        } else if leaf.depth * 4 > leaf.width * 5 {
+            false
+        } else {
+            rng.chance(0.5)
+        }
+    } else {
+        can_split_x
+    };
+    let (first, second) = if split_x {
+        let at = rng.range_usize(params.min_leaf, leaf.width - params.min_leaf + 1);
+        (Leaf { width: at, ..leaf }, Leaf { x: leaf.x + at, width: leaf.width - at, ..leaf })
+    } else {
+        let at = rng.range_usize(params.min_leaf, leaf.depth - params.min_leaf + 1);
+        (Leaf { depth: at, ..leaf }, Leaf { z: leaf.z + at, depth: leaf.depth - at, ..leaf })
+    };
+    let mut rooms = split(rng, layout, params, first, depth + 1);
+    let second_rooms = split(rng, layout, params, second, depth + 1);
+
+    let from = layout.get_rooms()[rooms[rng.range_usize(0, rooms.len())]].get_center();
+    let to = layout.get_rooms()[second_rooms[rng.range_usize(0, second_rooms.len())]].get_center();
+    let horizontal_first = rng.chance(0.5);
+    layout.carve_corridor(from, to, horizontal_first);
+
+    rooms.extend(second_rooms);
+    rooms
+}
+
+// Rooms keep one tile free on the far side of their leaf so neighboring rooms never touch
+fn carve_room(rng: &mut Rng, layout: &mut Layout, params: &DungeonParams, leaf: Leaf) -> usize {
+    let max_width = if leaf.width > 1 { leaf.width - 1 } else { 1 };
+    let max_depth = if leaf.depth > 1 { leaf.depth - 1 } else { 1 };
+    let width = rng.range_usize(params.min_room.min(max_width), max_width + 1);
+    let depth = rng.range_usize(params.min_room.min(max_depth), max_depth + 1);
+    let x = leaf.x + rng.range_usize(0, max_width - width + 1);
+    let z = leaf.z + rng.range_usize(0, max_depth - depth + 1);
+    layout.add_room(Room::new(x, z, width, depth));
+    layout.get_rooms().len() - 1
+}
+
+// A corridor tile right outside a room wall, with wall on both sides of it, is a doorway
+fn place_doors(layout: &mut Layout) {
+    let rooms = layout.get_rooms().clone();
+    for room in rooms.iter() {
+        let left = room.get_x() as i64 - 1;
+        let right = (room.get_x() + room.get_width()) as i64;
+        let top = room.get_z() as i64 - 1;
+        let bottom = (room.get_z() + room.get_depth()) as i64;
+        for z in room.get_z()..room.get_z() + room.get_depth() {
+            for &x in [left, right].iter() {
+                if x >= 0 {
+                    try_door(layout, x as usize, z, false);
+                }
+            }
+        }
+        for x in room.get_x()..room.get_x() + room.get_width() {
+            for &z in [top, bottom].iter() {
+                if z >= 0 {
+                    try_door(layout, x, z as usize, true);
+                }
+            }
+        }
+    }
+}
+
+fn try_door(layout: &mut Layout, x: usize, z: usize, walls_along_x: bool) {
+    if layout.get(x, z) != Tile::Floor {
+        return;
+    }
+    // Only one door where a corridor crosses a wall two tiles thick
+    let (walled, doubled) = if walls_along_x {
+        (
+            x > 0 && layout.get(x - 1, z) == Tile::Wall && layout.get(x + 1, z) == Tile::Wall,
+            (z > 0 && layout.get(x, z - 1) == Tile::Door) || layout.get(x, z + 1) == Tile::Door
+        )
+    } else {
+        (
+            z > 0 && layout.get(x, z - 1) == Tile::Wall && layout.get(x, z + 1) == Tile::Wall,
+            (x > 0 && layout.get(x - 1, z) == Tile::Door) || layout.get(x + 1, z) == Tile::Door
+        )
+    };
+    if walled && !doubled {
+        layout.set(x, z, Tile::Door);
+    }
+}
@@ -0,0 +1,262 @@
+use std::collections::{VecDeque};
+use std::fmt;
+
+use core::{BlockCoordSize};
+use components::{BlockCoords, BlockType, VoxelVolume};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Tile {
+    Wall,
+    Floor,
+    Door,
+}
+
+impl Tile {
+    pub fn is_walkable(&self) -> bool {
+        match *self {
+            Tile::Wall => false,
+            Tile::Floor | Tile::Door => true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Room {
+    x: usize,
+    z: usize,
+    width: usize,
+    depth: usize,
+}
+
+impl Room {
+    pub fn new(x: usize, z: usize, width: usize, depth: usize) -> Room {
+        Room {
+            x: x,
+            z: z,
+            width: width,
+            depth: depth,
+        }
+    }
+
+    pub fn get_x(&self) -> usize {
+        self.x
+    }
+
+    pub fn get_z(&self) -> usize {
+        self.z
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn get_center(&self) -> (usize, usize) {
+        (self.x + self.width / 2, self.z + self.depth / 2)
+    }
+
+    pub fn contains(&self, x: usize, z: usize) -> bool {
+        x >= self.x && x < self.x + self.width && z >= self.z && z < self.z + self.depth
+    }
+}
+
+// A single storey level plan on the x/z plane, one tile per block column
+pub struct Layout {
+    width: usize,
+    depth: usize,
+    tiles: Vec<Tile>,
+    rooms: Vec<Room>,
+    entrance: (usize, usize),
+    exit: (usize, usize),
+}
+
+impl Layout {
+    pub fn new(width: usize, depth: usize) -> Layout {
+        Layout {
+            width: width,
+            depth: depth,
+            tiles: vec!(Tile::Wall; width * depth),
+            rooms: vec!(),
+            entrance: (0, 0),
+            exit: (0, 0),
+        }
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn get(&self, x: usize, z: usize) -> Tile {
+        if x >= self.width || z >= self.depth {
+            return Tile::Wall;
+        }
+        self.tiles[z * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, z: usize, tile: Tile) {
+        if x < self.width && z < self.depth {
+            self.tiles[z * self.width + x] = tile;
+        }
+    }
+
+    pub fn get_tiles(&self) -> &Vec<Tile> {
+        &self.tiles
+    }
+
+    pub fn count(&self, tile: Tile) -> usize {
+        self.tiles.iter().filter(|other| **other == tile).count()
+    }
+
+    pub fn get_rooms(&self) -> &Vec<Room> {
+        &self.rooms
+    }
+
+    pub fn add_room(&mut self, room: Room) {
+        for z in room.get_z()..room.get_z() + room.get_depth() {
+            for x in room.get_x()..room.get_x() + room.get_width() {
+                self.set(x, z, Tile::Floor);
+            }
+        }
+        self.rooms.push(room);
+    }
+
+    pub fn get_entrance(&self) -> (usize, usize) {
+        self.entrance
+    }
+
+    pub fn set_entrance(&mut self, entrance: (usize, usize)) {
+        self.entrance = entrance;
+    }
+
+    pub fn get_exit(&self) -> (usize, usize) {
+        self.exit
+    }
+
+    pub fn set_exit(&mut self, exit: (usize, usize)) {
+        self.exit = exit;
+    }
+
+    pub fn get_walkable_neighbors(&self, x: usize, z: usize) -> Vec<(usize, usize)> {
+        let mut neighbors = vec!();
+        if x > 0 && self.get(x - 1, z).is_walkable() {
+            neighbors.push((x - 1, z));
+        }
+        if self.get(x + 1, z).is_walkable() {
+            neighbors.push((x + 1, z));
+        }
+        if z > 0 && self.get(x, z - 1).is_walkable() {
+            neighbors.push((x, z - 1));
+        }
+        if self.get(x, z + 1).is_walkable() {
+            neighbors.push((x, z + 1));
+        }
+        neighbors
+    }
+
+    // Steps from start to every walkable tile, None where unreachable
+    pub fn get_distances(&self, start: (usize, usize)) -> Vec<Option<usize>> {
+        let mut distances = vec!(None; self.tiles.len());
+        if !self.get(start.0, start.1).is_walkable() {
+            return distances;
+        }
+        let mut queue = VecDeque::new();
+        distances[start.1 * self.width + start.0] = Some(0);
+        queue.push_back(start);
+        while let Some((x, z)) = queue.pop_front() {
+            let distance = distances[z * self.width + x].unwrap_or(0);
+            for (nx, nz) in self.get_walkable_neighbors(x, z) {
+                let index = nz * self.width + nx;
+                if distances[index].is_none() {
+                    distances[index] = Some(distance + 1);
+                    queue.push_back((nx, nz));
+                }
+            }
+        }
+        distances
+    }
+
+    pub fn is_connected(&self, from: (usize, usize), to: (usize, usize)) -> bool {
+        if to.0 >= self.width || to.1 >= self.depth {
+            return false;
+        }
+        self.get_distances(from)[to.1 * self.width + to.0].is_some()
+    }
+
+    // Carves an L shaped one tile wide corridor, horizontal leg first when horizontal_first
+    pub fn carve_corridor(&mut self, from: (usize, usize), to: (usize, usize), horizontal_first: bool) {
+        let corner = if horizontal_first { (to.0, from.1) } else { (from.0, to.1) };
+        self.carve_line(from, corner);
+        self.carve_line(corner, to);
+    }
+
+    fn carve_line(&mut self, from: (usize, usize), to: (usize, usize)) {
+        let (mut x, mut z) = from;
+        loop {
+            if self.get(x, z) == Tile::Wall {
+                self.set(x, z, Tile::Floor);
+            }
+            if (x, z) == to {
+                break;
+            }
+            if x < to.0 {
+                x += 1;
+            } else if x > to.0 {
+                x -= 1;
+            } else if z < to.1 {
+                z += 1;
+            } else {
+                z -= 1;
+            }
+        }
+    }
+
+    // Writes the plan with its floor at origin: a solid floor layer, wall_height of wall
+    // blocks where the plan has walls, doors at floor level and air everywhere else
+    pub fn write_to_volume(&self, voxel_volume: &mut VoxelVolume, origin: &BlockCoords, wall_height: usize, solid: BlockType) {
+        for z in 0..self.depth {
+            for x in 0..self.width {
+                let column = origin.offset(x as BlockCoordSize, 0, z as BlockCoordSize);
+                voxel_volume.set(&column, solid);
+                for y in 1..wall_height as BlockCoordSize + 1 {
+                    let block_type = match (self.get(x, z), y) {
+                        (Tile::Wall, _) => solid,
+                        (Tile::Door, 1) => BlockType::DOOR,
+                        _ => BlockType::AIR,
+                    };
+                    voxel_volume.set(&column.offset(0, y, 0), block_type);
+                }
+            }
+        }
+    }
+}
+
+// '#' wall, '.' floor, '+' door, '<' entrance and '>' exit, one row per z
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for z in 0..self.depth {
+            for x in 0..self.width {
+                let symbol = if (x, z) == self.entrance {
+                    '<'
+                } else if (x, z) == self.exit {
+                    '>'
+                } else {
+                    match self.get(x, z) {
+                        Tile::Wall => '#',
+                        Tile::Floor => '.',
+                        Tile::Door => '+',
+                    }
+                };
+                try!(write!(f, "{}", symbol));
+            }
+            try!(writeln!(f, ""));
+        }
+        Ok(())
+    }
+}
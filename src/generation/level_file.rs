@@ -0,0 +1,302 @@
+use std::collections::{HashMap};
+use std::fs::{File};
+use std::io::{self, Read};
+use std::error::Error;
+use std::fmt;
+
+use core::{BlockCoordSize};
+use components::{BlockCoords, BlockType, BlockRegistry, VoxelVolume, MonsterRegistry, MonsterDef, ItemRegistry, ItemDef};
+
+// A hand drawn level. '[legend]' maps characters to what fills their cell:
+//   'c' = block <name>     a block from the block registry
+//   'c' = spawn            where the player starts
+//   'c' = monster <name>   a monster from the monster registry
+//   'c' = item <name>      an item from the item registry
+// The player, monsters and items stand in air. Each '[layer Y]' is the horizontal slice at
+// height Y seen from above, columns running along x and rows along z from 0. Rows are taken
+// exactly as written, so inside a layer '#' is just another character and not a comment, but
+// a row can't start with '['. Every row and layer has to be the same size, and heights left
+// out between layers are air
+pub struct LevelFile {
+    voxel_volume: VoxelVolume,
+    spawn: BlockCoords,
+    monsters: Vec<(BlockCoords, MonsterDef)>,
+    items: Vec<(BlockCoords, ItemDef)>,
+}
+
+enum LegendEntry {
+    Block(BlockType),
+    Spawn,
+    Monster(MonsterDef),
+    Item(ItemDef),
+}
+
+struct Layer {
+    y: BlockCoordSize,
+    line: usize,
+    // Line number and characters of each row
+    rows: Vec<(usize, Vec<char>)>,
+}
+
+enum Section {
+    Legend,
+    Layer,
+}
+
+impl LevelFile {
+    pub fn load(path: &str, block_registry: &BlockRegistry, monster_registry: &MonsterRegistry, item_registry: &ItemRegistry) -> Result<LevelFile, LevelFileErr> {
+        let mut text = String::new();
+        match File::open(path) {
+            Ok(mut file) => match file.read_to_string(&mut text) {
+                Ok(_) => (),
+                Err(err) => return Err(LevelFileErr::Io(path.to_string(), err)),
+            },
+            Err(err) => return Err(LevelFileErr::Io(path.to_string(), err)),
+        }
+        LevelFile::parse(&text, block_registry, monster_registry, item_registry)
+    }
+
+    pub fn parse(text: &str, block_registry: &BlockRegistry, monster_registry: &MonsterRegistry, item_registry: &ItemRegistry) -> Result<LevelFile, LevelFileErr> {
+        let mut legend: Option<HashMap<char, LegendEntry>> = None;
+        let mut layers: Vec<Layer> = vec!();
+        let mut section = None;
+        for (index, raw) in text.lines().enumerate() {
+            let line = index + 1;
+            let content = raw.trim();
+            let indent = raw.chars().take_while(|c| c.is_whitespace()).count();
+            if content.starts_with('[') {
+                if !content.ends_with(']') {
+                    return Err(LevelFileErr::Syntax(line, indent + 1, "Section header must look like [legend] or [layer Y]"));
+                }
+                let name = content[1..content.len() - 1].trim();
+                if name == "legend" {
+                    if legend.is_some() {
+                        return Err(LevelFileErr::Duplicate(line, indent + 1, "[legend]".to_string()));
+                    }
+                    legend = Some(HashMap::new());
+                    section = Some(Section::Legend);
+                    continue;
+                }
+                let mut words = name.split_whitespace();
+                let y = match (words.next(), words.next().and_then(|y| y.parse().ok()), words.next()) {
+                    (Some("layer"), Some(y), None) => y,
+                    _ => return Err(LevelFileErr::Syntax(line, indent + 1, "Section header must look like [legend] or [layer Y]")),
+                };
+                if layers.iter().any(|layer| layer.y == y) {
+                    return Err(LevelFileErr::Duplicate(line, indent + 1, format!("[layer {}]", y)));
+                }
+                layers.push(Layer {
+                    y: y,
+                    line: line,
+                    rows: vec!(),
+                });
+                section = Some(Section::Layer);
+                continue;
+            }
+            match section {
+                Some(Section::Layer) => {
+                    if !content.is_empty() {
+                        if let Some(layer) = layers.last_mut() {
+                            layer.rows.push((line, raw.chars().collect()));
+                        }
+                    }
+                },
+                Some(Section::Legend) => {
+                    if content.is_empty() || content.starts_with('#') {
+                        continue;
+                    }
+                    let (key, entry) = match parse_legend_entry(raw, line, block_registry, monster_registry, item_registry) {
+                        Ok(entry) => entry,
+                        Err(err) => return Err(err),
+                    };
+                    if let Some(ref mut legend) = legend {
+                        if legend.contains_key(&key) {
+                            return Err(LevelFileErr::Duplicate(line, indent + 1, format!("'{}'", key)));
+                        }
+                        legend.insert(key, entry);
+                    }
+                },
+                None => {
+                    if !content.is_empty() && !content.starts_with('#') {
+                        return Err(LevelFileErr::Syntax(line, indent + 1, "Expected [legend] or [layer Y] before this"));
+                    }
+                },
+            }
+        }
+
+        let legend = match legend {
+            Some(legend) => legend,
+            None => return Err(LevelFileErr::Missing("[legend] section")),
+        };
+        let (width, depth) = match layers.first() {
+            Some(layer) => match layer.rows.first() {
+                Some(&(_, ref row)) => (row.len(), layer.rows.len()),
+                None => return Err(LevelFileErr::Syntax(layer.line, 1, "Layer has no rows")),
+            },
+            None => return Err(LevelFileErr::Missing("[layer Y] section")),
+        };
+        for layer in layers.iter() {
+            if layer.rows.len() != depth {
+                return Err(LevelFileErr::Depth(layer.line, layer.rows.len(), depth));
+            }
+            for &(line, ref row) in layer.rows.iter() {
+                if row.len() != width {
+                    return Err(LevelFileErr::Width(line, row.len().min(width) + 1, row.len(), width));
+                }
+            }
+        }
+
+        let min_y = layers.iter().map(|layer| layer.y).min().unwrap_or(0);
+        let max_y = layers.iter().map(|layer| layer.y).max().unwrap_or(0);
+        let mut voxel_volume = VoxelVolume::new(BlockCoords::new(0, min_y, 0), width, (max_y - min_y + 1) as usize, depth);
+        let mut spawn: Option<(BlockCoords, usize, usize)> = None;
+        let mut monsters = vec!();
+        let mut items = vec!();
+        for layer in layers.iter() {
+            for (z, &(line, ref row)) in layer.rows.iter().enumerate() {
+                for (x, key) in row.iter().enumerate() {
+                    let block_coords = BlockCoords::new(x as BlockCoordSize, layer.y, z as BlockCoordSize);
+                    match legend.get(key) {
+                        Some(&LegendEntry::Block(block_type)) => {
+                            voxel_volume.set(&block_coords, block_type);
+                        },
+                        Some(&LegendEntry::Spawn) => match spawn {
+                            Some((_, first_line, first_column)) => return Err(LevelFileErr::Spawn(line, x + 1, first_line, first_column)),
+                            None => spawn = Some((block_coords, line, x + 1)),
+                        },
+                        Some(&LegendEntry::Monster(ref monster_def)) => monsters.push((block_coords, monster_def.clone())),
+                        Some(&LegendEntry::Item(ref item_def)) => items.push((block_coords, item_def.clone())),
+                        None => return Err(LevelFileErr::NotInLegend(line, x + 1, *key)),
+                    }
+                }
+            }
+        }
+
+        match spawn {
+            Some((spawn, _, _)) => Ok(LevelFile {
+                voxel_volume: voxel_volume,
+                spawn: spawn,
+                monsters: monsters,
+                items: items,
+            }),
+            None => Err(LevelFileErr::Missing("spawn")),
+        }
+    }
+
+    pub fn get_voxel_volume(&self) -> &VoxelVolume {
+        &self.voxel_volume
+    }
+
+    pub fn get_spawn(&self) -> BlockCoords {
+        self.spawn
+    }
+
+    // In the order they are drawn, layer by layer, then row by row
+    pub fn get_monsters(&self) -> &Vec<(BlockCoords, MonsterDef)> {
+        &self.monsters
+    }
+
+    pub fn get_items(&self) -> &Vec<(BlockCoords, ItemDef)> {
+        &self.items
+    }
+}
+
+// 'c' = kind [name], returning the character and what it stands for
+fn parse_legend_entry(raw: &str, line: usize, block_registry: &BlockRegistry, monster_registry: &MonsterRegistry, item_registry: &ItemRegistry) -> Result<(char, LegendEntry), LevelFileErr> {
+    let chars: Vec<(usize, char)> = raw.char_indices().collect();
+    let start = chars.iter().position(|&(_, c)| !c.is_whitespace()).unwrap_or(0);
+    let key = match (chars.get(start), chars.get(start + 1), chars.get(start + 2)) {
+        (Some(&(_, '\'')), Some(&(_, key)), Some(&(_, '\''))) => key,
+        _ => return Err(LevelFileErr::Syntax(line, start + 1, "Legend entries must look like 'c' = what")),
+    };
+    // Columns count characters, so later positions are found from the char list
+    let column_of = |offset: usize| chars.iter().position(|&(byte, _)| byte >= offset).unwrap_or(chars.len()) + 1;
+    let rest_offset = match chars.get(start + 3) {
+        Some(&(byte, _)) => byte,
+        None => raw.len(),
+    };
+    let rest = &raw[rest_offset..];
+    let rest = match rest.find('#') {
+        Some(comment) => &rest[..comment],
+        None => rest,
+    };
+    let equals = match rest.find('=') {
+        Some(equals) if rest[..equals].trim().is_empty() => equals,
+        _ => return Err(LevelFileErr::Syntax(line, column_of(rest_offset), "Expected = after the character")),
+    };
+    let value_offset = rest_offset + equals + 1;
+    let value = &raw[value_offset..rest_offset + rest.len()];
+    let mut words = value.split_whitespace();
+    let kind = words.next();
+    let name = words.next();
+    let name_column = match name {
+        Some(name) => column_of(value_offset + value.find(name).unwrap_or(0)),
+        None => column_of(value_offset),
+    };
+    if words.next().is_some() {
+        return Err(LevelFileErr::Syntax(line, column_of(value_offset), "Expected block, spawn, monster or item and at most one name"));
+    }
+    let entry = match (kind, name) {
+        (Some("spawn"), None) => LegendEntry::Spawn,
+        (Some("block"), Some(name)) => match block_registry.get_by_name(name) {
+            Some(block_type) => LegendEntry::Block(block_type),
+            None => return Err(LevelFileErr::Unknown(line, name_column, "block", name.to_string())),
+        },
+        (Some("monster"), Some(name)) => match monster_registry.get(name) {
+            Some(monster_def) => LegendEntry::Monster(monster_def.clone()),
+            None => return Err(LevelFileErr::Unknown(line, name_column, "monster", name.to_string())),
+        },
+        (Some("item"), Some(name)) => match item_registry.get(name) {
+            Some(item_def) => LegendEntry::Item(item_def.clone()),
+            None => return Err(LevelFileErr::Unknown(line, name_column, "item", name.to_string())),
+        },
+        _ => return Err(LevelFileErr::Syntax(line, column_of(value_offset), "Expected block <name>, spawn, monster <name> or item <name>")),
+    };
+    Ok((key, entry))
+}
+
+// Lines and columns count from 1
+#[derive(Debug)]
+pub enum LevelFileErr {
+    Io(String, io::Error),
+    Syntax(usize, usize, &'static str),
+    Duplicate(usize, usize, String),
+    Unknown(usize, usize, &'static str, String),
+    NotInLegend(usize, usize, char),
+    Width(usize, usize, usize, usize),
+    Depth(usize, usize, usize),
+    Spawn(usize, usize, usize, usize),
+    Missing(&'static str),
+}
+
+impl fmt::Display for LevelFileErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LevelFileErr::Io(ref path, ref err) => write!(f, "{}: {}", path, err),
+            LevelFileErr::Syntax(line, column, message) => write!(f, "line {}, column {}: {}", line, column, message),
+            LevelFileErr::Duplicate(line, column, ref name) => write!(f, "line {}, column {}: {} is defined twice", line, column, name),
+            LevelFileErr::Unknown(line, column, kind, ref name) => write!(f, "line {}, column {}: unknown {} '{}'", line, column, kind, name),
+            LevelFileErr::NotInLegend(line, column, key) => write!(f, "line {}, column {}: '{}' is not in the legend", line, column, key),
+            LevelFileErr::Width(line, column, width, expected) => write!(f, "line {}, column {}: row is {} wide, expected {}", line, column, width, expected),
+            LevelFileErr::Depth(line, depth, expected) => write!(f, "line {}, column 1: layer has {} rows, expected {}", line, depth, expected),
+            LevelFileErr::Spawn(line, column, first_line, first_column) => write!(f, "line {}, column {}: second spawn, the first is at line {}, column {}", line, column, first_line, first_column),
+            LevelFileErr::Missing(what) => write!(f, "Level has no {}", what),
+        }
+    }
+}
+
+impl Error for LevelFileErr {
+    fn description(&self) -> &str {
+        match *self {
+            LevelFileErr::Io(_, ref err) => err.description(),
+            LevelFileErr::Syntax(_, _, message) => message,
+            LevelFileErr::Duplicate(_, _, _) => "Defined twice",
+            LevelFileErr::Unknown(_, _, _, _) => "Unknown name",
+            LevelFileErr::NotInLegend(_, _, _) => "Character not in the legend",
+            LevelFileErr::Width(_, _, _, _) => "Row has the wrong width",
+            LevelFileErr::Depth(_, _, _) => "Layer has the wrong number of rows",
+            LevelFileErr::Spawn(_, _, _, _) => "More than one spawn",
+            LevelFileErr::Missing(_) => "Missing part of the level",
+        }
+    }
+}
@@ -0,0 +1,148 @@
+pub mod layout;
+pub mod dungeon;
+pub mod cave;
+pub mod noise;
+pub mod terrain;
+pub mod level_file;
+
+pub use self::layout::{Layout, Room, Tile};
+pub use self::dungeon::{DungeonParams, generate_dungeon};
+pub use self::cave::{CaveParams, generate_cave};
+pub use self::noise::{Perlin};
+pub use self::terrain::{TerrainParams, TerrainGenerator};
+pub use self::level_file::{LevelFile, LevelFileErr};
+
+use core::{BlockCoordSize};
+use components::{BlockCoords, BlockType, VoxelVolume, ChunkCoords, CHUNK_SIZE, CHUNK_VOLUME};
+use save::{checksum};
+
+pub static WALL_HEIGHT: usize = 2;
+
+#[derive(Clone, Debug)]
+pub enum LevelKind {
+    Dungeon(DungeonParams),
+    Cave(CaveParams),
+    Terrain(TerrainParams),
+}
+
+impl LevelKind {
+    pub fn get_width(&self) -> usize {
+        match *self {
+            LevelKind::Dungeon(ref params) => params.get_width(),
+            LevelKind::Cave(ref params) => params.get_width(),
+            LevelKind::Terrain(ref params) => params.get_width(),
+        }
+    }
+
+    pub fn get_height(&self) -> usize {
+        match *self {
+            LevelKind::Dungeon(_) | LevelKind::Cave(_) => WALL_HEIGHT + 1,
+            LevelKind::Terrain(ref params) => params.get_height(),
+        }
+    }
+
+    pub fn get_depth(&self) -> usize {
+        match *self {
+            LevelKind::Dungeon(ref params) => params.get_depth(),
+            LevelKind::Cave(ref params) => params.get_depth(),
+            LevelKind::Terrain(ref params) => params.get_depth(),
+        }
+    }
+
+    // The single storey plan for dungeons and caves; terrain has none
+    pub fn generate(&self, seed: u64) -> Option<Layout> {
+        match *self {
+            LevelKind::Dungeon(ref params) => Some(generate_dungeon(params, seed)),
+            LevelKind::Cave(ref params) => Some(generate_cave(params, seed)),
+            LevelKind::Terrain(_) => None,
+        }
+    }
+
+    // Lowest corner of the level, which is centered on x = z = 0
+    pub fn get_origin(&self) -> BlockCoords {
+        let y = match *self {
+            LevelKind::Terrain(_) => 0,
+            _ => -1,
+        };
+        BlockCoords::new(-(self.get_width() as BlockCoordSize) / 2, y, -(self.get_depth() as BlockCoordSize) / 2)
+    }
+
+    // Builds the level and returns it with the cell the player starts in
+    pub fn build_volume(&self, seed: u64) -> (VoxelVolume, BlockCoords) {
+        let origin = self.get_origin();
+        let mut voxel_volume = VoxelVolume::new(origin, self.get_width(), self.get_height(), self.get_depth());
+        match *self {
+            LevelKind::Terrain(ref params) => {
+                let terrain = TerrainGenerator::new(params.clone(), seed);
+                terrain.fill_volume(&mut voxel_volume);
+                let spawn = find_terrain_spawn(&terrain, &voxel_volume);
+                (voxel_volume, spawn)
+            },
+            _ => {
+                let layout = self.generate_layout(seed);
+                layout.write_to_volume(&mut voxel_volume, &origin, WALL_HEIGHT, BlockType::DIRT);
+                (voxel_volume, find_layout_spawn(&layout, &origin))
+            },
+        }
+    }
+
+    // The cell the player starts in, without building the blocks around it
+    pub fn find_spawn(&self, seed: u64) -> BlockCoords {
+        match *self {
+            LevelKind::Terrain(ref params) => {
+                let voxel_volume = VoxelVolume::new(self.get_origin(), 1, self.get_height(), 1);
+                find_terrain_spawn(&TerrainGenerator::new(params.clone(), seed), &voxel_volume)
+            },
+            _ => find_layout_spawn(&self.generate_layout(seed), &self.get_origin()),
+        }
+    }
+
+    // The blocks build_volume would put in one chunk. Terrain makes just that chunk, the
+    // storey plans are only cheap to make whole so those build the volume and cut it out
+    pub fn generate_chunk(&self, seed: u64, chunk_coords: &ChunkCoords) -> Vec<BlockType> {
+        match *self {
+            LevelKind::Terrain(ref params) => {
+                // Only the part of the chunk inside the level is kept, like fill_volume would
+                let origin = self.get_origin();
+                let level_max = origin.offset(self.get_width() as BlockCoordSize, self.get_height() as BlockCoordSize, self.get_depth() as BlockCoordSize);
+                let chunk_min = chunk_coords.get_origin();
+                let chunk_max = chunk_min.offset(CHUNK_SIZE, CHUNK_SIZE, CHUNK_SIZE);
+                let min = BlockCoords::new(origin.get_x().max(chunk_min.get_x()), origin.get_y().max(chunk_min.get_y()), origin.get_z().max(chunk_min.get_z()));
+                let max = BlockCoords::new(level_max.get_x().min(chunk_max.get_x()), level_max.get_y().min(chunk_max.get_y()), level_max.get_z().min(chunk_max.get_z()));
+                if max.get_x() <= min.get_x() || max.get_y() <= min.get_y() || max.get_z() <= min.get_z() {
+                    return vec!(BlockType::AIR; CHUNK_VOLUME);
+                }
+                let mut overlap = VoxelVolume::new(min, (max.get_x() - min.get_x()) as usize, (max.get_y() - min.get_y()) as usize, (max.get_z() - min.get_z()) as usize);
+                overlap.set_chunk(chunk_coords, &TerrainGenerator::new(params.clone(), seed).generate_chunk(chunk_coords));
+                overlap.get_chunk(chunk_coords)
+            },
+            _ => self.build_volume(seed).0.get_chunk(chunk_coords),
+        }
+    }
+
+    // Changes with the kind of level and any of its generator settings, the Debug text holds both
+    pub fn get_fingerprint(&self) -> u32 {
+        checksum(format!("{:?}", self).as_bytes())
+    }
+
+    fn generate_layout(&self, seed: u64) -> Layout {
+        match self.generate(seed) {
+            Some(layout) => layout,
+            None => Layout::new(self.get_width(), self.get_depth()),
+        }
+    }
+}
+
+// Spawn only looks at the x = z = 0 column, so the volume just needs the right y range
+fn find_terrain_spawn(terrain: &TerrainGenerator, voxel_volume: &VoxelVolume) -> BlockCoords {
+    let top = voxel_volume.get_max().get_y();
+    match terrain.find_surface(0, 0, voxel_volume.get_origin().get_y(), top - 1) {
+        Some(y) => BlockCoords::new(0, y + 1, 0),
+        None => BlockCoords::new(0, top, 0),
+    }
+}
+
+fn find_layout_spawn(layout: &Layout, origin: &BlockCoords) -> BlockCoords {
+    let (entrance_x, entrance_z) = layout.get_entrance();
+    origin.offset(entrance_x as BlockCoordSize, 1, entrance_z as BlockCoordSize)
+}
@@ -0,0 +1,87 @@
+use core::{Rng};
+
+// Improved Perlin noise over a seeded permutation table
+pub struct Perlin {
+    permutation: Vec<usize>,
+}
+
+impl Perlin {
+    pub fn new(seed: u64) -> Perlin {
+        let mut rng = Rng::new(seed);
+        let mut table: Vec<usize> = (0..256).collect();
+        for i in (1..256).rev() {
+            let j = rng.range_usize(0, i + 1);
+            table.swap(i, j);
+        }
+        let mut permutation = table.clone();
+        permutation.extend(table);
+        Perlin {
+            permutation: permutation,
+        }
+    }
+
+    // Roughly in -1..1, zero at every integer lattice point
+    pub fn noise3(&self, x: f64, y: f64, z: f64) -> f64 {
+        let xi = (x.floor() as i64 & 255) as usize;
+        let yi = (y.floor() as i64 & 255) as usize;
+        let zi = (z.floor() as i64 & 255) as usize;
+        let x = x - x.floor();
+        let y = y - y.floor();
+        let z = z - z.floor();
+        let u = fade(x);
+        let v = fade(y);
+        let w = fade(z);
+        let p = &self.permutation;
+        let a = p[xi] + yi;
+        let aa = p[a] + zi;
+        let ab = p[a + 1] + zi;
+        let b = p[xi + 1] + yi;
+        let ba = p[b] + zi;
+        let bb = p[b + 1] + zi;
+        lerp(w,
+            lerp(v,
+                lerp(u, grad(p[aa], x, y, z), grad(p[ba], x - 1.0, y, z)),
+                lerp(u, grad(p[ab], x, y - 1.0, z), grad(p[bb], x - 1.0, y - 1.0, z))),
+            lerp(v,
+                lerp(u, grad(p[aa + 1], x, y, z - 1.0), grad(p[ba + 1], x - 1.0, y, z - 1.0)),
+                lerp(u, grad(p[ab + 1], x, y - 1.0, z - 1.0), grad(p[bb + 1], x - 1.0, y - 1.0, z - 1.0))))
+    }
+
+    pub fn noise2(&self, x: f64, z: f64) -> f64 {
+        self.noise3(x, 0.5, z)
+    }
+
+    // Fractal sum of octaves, each at twice the frequency and half the amplitude, normalized to about -1..1
+    pub fn fbm3(&self, x: f64, y: f64, z: f64, octaves: usize) -> f64 {
+        let mut total = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = 1.0;
+        let mut max = 0.0;
+        for _ in 0..octaves {
+            total += self.noise3(x * frequency, y * frequency, z * frequency) * amplitude;
+            max += amplitude;
+            amplitude *= 0.5;
+            frequency *= 2.0;
+        }
+        if max > 0.0 { total / max } else { 0.0 }
+    }
+
+    pub fn fbm2(&self, x: f64, z: f64, octaves: usize) -> f64 {
+        self.fbm3(x, 0.5, z, octaves)
+    }
+}
+
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
+
+fn grad(hash: usize, x: f64, y: f64, z: f64) -> f64 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 { y } else if h == 12 || h == 14 { x } else { z };
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
@@ -0,0 +1,203 @@
+use core::{BlockCoordSize};
+use components::{BlockCoords, BlockType, ChunkCoords, VoxelVolume, CHUNK_SIZE, CHUNK_VOLUME};
+use generation::{Perlin};
+
+#[derive(Clone, Debug)]
+pub struct TerrainParams {
+    width: usize,
+    height: usize,
+    depth: usize,
+    base_height: f64,
+    height_amplitude: f64,
+    height_frequency: f64,
+    octaves: usize,
+    overhang_strength: f64,
+    overhang_frequency: f64,
+    cave_threshold: f64,
+    cave_frequency: f64,
+    dirt_depth: BlockCoordSize,
+}
+
+impl TerrainParams {
+    pub fn new(width: usize, height: usize, depth: usize) -> TerrainParams {
+        TerrainParams {
+            width: width,
+            height: height,
+            depth: depth,
+            base_height: height as f64 / 3.0,
+            height_amplitude: height as f64 / 4.0,
+            height_frequency: 1.0 / 48.0,
+            octaves: 4,
+            overhang_strength: 4.0,
+            overhang_frequency: 1.0 / 16.0,
+            cave_threshold: 0.35,
+            cave_frequency: 1.0 / 12.0,
+            dirt_depth: 3,
+        }
+    }
+
+    // Surface heights swing base_height +- amplitude around y = 0
+    pub fn with_height(mut self, base_height: f64, height_amplitude: f64) -> TerrainParams {
+        self.base_height = base_height;
+        self.height_amplitude = height_amplitude;
+        self
+    }
+
+    pub fn with_height_frequency(mut self, height_frequency: f64, octaves: usize) -> TerrainParams {
+        self.height_frequency = height_frequency;
+        self.octaves = octaves;
+        self
+    }
+
+    // How many blocks 3D noise may push the surface in or out, giving overhangs and arches
+    pub fn with_overhangs(mut self, overhang_strength: f64, overhang_frequency: f64) -> TerrainParams {
+        self.overhang_strength = overhang_strength;
+        self.overhang_frequency = overhang_frequency;
+        self
+    }
+
+    // Caves are carved where 3D noise rises over the threshold; raise it above 1 for no caves
+    pub fn with_caves(mut self, cave_threshold: f64, cave_frequency: f64) -> TerrainParams {
+        self.cave_threshold = cave_threshold;
+        self.cave_frequency = cave_frequency;
+        self
+    }
+
+    pub fn with_dirt_depth(mut self, dirt_depth: BlockCoordSize) -> TerrainParams {
+        self.dirt_depth = dirt_depth;
+        self
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get_depth(&self) -> usize {
+        self.depth
+    }
+}
+
+// Every block is a pure function of the seed and its coords, so any chunk or region can be
+// generated on its own and match what a larger fill would have produced
+pub struct TerrainGenerator {
+    params: TerrainParams,
+    height_noise: Perlin,
+    overhang_noise: Perlin,
+    cave_noise: Perlin,
+}
+
+impl TerrainGenerator {
+    pub fn new(params: TerrainParams, seed: u64) -> TerrainGenerator {
+        TerrainGenerator {
+            params: params,
+            height_noise: Perlin::new(seed),
+            overhang_noise: Perlin::new(seed.wrapping_add(1)),
+            cave_noise: Perlin::new(seed.wrapping_add(2)),
+        }
+    }
+
+    pub fn get_params(&self) -> &TerrainParams {
+        &self.params
+    }
+
+    pub fn get_surface_height(&self, x: BlockCoordSize, z: BlockCoordSize) -> f64 {
+        let frequency = self.params.height_frequency;
+        self.params.base_height + self.height_noise.fbm2(x as f64 * frequency, z as f64 * frequency, self.params.octaves) * self.params.height_amplitude
+    }
+
+    pub fn is_solid(&self, block_coords: &BlockCoords) -> bool {
+        let x = block_coords.get_x();
+        let y = block_coords.get_y();
+        let z = block_coords.get_z();
+        let overhang = self.params.overhang_frequency;
+        let density = self.get_surface_height(x, z) - y as f64
+            + self.overhang_noise.fbm3(x as f64 * overhang, y as f64 * overhang, z as f64 * overhang, 2) * self.params.overhang_strength;
+        if density <= 0.0 {
+            return false;
+        }
+        let cave = self.params.cave_frequency;
+        self.cave_noise.fbm3(x as f64 * cave, y as f64 * cave, z as f64 * cave, 2) < self.params.cave_threshold
+    }
+
+    // Grass on blocks open to the air above, dirt below that down to dirt_depth, stone under it
+    pub fn get_block(&self, block_coords: &BlockCoords) -> BlockType {
+        if !self.is_solid(block_coords) {
+            return BlockType::AIR;
+        }
+        let mut solid_above = (1..self.params.dirt_depth + 1).map(|up| self.is_solid(&block_coords.offset(0, up, 0)));
+        classify(&mut solid_above)
+    }
+
+    // Blocks of one chunk in the same x fastest, then y, then z order Chunk uses
+    pub fn generate_chunk(&self, chunk_coords: &ChunkCoords) -> Vec<BlockType> {
+        let size = CHUNK_SIZE as usize;
+        let origin = chunk_coords.get_origin();
+        let column_height = size + self.params.dirt_depth as usize;
+        let mut blocks = vec!(BlockType::AIR; CHUNK_VOLUME);
+        for z in 0..size {
+            for x in 0..size {
+                let solid: Vec<bool> = (0..column_height)
+                    .map(|y| self.is_solid(&origin.offset(x as BlockCoordSize, y as BlockCoordSize, z as BlockCoordSize)))
+                    .collect();
+                for y in 0..size {
+                    if solid[y] {
+                        let mut solid_above = solid[y + 1..y + 1 + self.params.dirt_depth as usize].iter().cloned();
+                        blocks[(z * size + y) * size + x] = classify(&mut solid_above);
+                    }
+                }
+            }
+        }
+        blocks
+    }
+
+    pub fn fill_volume(&self, voxel_volume: &mut VoxelVolume) {
+        let origin = voxel_volume.get_origin();
+        let max = voxel_volume.get_max();
+        for z in origin.get_z()..max.get_z() + 1 {
+            for x in origin.get_x()..max.get_x() + 1 {
+                let top = max.get_y() + self.params.dirt_depth;
+                let solid: Vec<bool> = (origin.get_y()..top + 1)
+                    .map(|y| self.is_solid(&BlockCoords::new(x, y, z)))
+                    .collect();
+                for y in 0..voxel_volume.get_height() {
+                    let block_type = if solid[y] {
+                        let mut solid_above = solid[y + 1..y + 1 + self.params.dirt_depth as usize].iter().cloned();
+                        classify(&mut solid_above)
+                    } else {
+                        BlockType::AIR
+                    };
+                    voxel_volume.set(&BlockCoords::new(x, origin.get_y() + y as BlockCoordSize, z), block_type);
+                }
+            }
+        }
+    }
+
+    // Top solid block of a column inside min_y..max_y, for placing spawns on the surface
+    pub fn find_surface(&self, x: BlockCoordSize, z: BlockCoordSize, min_y: BlockCoordSize, max_y: BlockCoordSize) -> Option<BlockCoordSize> {
+        let mut y = max_y;
+        while y >= min_y {
+            if self.is_solid(&BlockCoords::new(x, y, z)) && !self.is_solid(&BlockCoords::new(x, y + 1, z)) {
+                return Some(y);
+            }
+            y -= 1;
+        }
+        None
+    }
+}
+
+fn classify<I: Iterator<Item = bool>>(solid_above: &mut I) -> BlockType {
+    match solid_above.next() {
+        Some(false) => BlockType::GRASS,
+        _ => {
+            if solid_above.all(|solid| solid) {
+                BlockType::STONE
+            } else {
+                BlockType::DIRT
+            }
+        },
+    }
+}
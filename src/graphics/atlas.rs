@@ -0,0 +1,237 @@
+use std::collections::{HashMap};
+use std::error::Error;
+use std::fmt;
+
+use image::{RgbaImage, Rgba};
+
+use assets::{AssetLoader, AssetErr};
+use components::{BlockRegistry, BlockType};
+
+pub static ATLAS_WIDTH: u32 = 256;
+// Pixels around each sprite copied from its nearest edge, so filtering never samples a neighbour
+pub static ATLAS_PADDING: u32 = 1;
+static SWATCH_SIZE: u32 = 4;
+
+// Normalized rect within the atlas, (0, 0) is the top left pixel
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct UvRect {
+    min: [f32; 2],
+    max: [f32; 2],
+}
+
+impl UvRect {
+    pub fn new(min: [f32; 2], max: [f32; 2]) -> UvRect {
+        UvRect {
+            min: min,
+            max: max,
+        }
+    }
+
+    pub fn get_min(&self) -> [f32; 2] {
+        self.min
+    }
+
+    pub fn get_max(&self) -> [f32; 2] {
+        self.max
+    }
+
+    // Maps a point of the sprite, each axis 0 to 1 from its top left, into atlas space
+    pub fn get_uv(&self, s: f32, t: f32) -> [f32; 2] {
+        [
+            self.min[0] + (self.max[0] - self.min[0]) * s,
+            self.min[1] + (self.max[1] - self.min[1]) * t
+        ]
+    }
+}
+
+pub struct Atlas {
+    image: RgbaImage,
+    uv_rects: HashMap<String, UvRect>,
+    dimmed: bool,
+}
+
+impl Atlas {
+    // Shelf packing: tallest sprites first, left to right along a row until the width is used up,
+    // then a new row below the tallest sprite of the last one. Height is rounded up to a power of two
+    pub fn pack(mut sprites: Vec<(String, RgbaImage)>, width: u32) -> Result<Atlas, AtlasErr> {
+        sprites.sort_by(|a, b| b.1.height().cmp(&a.1.height()).then(a.0.cmp(&b.0)));
+        let mut placements = vec!();
+        let mut x = 0;
+        let mut y = 0;
+        let mut shelf_height = 0;
+        for &(ref name, ref sprite) in sprites.iter() {
+            let padded_width = sprite.width() + ATLAS_PADDING * 2;
+            let padded_height = sprite.height() + ATLAS_PADDING * 2;
+            if padded_width > width {
+                return Err(AtlasErr::TooWide(name.clone(), sprite.width()));
+            }
+            if x + padded_width > width {
+                x = 0;
+                y += shelf_height;
+                shelf_height = 0;
+            }
+            placements.push((x + ATLAS_PADDING, y + ATLAS_PADDING));
+            x += padded_width;
+            shelf_height = shelf_height.max(padded_height);
+        }
+        let height = (y + shelf_height).max(1).next_power_of_two();
+
+        let mut atlas_image = RgbaImage::new(width, height);
+        let mut uv_rects = HashMap::new();
+        for (&(ref name, ref sprite), &(left, top)) in sprites.iter().zip(placements.iter()) {
+            let (sprite_width, sprite_height) = sprite.dimensions();
+            for py in 0..sprite_height + ATLAS_PADDING * 2 {
+                for px in 0..sprite_width + ATLAS_PADDING * 2 {
+                    let sx = (px as i64 - ATLAS_PADDING as i64).max(0).min(sprite_width as i64 - 1) as u32;
+                    let sy = (py as i64 - ATLAS_PADDING as i64).max(0).min(sprite_height as i64 - 1) as u32;
+                    atlas_image.put_pixel(left - ATLAS_PADDING + px, top - ATLAS_PADDING + py, *sprite.get_pixel(sx, sy));
+                }
+            }
+            if uv_rects.insert(name.clone(), UvRect::new(
+                [left as f32 / width as f32, top as f32 / height as f32],
+                [(left + sprite_width) as f32 / width as f32, (top + sprite_height) as f32 / height as f32]
+            )).is_some() {
+                return Err(AtlasErr::Duplicate(name.clone()));
+            }
+        }
+        Ok(Atlas {
+            image: atlas_image,
+            uv_rects: uv_rects,
+            dimmed: false,
+        })
+    }
+
+    // Stacks a copy of the atlas darkened to brightness below it, for blocks drawn from memory
+    pub fn with_dimmed(self, brightness: f32) -> Atlas {
+        if self.dimmed {
+            return self;
+        }
+        let (width, height) = self.image.dimensions();
+        let mut atlas_image = RgbaImage::new(width, height * 2);
+        for y in 0..height {
+            for x in 0..width {
+                let pixel = *self.image.get_pixel(x, y);
+                let mut dim = pixel;
+                for channel in 0..3 {
+                    dim.data[channel] = (pixel.data[channel] as f32 * brightness).max(0.0).min(255.0) as u8;
+                }
+                atlas_image.put_pixel(x, y, pixel);
+                atlas_image.put_pixel(x, y + height, dim);
+            }
+        }
+        let uv_rects = self.uv_rects.into_iter()
+            .map(|(name, uv_rect)| {
+                let min = uv_rect.get_min();
+                let max = uv_rect.get_max();
+                (name, UvRect::new([min[0], min[1] / 2.0], [max[0], max[1] / 2.0]))
+            })
+            .collect();
+        Atlas {
+            image: atlas_image,
+            uv_rects: uv_rects,
+            dimmed: true,
+        }
+    }
+
+    pub fn is_dimmed(&self) -> bool {
+        self.dimmed
+    }
+
+    // The same sprite in the darkened half, or unchanged when there is none
+    pub fn get_dimmed_uv_rect(&self, uv_rect: &UvRect) -> UvRect {
+        if !self.dimmed {
+            return *uv_rect;
+        }
+        let min = uv_rect.get_min();
+        let max = uv_rect.get_max();
+        UvRect::new([min[0], min[1] + 0.5], [max[0], max[1] + 0.5])
+    }
+
+    // Packs every texture named in the registry, plus a flat color swatch for each block
+    // without one, so all blocks can be drawn from the same texture
+    pub fn for_blocks(block_registry: &BlockRegistry, asset_loader: &mut AssetLoader) -> Result<Atlas, AtlasErr> {
+        let mut sprites: Vec<(String, RgbaImage)> = vec!();
+        for block_type in block_registry.get_block_types() {
+            let block_def = match block_registry.get(block_type) {
+                Some(block_def) => block_def,
+                None => continue,
+            };
+            if !block_def.is_visible() {
+                continue;
+            }
+            match block_def.get_texture() {
+                Some(texture) => {
+                    if sprites.iter().any(|&(ref name, _)| name == texture) {
+                        continue;
+                    }
+                    match asset_loader.load_image(texture) {
+                        Ok(sprite) => sprites.push((texture.to_string(), (*sprite).clone())),
+                        Err(err) => return Err(AtlasErr::Asset("Asset Loader Load Image", err)),
+                    }
+                },
+                None => {
+                    let color = block_def.get_color();
+                    let pixel = Rgba {
+                        data: [
+                            (color[0] * 255.0) as u8,
+                            (color[1] * 255.0) as u8,
+                            (color[2] * 255.0) as u8,
+                            (color[3] * 255.0) as u8
+                        ],
+                    };
+                    sprites.push((swatch_name(block_def.get_name()), RgbaImage::from_pixel(SWATCH_SIZE, SWATCH_SIZE, pixel)));
+                },
+            }
+        }
+        Atlas::pack(sprites, ATLAS_WIDTH)
+    }
+
+    pub fn get_image(&self) -> &RgbaImage {
+        &self.image
+    }
+
+    pub fn get_uv_rect(&self, name: &str) -> Option<UvRect> {
+        self.uv_rects.get(name).cloned()
+    }
+
+    pub fn get_block_uv_rect(&self, block_registry: &BlockRegistry, block_type: BlockType) -> Option<UvRect> {
+        match block_registry.get(block_type) {
+            Some(block_def) => match block_def.get_texture() {
+                Some(texture) => self.get_uv_rect(texture),
+                None => self.get_uv_rect(&swatch_name(block_def.get_name())),
+            },
+            None => None,
+        }
+    }
+}
+
+fn swatch_name(block_name: &str) -> String {
+    format!("color:{}", block_name)
+}
+
+#[derive(Debug)]
+pub enum AtlasErr {
+    Asset(&'static str, AssetErr),
+    TooWide(String, u32),
+    Duplicate(String),
+}
+
+impl fmt::Display for AtlasErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            AtlasErr::Asset(_, ref err) => err.fmt(f),
+            AtlasErr::TooWide(ref name, width) => write!(f, "{} is {} pixels wide, wider than the atlas", name, width),
+            AtlasErr::Duplicate(ref name) => write!(f, "{} is packed twice", name),
+        }
+    }
+}
+
+impl Error for AtlasErr {
+    fn description(&self) -> &str {
+        match *self {
+            AtlasErr::Asset(_, ref err) => err.description(),
+            AtlasErr::TooWide(_, _) => "Sprite wider than the atlas",
+            AtlasErr::Duplicate(_) => "Sprite packed twice",
+        }
+    }
+}
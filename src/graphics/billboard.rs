@@ -0,0 +1,112 @@
+use core::{Direction};
+use components::{BlockCoords};
+use graphics::{UvRect};
+
+// Orientation of the isometric view, matching Mat4::x_rotation(pitch) * Mat4::y_rotation(yaw)
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct BillboardView {
+    pitch: f32,
+    yaw: f32,
+}
+
+// Which way a being faces as seen on screen. The view is always turned 45 degrees off the
+// grid, so each direction a being can face lands on one of the screen diagonals
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ScreenFacing {
+    TowardLeft,
+    TowardRight,
+    AwayLeft,
+    AwayRight,
+}
+
+impl ScreenFacing {
+    pub fn is_away(&self) -> bool {
+        match *self {
+            ScreenFacing::AwayLeft | ScreenFacing::AwayRight => true,
+            ScreenFacing::TowardLeft | ScreenFacing::TowardRight => false,
+        }
+    }
+
+    pub fn is_left(&self) -> bool {
+        match *self {
+            ScreenFacing::TowardLeft | ScreenFacing::AwayLeft => true,
+            ScreenFacing::TowardRight | ScreenFacing::AwayRight => false,
+        }
+    }
+}
+
+impl BillboardView {
+    pub fn new(pitch: f32, yaw: f32) -> BillboardView {
+        BillboardView {
+            pitch: pitch,
+            yaw: yaw,
+        }
+    }
+
+    pub fn isometric() -> BillboardView {
+        BillboardView::new(45.0f32.to_radians(), 45.0f32.to_radians())
+    }
+
+    pub fn get_pitch(&self) -> f32 {
+        self.pitch
+    }
+
+    pub fn get_yaw(&self) -> f32 {
+        self.yaw
+    }
+
+    // World space axes of the screen, the rows of the view rotation
+    pub fn get_right(&self) -> [f32; 3] {
+        [self.yaw.cos(), 0.0, self.yaw.sin()]
+    }
+
+    pub fn get_up(&self) -> [f32; 3] {
+        [self.pitch.sin() * self.yaw.sin(), self.pitch.cos(), -self.pitch.sin() * self.yaw.cos()]
+    }
+
+    // Direction the camera looks in
+    pub fn get_forward(&self) -> [f32; 3] {
+        [self.pitch.cos() * self.yaw.sin(), -self.pitch.sin(), -self.pitch.cos() * self.yaw.cos()]
+    }
+
+    // Distance along the view direction, larger is further from the camera
+    pub fn get_depth(&self, position: [f32; 3]) -> f32 {
+        let forward = self.get_forward();
+        position[0] * forward[0] + position[1] * forward[1] + position[2] * forward[2]
+    }
+
+    pub fn get_screen_facing(&self, direction: Direction) -> ScreenFacing {
+        let (x, _, z) = direction.get_offset();
+        let forward = self.get_forward();
+        let right = self.get_right();
+        let away = x as f32 * forward[0] + z as f32 * forward[2];
+        let side = x as f32 * right[0] + z as f32 * right[2];
+        match (away > 0.0, side < 0.0) {
+            (false, true) => ScreenFacing::TowardLeft,
+            (false, false) => ScreenFacing::TowardRight,
+            (true, true) => ScreenFacing::AwayLeft,
+            (true, false) => ScreenFacing::AwayRight,
+        }
+    }
+
+    // Upright quad standing on the bottom centre of a cell and turned to face the camera. It stays
+    // vertical so it never leans into the wall behind it, and is stretched by 1 / cos(pitch) so the
+    // sprite keeps its proportions once the view tilts it away. Same winding as the block faces
+    pub fn get_quad(&self, block_coords: &BlockCoords, width: f32, height: f32) -> [[f32; 3]; 4] {
+        let right = self.get_right();
+        let height = height / self.pitch.cos();
+        let base = [block_coords.get_x() as f32 + 0.5, block_coords.get_y() as f32, block_coords.get_z() as f32 + 0.5];
+        let corner = |side: f32, up: f32| [
+            base[0] + right[0] * side * width / 2.0,
+            base[1] + up * height,
+            base[2] + right[2] * side * width / 2.0
+        ];
+        [corner(-1.0, 0.0), corner(-1.0, 1.0), corner(1.0, 1.0), corner(1.0, 0.0)]
+    }
+}
+
+// Tex coords matching the corners of BillboardView::get_quad, (0, 0) being the top left of the frame
+pub fn get_quad_tex_coords(frame: &UvRect, flip: bool) -> [[f32; 2]; 4] {
+    let (left, right) = if flip { (1.0, 0.0) } else { (0.0, 1.0) };
+    [frame.get_uv(left, 1.0), frame.get_uv(left, 0.0), frame.get_uv(right, 0.0), frame.get_uv(right, 1.0)]
+}
@@ -0,0 +1,112 @@
+use core::{BlockCoordSize};
+use components::{BlockCoords, BlockRegistry, ChunkCoords, VoxelVolume, CHUNK_SIZE};
+use graphics::{BillboardView};
+
+static AUTO_RADIUS: f32 = 3.0;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CutawayMode {
+    Off,
+    // Only layers up to and including this y are shown
+    Slice(BlockCoordSize),
+    // Blocks between the camera and the focus are hidden
+    Auto,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Cutaway {
+    mode: CutawayMode,
+    view: BillboardView,
+    focus: Option<BlockCoords>,
+    radius: f32,
+}
+
+impl Cutaway {
+    pub fn new(mode: CutawayMode, view: BillboardView) -> Cutaway {
+        Cutaway {
+            mode: mode,
+            view: view,
+            focus: None,
+            radius: AUTO_RADIUS,
+        }
+    }
+
+    pub fn off() -> Cutaway {
+        Cutaway::new(CutawayMode::Off, BillboardView::isometric())
+    }
+
+    pub fn with_focus(mut self, focus: BlockCoords) -> Cutaway {
+        self.focus = Some(focus);
+        self
+    }
+
+    // Screen space distance from the focus within which Auto cuts blocks away
+    pub fn with_radius(mut self, radius: f32) -> Cutaway {
+        self.radius = radius;
+        self
+    }
+
+    pub fn get_mode(&self) -> CutawayMode {
+        self.mode
+    }
+
+    pub fn get_focus(&self) -> Option<BlockCoords> {
+        self.focus
+    }
+
+    // Auto hides a block when it is on or above the focus layer, nearer the camera than the
+    // focus and close to it on screen, which covers both the floors above and the walls in front
+    pub fn is_visible(&self, block_coords: &BlockCoords) -> bool {
+        match self.mode {
+            CutawayMode::Off => true,
+            CutawayMode::Slice(max_y) => block_coords.get_y() <= max_y,
+            CutawayMode::Auto => {
+                let focus = match self.focus {
+                    Some(focus) => focus,
+                    None => return true,
+                };
+                if block_coords.get_y() < focus.get_y() {
+                    return true;
+                }
+                let offset = [
+                    (block_coords.get_x() - focus.get_x()) as f32,
+                    (block_coords.get_y() - focus.get_y()) as f32,
+                    (block_coords.get_z() - focus.get_z()) as f32
+                ];
+                let forward = self.view.get_forward();
+                let depth = offset[0] * forward[0] + offset[1] * forward[1] + offset[2] * forward[2];
+                if depth >= 0.0 {
+                    return true;
+                }
+                let right = self.view.get_right();
+                let up = self.view.get_up();
+                let screen_x = offset[0] * right[0] + offset[1] * right[1] + offset[2] * right[2];
+                let screen_y = offset[0] * up[0] + offset[1] * up[1] + offset[2] * up[2];
+                screen_x * screen_x + screen_y * screen_y > self.radius * self.radius
+            },
+        }
+    }
+
+    // Whether switching from self to other changes what a chunk's mesh shows, checking one block
+    // past its edges since a cut neighbour exposes the faces next to it
+    pub fn changes_chunk(&self, other: &Cutaway, voxel_volume: &VoxelVolume, block_registry: &BlockRegistry, chunk_coords: &ChunkCoords) -> bool {
+        if self == other {
+            return false;
+        }
+        let origin = chunk_coords.get_origin();
+        for z in -1..CHUNK_SIZE + 1 {
+            for y in -1..CHUNK_SIZE + 1 {
+                for x in -1..CHUNK_SIZE + 1 {
+                    let block_coords = origin.offset(x, y, z);
+                    if !block_registry.is_visible(voxel_volume.get(&block_coords)) {
+                        continue;
+                    }
+                    if self.is_visible(&block_coords) != other.is_visible(&block_coords) {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+}
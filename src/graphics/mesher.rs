@@ -0,0 +1,196 @@
+use core::{BlockCoordSize};
+use components::{BlockCoords, BlockType, BlockRegistry, ChunkCoords, VoxelVolume, FieldOfView, Sight, CHUNK_SIZE};
+use graphics::{Atlas, Cutaway};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TexturedVertex {
+    position: [f32; 3],
+    tex_coord: [f32; 2],
+}
+
+impl TexturedVertex {
+    pub fn new(position: [f32; 3], tex_coord: [f32; 2]) -> TexturedVertex {
+        TexturedVertex {
+            position: position,
+            tex_coord: tex_coord,
+        }
+    }
+
+    pub fn get_position(&self) -> [f32; 3] {
+        self.position
+    }
+
+    pub fn get_tex_coord(&self) -> [f32; 2] {
+        self.tex_coord
+    }
+}
+
+pub struct TexturedChunkMesh {
+    chunk_coords: ChunkCoords,
+    vertices: Vec<TexturedVertex>,
+    indices: Vec<u32>,
+}
+
+impl TexturedChunkMesh {
+    fn new(chunk_coords: ChunkCoords) -> TexturedChunkMesh {
+        TexturedChunkMesh {
+            chunk_coords: chunk_coords,
+            vertices: vec!(),
+            indices: vec!(),
+        }
+    }
+
+    pub fn get_chunk_coords(&self) -> ChunkCoords {
+        self.chunk_coords
+    }
+
+    pub fn get_vertices(&self) -> &Vec<TexturedVertex> {
+        &self.vertices
+    }
+
+    pub fn get_indices(&self) -> &Vec<u32> {
+        &self.indices
+    }
+
+    pub fn get_quad_count(&self) -> usize {
+        self.vertices.len() / 4
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    // Corners must wind clockwise seen from outside the block, matching the cube
+    // indices the scene was built with under CullingMethod::CounterClockwise
+    fn push_quad(&mut self, corners: [[f32; 3]; 4], tex_coords: [[f32; 2]; 4]) {
+        let base = self.vertices.len() as u32;
+        for (corner, tex_coord) in corners.iter().zip(tex_coords.iter()) {
+            self.vertices.push(TexturedVertex::new(*corner, *tex_coord));
+        }
+        self.indices.extend([0, 1, 2, 2, 3, 0].iter().map(|index| base + index));
+    }
+}
+
+fn axis_coords(axes: [BlockCoordSize; 3]) -> BlockCoords {
+    BlockCoords::new(axes[0], axes[1], axes[2])
+}
+
+fn sight(field_of_view: Option<&FieldOfView>, block_coords: &BlockCoords) -> Sight {
+    match field_of_view {
+        Some(field_of_view) => field_of_view.get_sight(block_coords),
+        None => Sight::Visible,
+    }
+}
+
+fn cut_block(voxel_volume: &VoxelVolume, cutaway: &Cutaway, field_of_view: Option<&FieldOfView>, block_coords: &BlockCoords) -> BlockType {
+    if cutaway.is_visible(block_coords) && sight(field_of_view, block_coords) != Sight::Unseen {
+        voxel_volume.get(block_coords)
+    } else {
+        BlockType::AIR
+    }
+}
+
+// The block whose face between cell and beside should be drawn. A face is hidden by an opaque
+// neighbour or by a neighbour of the same type, so water doesn't draw inner walls. Blocks the
+// cutaway hides or that were never seen count as air, so the blocks beside them show their faces
+fn face_block(voxel_volume: &VoxelVolume, block_registry: &BlockRegistry, cutaway: &Cutaway, field_of_view: Option<&FieldOfView>, cell: [BlockCoordSize; 3], beside: [BlockCoordSize; 3]) -> Option<BlockType> {
+    let block_type = cut_block(voxel_volume, cutaway, field_of_view, &axis_coords(cell));
+    let beside_type = cut_block(voxel_volume, cutaway, field_of_view, &axis_coords(beside));
+    if block_registry.is_visible(block_type) && !block_registry.is_opaque(beside_type) && beside_type != block_type {
+        Some(block_type)
+    } else {
+        None
+    }
+}
+
+// One quad per visible face, as a repeating texture can't be stretched over a merged face
+// when it only covers its own rect of the atlas. Side faces keep the sprite upright. With a field
+// of view, unseen blocks are left out and remembered ones come from the atlas's dimmed half
+pub fn mesh_chunk_textured(voxel_volume: &VoxelVolume, block_registry: &BlockRegistry, atlas: &Atlas, cutaway: &Cutaway, field_of_view: Option<&FieldOfView>, chunk_coords: &ChunkCoords) -> TexturedChunkMesh {
+    let mut chunk_mesh = TexturedChunkMesh::new(*chunk_coords);
+    let origin = chunk_coords.get_origin();
+    let origin = [origin.get_x(), origin.get_y(), origin.get_z()];
+    let size = CHUNK_SIZE as usize;
+
+    for axis in 0..3 {
+        let u = (axis + 1) % 3;
+        let v = (axis + 2) % 3;
+        // Axes the sprite's s and t run along, t runs down the world y axis on side faces
+        let (s_axis, t_axis) = match axis {
+            0 => (2, 1),
+            1 => (0, 2),
+            _ => (0, 1),
+        };
+        for &positive in [false, true].iter() {
+            let step = if positive { 1 } else { -1 };
+            for slice in 0..size {
+                for j in 0..size {
+                    for i in 0..size {
+                        let mut cell = origin;
+                        cell[axis] += slice as BlockCoordSize;
+                        cell[u] += i as BlockCoordSize;
+                        cell[v] += j as BlockCoordSize;
+                        let mut beside = cell;
+                        beside[axis] += step;
+                        let block_type = match face_block(voxel_volume, block_registry, cutaway, field_of_view, cell, beside) {
+                            Some(block_type) => block_type,
+                            None => continue,
+                        };
+                        let uv_rect = match atlas.get_block_uv_rect(block_registry, block_type) {
+                            Some(uv_rect) => match sight(field_of_view, &axis_coords(cell)) {
+                                Sight::Remembered => atlas.get_dimmed_uv_rect(&uv_rect),
+                                _ => uv_rect,
+                            },
+                            None => continue,
+                        };
+
+                        let mut corner = [cell[0] as f32, cell[1] as f32, cell[2] as f32];
+                        if positive {
+                            corner[axis] += 1.0;
+                        }
+                        let mut p1 = corner;
+                        p1[u] += 1.0;
+                        let mut p2 = p1;
+                        p2[v] += 1.0;
+                        let mut p3 = corner;
+                        p3[v] += 1.0;
+                        let corners = if positive {
+                            [corner, p3, p2, p1]
+                        } else {
+                            [corner, p1, p2, p3]
+                        };
+                        let mut tex_coords = [[0.0; 2]; 4];
+                        for (tex_coord, point) in tex_coords.iter_mut().zip(corners.iter()) {
+                            let s = point[s_axis] - cell[s_axis] as f32;
+                            let t = point[t_axis] - cell[t_axis] as f32;
+                            *tex_coord = if t_axis == 1 {
+                                uv_rect.get_uv(s, 1.0 - t)
+                            } else {
+                                uv_rect.get_uv(s, t)
+                            };
+                        }
+                        chunk_mesh.push_quad(corners, tex_coords);
+                    }
+                }
+            }
+        }
+    }
+    chunk_mesh
+}
+
+pub fn mesh_volume_textured(voxel_volume: &VoxelVolume, block_registry: &BlockRegistry, atlas: &Atlas, cutaway: &Cutaway, field_of_view: Option<&FieldOfView>) -> Vec<TexturedChunkMesh> {
+    let min = voxel_volume.get_origin().get_chunk_coords();
+    let max = voxel_volume.get_max().get_chunk_coords();
+    let mut chunk_meshes = vec!();
+    for z in min.get_z()..max.get_z() + 1 {
+        for y in min.get_y()..max.get_y() + 1 {
+            for x in min.get_x()..max.get_x() + 1 {
+                let chunk_mesh = mesh_chunk_textured(voxel_volume, block_registry, atlas, cutaway, field_of_view, &ChunkCoords::new(x, y, z));
+                if !chunk_mesh.is_empty() {
+                    chunk_meshes.push(chunk_mesh);
+                }
+            }
+        }
+    }
+    chunk_meshes
+}
@@ -0,0 +1,9 @@
+pub mod mesher;
+pub mod atlas;
+pub mod billboard;
+pub mod cutaway;
+
+pub use self::mesher::{TexturedVertex, TexturedChunkMesh, mesh_chunk_textured, mesh_volume_textured};
+pub use self::atlas::{Atlas, AtlasErr, UvRect, ATLAS_WIDTH, ATLAS_PADDING};
+pub use self::billboard::{BillboardView, ScreenFacing, get_quad_tex_coords};
+pub use self::cutaway::{Cutaway, CutawayMode};
@@ -0,0 +1,89 @@
+use std::str::{FromStr};
+
+use core::{Direction};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Action {
+    MoveNorth,
+    MoveSouth,
+    MoveEast,
+    MoveWest,
+    Wait,
+    Fire,
+    PickUp,
+    ZoomIn,
+    ZoomOut,
+    RotateLeft,
+    RotateRight,
+    PanUp,
+    PanDown,
+    PanLeft,
+    PanRight,
+    ResetPan,
+    SliceUp,
+    SliceDown,
+    ToggleCutaway,
+    Save,
+}
+
+// Names used in the input config, in declaration order
+static ACTION_NAMES: &'static [(&'static str, Action)] = &[
+    ("move_north", Action::MoveNorth),
+    ("move_south", Action::MoveSouth),
+    ("move_east", Action::MoveEast),
+    ("move_west", Action::MoveWest),
+    ("wait", Action::Wait),
+    ("fire", Action::Fire),
+    ("pick_up", Action::PickUp),
+    ("zoom_in", Action::ZoomIn),
+    ("zoom_out", Action::ZoomOut),
+    ("rotate_left", Action::RotateLeft),
+    ("rotate_right", Action::RotateRight),
+    ("pan_up", Action::PanUp),
+    ("pan_down", Action::PanDown),
+    ("pan_left", Action::PanLeft),
+    ("pan_right", Action::PanRight),
+    ("reset_pan", Action::ResetPan),
+    ("slice_up", Action::SliceUp),
+    ("slice_down", Action::SliceDown),
+    ("toggle_cutaway", Action::ToggleCutaway),
+    ("save", Action::Save),
+];
+
+impl Action {
+    pub fn get_all() -> Vec<Action> {
+        ACTION_NAMES.iter().map(|&(_, action)| action).collect()
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        for &(name, action) in ACTION_NAMES.iter() {
+            if action == *self {
+                return name;
+            }
+        }
+        unreachable!()
+    }
+
+    pub fn get_direction(&self) -> Option<Direction> {
+        match *self {
+            Action::MoveNorth => Some(Direction::North),
+            Action::MoveSouth => Some(Direction::South),
+            Action::MoveEast => Some(Direction::East),
+            Action::MoveWest => Some(Direction::West),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Action {
+    type Err = ();
+
+    fn from_str(name: &str) -> Result<Action, ()> {
+        for &(action_name, action) in ACTION_NAMES.iter() {
+            if action_name == name {
+                return Ok(action);
+            }
+        }
+        Err(())
+    }
+}
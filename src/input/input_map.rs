@@ -0,0 +1,87 @@
+use std::collections::{HashMap};
+
+use core::{Config, ConfigErr};
+use input::{Action};
+
+pub static INPUT_PATH: &'static str = "assets/input.cfg";
+
+// Which inputs trigger each action. Inputs are named after the window's key codes such as
+// W, Up or Space, plus MouseLeft style buttons and WheelUp and WheelDown
+#[derive(Clone, Debug)]
+pub struct InputMap {
+    bindings: HashMap<Action, Vec<String>>,
+}
+
+impl InputMap {
+    pub fn new() -> InputMap {
+        InputMap {
+            bindings: HashMap::new(),
+        }
+    }
+
+    pub fn load(path: &str) -> Result<InputMap, ConfigErr> {
+        match Config::load(path) {
+            Ok(config) => InputMap::from_config(&config),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<InputMap, ConfigErr> {
+        match Config::parse(text) {
+            Ok(config) => InputMap::from_config(&config),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Result<InputMap, ConfigErr> {
+        let mut input_map = InputMap::new();
+        for section in config.get_sections() {
+            match section.get_name() {
+                "bindings" => for entry in section.get_entries() {
+                    let action = match entry.get_key().parse() {
+                        Ok(action) => action,
+                        Err(()) => return Err(ConfigErr::Value(entry.get_line(), "action".to_string(), entry.get_key().to_string())),
+                    };
+                    match entry.parse_list() {
+                        Ok(inputs) => input_map.rebind(action, inputs),
+                        Err(err) => return Err(err),
+                    }
+                },
+                _ => return Err(ConfigErr::Syntax(section.get_line(), "Unknown input section, expected [bindings]")),
+            }
+        }
+        Ok(input_map)
+    }
+
+    pub fn get_inputs(&self, action: Action) -> &[String] {
+        match self.bindings.get(&action) {
+            Some(inputs) => inputs,
+            None => &[],
+        }
+    }
+
+    // Actions bound to input, in declaration order
+    pub fn get_actions(&self, input: &str) -> Vec<Action> {
+        Action::get_all().into_iter()
+            .filter(|action| self.get_inputs(*action).iter().any(|bound| bound == input))
+            .collect()
+    }
+
+    pub fn bind(&mut self, action: Action, input: &str) {
+        let inputs = self.bindings.entry(action).or_insert(vec!());
+        if !inputs.iter().any(|bound| bound == input) {
+            inputs.push(input.to_string());
+        }
+    }
+
+    pub fn rebind(&mut self, action: Action, inputs: Vec<String>) {
+        self.bindings.insert(action, inputs);
+    }
+
+    // Removes input from every action it triggers
+    pub fn unbind(&mut self, input: &str) {
+        for inputs in self.bindings.values_mut() {
+            inputs.retain(|bound| bound != input);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use dorp::{Window};
+use dorp::glium::glutin::{Event, ElementState, MouseScrollDelta};
+
+use input::{Action, InputMap};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum InputEvent {
+    Pressed(String),
+    Released(String),
+}
+
+// Window events queue up between frames and are turned into the frame's actions on update,
+// so every entity sees the same actions for the whole of tick_mut
+pub struct InputState {
+    input_map: InputMap,
+    pending: Vec<InputEvent>,
+    held: Vec<String>,
+    pressed: Vec<Action>,
+    frame: u64,
+}
+
+impl InputState {
+    pub fn new(input_map: InputMap) -> InputState {
+        InputState {
+            input_map: input_map,
+            pending: vec!(),
+            held: vec!(),
+            pressed: vec!(),
+            frame: 0,
+        }
+    }
+
+    pub fn get_input_map(&self) -> &InputMap {
+        &self.input_map
+    }
+
+    pub fn get_mut_input_map(&mut self) -> &mut InputMap {
+        &mut self.input_map
+    }
+
+    // Counts updates, so readers that tick more often than frames render can act once per frame
+    pub fn get_frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn get_pressed(&self) -> &Vec<Action> {
+        &self.pressed
+    }
+
+    pub fn was_pressed(&self, action: Action) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn is_held(&self, action: Action) -> bool {
+        self.input_map.get_inputs(action).iter().any(|input| self.held.contains(input))
+    }
+
+    pub fn push_event(&mut self, input_event: InputEvent) {
+        self.pending.push(input_event);
+    }
+
+    pub fn poll_window(&mut self, window: &mut Window) {
+        for event in window.poll_events() {
+            match event {
+                Event::KeyboardInput(state, _, Some(key)) => self.push_state(state, format!("{:?}", key)),
+                Event::MouseInput(state, button) => self.push_state(state, format!("Mouse{:?}", button)),
+                Event::MouseWheel(delta, _) => {
+                    let y = match delta {
+                        MouseScrollDelta::LineDelta(_, y) => y,
+                        MouseScrollDelta::PixelDelta(_, y) => y,
+                    };
+                    let input = if y > 0.0 {
+                        "WheelUp"
+                    } else if y < 0.0 {
+                        "WheelDown"
+                    } else {
+                        continue;
+                    };
+                    self.push_event(InputEvent::Pressed(input.to_string()));
+                    self.push_event(InputEvent::Released(input.to_string()));
+                },
+                _ => (),
+            }
+        }
+    }
+
+    fn push_state(&mut self, state: ElementState, input: String) {
+        match state {
+            ElementState::Pressed => self.push_event(InputEvent::Pressed(input)),
+            ElementState::Released => self.push_event(InputEvent::Released(input)),
+        }
+    }
+
+    // Key repeat arrives as more presses of a held input, which are ignored
+    pub fn update(&mut self) {
+        self.pressed.clear();
+        for input_event in self.pending.drain(..) {
+            match input_event {
+                InputEvent::Pressed(input) => {
+                    if self.held.contains(&input) {
+                        continue;
+                    }
+                    for action in self.input_map.get_actions(&input) {
+                        if !self.pressed.contains(&action) {
+                            self.pressed.push(action);
+                        }
+                    }
+                    self.held.push(input);
+                },
+                InputEvent::Released(input) => self.held.retain(|held| *held != input),
+            }
+        }
+        self.frame += 1;
+    }
+}
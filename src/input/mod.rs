@@ -0,0 +1,7 @@
+pub mod action;
+pub mod input_map;
+pub mod input_state;
+
+pub use self::action::{Action};
+pub use self::input_map::{InputMap, INPUT_PATH};
+pub use self::input_state::{InputState, InputEvent};
@@ -0,0 +1,49 @@
+extern crate dorp;
+extern crate image;
+
+pub mod assets;
+pub mod components;
+pub mod entities;
+pub mod core;
+pub mod fov;
+pub mod graphics;
+pub mod input;
+pub mod pathing;
+pub mod generation;
+pub mod save;
+
+pub use assets::{AssetManifest, AssetLoader, AssetErr, MANIFEST_PATH};
+pub use input::{Action, InputMap, InputState, InputEvent, INPUT_PATH};
+pub use core::{
+    RogueData, RogueDataErr, RogueWorld, Player, Direction, Rng, Config, ConfigErr, BLOCK_MAP_NAME,
+    BLOCK_REGISTRY_NAME, PLAYER_NAME, VOXEL_VOLUME_NAME, ITEM_MAP_NAME, CAMERA_NAME, INPUT_NAME, SCHEDULER_NAME, Scheduler, SchedulerErr,
+    PlayerAction, Ai, AiErr, AiState, AiAction, new_scene, build_scene, build_generated_scene, build_level_file, new_level_file_scene, pick_monster_spawns, pick_item_spawns,
+    HeadlessLevel, run_headless, run_voxel_bench, tick_mut_entity, Shot, AttackErr, trace_shot, blocks_shot, strike, fire, LootErr, pick_up, drop_item, use_item
+};
+pub use components::{
+    Block, BlockErr, BlockMap, BlockCoords, BlockType, Chunk, ChunkCoords, CHUNK_SIZE, CHUNK_VOLUME,
+    VoxelVolume, BlockRegistry, BlockRegistryErr, BlockDef, BLOCKS_PATH, Sprite, SpriteSheet, Camera,
+    ChunkView, Actor, FieldOfView, Sight, ACTION_COST, NORMAL_SPEED, SIGHT_RADIUS, MonsterRegistry,
+    MonsterRegistryErr, MonsterDef, MONSTERS_PATH, Combat, DamageType, AttackResult,
+    StatBonus, Inventory, InventoryErr, Item, ItemMap, ItemRegistry, ItemRegistryErr, ItemDef, EquipSlot, ITEMS_PATH
+};
+pub use save::{
+    SaveFile, SavedLevel, SavedActor, SavedSight, SavedAi, SavedInventory, SavedItem, SaveErr, RestoreErr, SAVE_PATH,
+    SAVE_VERSION, save_world, new_saved_scene, restore_world, restore_volume, ChunkFile, SavedChunk, ChunkFileErr,
+    CHUNK_FILE_MAGIC, CHUNK_FILE_VERSION, checksum, ChunkLoad, load_level_chunks
+};
+pub use generation::{
+    Layout, Room, Tile, LevelKind, DungeonParams, generate_dungeon, CaveParams, generate_cave, Perlin,
+    TerrainParams, TerrainGenerator, LevelFile, LevelFileErr
+};
+pub use graphics::{
+    TexturedVertex, TexturedChunkMesh, Atlas, AtlasErr, UvRect, BillboardView, ScreenFacing, Cutaway, CutawayMode,
+    mesh_chunk_textured, mesh_volume_textured
+};
+pub use entities::{
+    new_block_entity, BlockEntityErr, new_block_map_entity, BlockMapEntityErr, new_player_entity,
+    PlayerEntityErr, new_block_registry_entity, BlockRegistryEntityErr, new_camera_entity, CameraEntityErr,
+    new_input_entity, InputEntityErr, new_scheduler_entity, SchedulerEntityErr, new_monster_entity,
+    MonsterEntityErr, new_voxel_volume_entity, VoxelVolumeEntityErr, new_item_entity, ItemEntityErr, new_item_map_entity,
+    ItemMapEntityErr
+};
@@ -1,24 +1,116 @@
 extern crate dorp;
+extern crate rogue;
 
-use std::sync::{Arc};
-use std::fmt;
-use std::error::{Error};
+use std::env;
+use std::process;
+use std::str::{FromStr};
 
 use dorp::{
-    WindowBuilder, Game, Vec2, Renderable, Transform, IdManager, Id, Mat4, IdType, Vec3,
-    RenderableVertexColor, Named, Vec4, DEG_TO_RAD, Scene, WorldErr, NamedErr, OptErr
+    WindowBuilder, Game, Vec2, IdManager, Id, IdType
 };
-use dorp::graphics::vertex_color;
 
+use rogue::{
+    RogueData, LevelKind, DungeonParams, CaveParams, TerrainParams, BlockRegistry, MonsterRegistry, ItemRegistry, AssetLoader,
+    InputMap, InputState, SaveFile, LevelFile, HeadlessLevel, BLOCKS_PATH, MONSTERS_PATH, ITEMS_PATH, MANIFEST_PATH, INPUT_PATH, new_scene,
+    new_saved_scene, new_level_file_scene, new_input_entity, run_headless, run_voxel_bench
+};
 
-pub mod components;
-pub mod entities;
-pub mod core;
-
-use core::{RogueData, RogueDataErr, BLOCK_MAP_NAME};
-use components::{BlockMap, BlockCoords, BlockType, Block, BlockErr};
+static DEFAULT_SEED: u64 = 0;
+static LEVEL_SIZE: usize = 48;
+static TERRAIN_HEIGHT: usize = 32;
 
 pub fn main() {
+    let args: Vec<String> = env::args().collect();
+    if has_flag(&args, "--check-assets") {
+        if check_assets() > 0 {
+            process::exit(1);
+        }
+        return;
+    }
+    let seed = parse_flag(&args, "--seed", 0).unwrap_or(DEFAULT_SEED);
+    let block_registry = match BlockRegistry::load(BLOCKS_PATH) {
+        Ok(block_registry) => block_registry,
+        Err(err) => panic!("{}: {}", BLOCKS_PATH, err),
+    };
+    let monster_registry = match MonsterRegistry::load(MONSTERS_PATH) {
+        Ok(monster_registry) => monster_registry,
+        Err(err) => panic!("{}: {}", MONSTERS_PATH, err),
+    };
+    let item_registry = match ItemRegistry::load(ITEMS_PATH) {
+        Ok(item_registry) => item_registry,
+        Err(err) => panic!("{}: {}", ITEMS_PATH, err),
+    };
+    let asset_loader = match AssetLoader::load(MANIFEST_PATH) {
+        Ok(asset_loader) => asset_loader,
+        Err(err) => panic!("{}", err),
+    };
+    let asset_manifest = asset_loader.get_manifest().clone();
+    let level_kind = if has_flag(&args, "--cave") {
+        let mut params = CaveParams::new(LEVEL_SIZE, LEVEL_SIZE);
+        if let Some(fill_ratio) = parse_flag(&args, "--fill", 0) {
+            params = params.with_fill_ratio(fill_ratio);
+        }
+        if let Some(iterations) = parse_flag(&args, "--iterations", 0) {
+            params = params.with_iterations(iterations);
+        }
+        LevelKind::Cave(params)
+    } else if has_flag(&args, "--terrain") {
+        LevelKind::Terrain(TerrainParams::new(LEVEL_SIZE, TERRAIN_HEIGHT, LEVEL_SIZE))
+    } else {
+        LevelKind::Dungeon(DungeonParams::new(LEVEL_SIZE, LEVEL_SIZE))
+    };
+    // A loaded save replaces the generated level, so seed, level and chunk flags don't apply to it
+    let save_file = match get_flag_value(&args, "--load") {
+        Some(path) => match SaveFile::load(path) {
+            Ok(save_file) => Some(save_file),
+            Err(err) => panic!("{}: {}", path, err),
+        },
+        None => None,
+    };
+    // A hand drawn level replaces the generated one too, the seed still drives the monsters
+    let level_file = match get_flag_value(&args, "--level") {
+        Some(path) => match LevelFile::load(path, &block_registry, &monster_registry, &item_registry) {
+            Ok(level_file) => Some(level_file),
+            Err(err) => panic!("{}: {}", path, err),
+        },
+        None => None,
+    };
+    if has_flag(&args, "--headless") {
+        let ticks = parse_flag(&args, "--ticks", 0).unwrap_or(100);
+        let headless_level = match (&save_file, &level_file) {
+            (&Some(ref save_file), _) => {
+                println!("Turn: {}", save_file.get_turn());
+                HeadlessLevel::Load(save_file)
+            },
+            (&None, &Some(ref level_file)) => {
+                println!("Seed: {}", seed);
+                HeadlessLevel::File(level_file, seed)
+            },
+            (&None, &None) => {
+                println!("Seed: {}", seed);
+                match get_flag_value(&args, "--chunks") {
+                    Some(chunk_path) => HeadlessLevel::Cached(&level_kind, seed, chunk_path),
+                    None => HeadlessLevel::Generate(&level_kind, seed),
+                }
+            },
+        };
+        let report = run_headless(headless_level, &block_registry, &monster_registry, &item_registry, &asset_manifest, ticks, get_flag_value(&args, "--save")).unwrap();
+        println!("{}", report);
+        return;
+    }
+    if has_flag(&args, "--bench-voxels") {
+        let ticks = parse_flag(&args, "--ticks", 0).unwrap_or(1);
+        let width = parse_flag(&args, "--size", 0).unwrap_or(256);
+        let height = parse_flag(&args, "--size", 1).unwrap_or(64);
+        let depth = parse_flag(&args, "--size", 2).unwrap_or(256);
+        let report = run_voxel_bench(&block_registry, width, height, depth, ticks).unwrap();
+        println!("{}", report);
+        return;
+    }
+    let input_map = match InputMap::load(INPUT_PATH) {
+        Ok(input_map) => input_map,
+        Err(err) => panic!("{}: {}", INPUT_PATH, err),
+    };
     let mut manager = IdManager::new();
     let (mut window, resolution) = WindowBuilder::new()
         .with_title("Rogue".to_string())
@@ -28,159 +120,21 @@ pub fn main() {
     let mut game = Game::<RogueData>::new(thread_count, Vec2::from([resolution.0 as f32, resolution.1 as f32]));
     {
         let mut world = game.get_mut_world().unwrap();
+        new_input_entity(InputState::new(input_map), &mut manager, &mut world).unwrap();
         {
             let id = Id::new(&mut manager, IdType::Entity);
-            let scene = Scene::new(Box::new(|manager, world| {
-                let block_map_id = {
-                    let id = Id::new(manager, IdType::Entity);
-                    let block_map = BlockMap::new();
-                    let named = match Named::new(BLOCK_MAP_NAME, id, world) {
-                        Ok(named) => named,
-                        Err(err) => return Err(Box::new(SceneErr::Named("Named New", err))),
-                    };
-                    match world.add_entity(RogueData::new(id)
-                        .with_block_map(
-                            block_map
-                        )
-                        .with_named(
-                            named
-                        )
-                    ) {
-                        Ok(()) => (),
-                        Err(err) => return Err(Box::new(SceneErr::World("World Add Entity", err))),
-                    }
-                    id
-                };
-                {
-                    let vertex_color = {
-                        let mut vertex_color = RenderableVertexColor::new(manager);
-                        vertex_color.set_indices(vec!(
-                            0, 1, 2,
-                            2, 3, 0,
-
-                            5, 4, 0,
-                            0, 1, 5,
-
-                            1, 2, 6,
-                            6, 5, 1,
-
-                            3, 7, 6,
-                            6, 2, 3,
-
-                            0, 4, 7,
-                            7, 3, 0,
-
-                            4, 5, 6,
-                            6, 7, 4
-                         ));
-                         vertex_color.set_draw_method(vertex_color::DrawMethod::Both(vertex_color::DepthTestMethod::IfLess, vertex_color::CullingMethod::CounterClockwise));
-                         vertex_color.set_perspective(Mat4::orthographic(0.1, 100.0, 90.0, world.get_aspect_ratio()));
-                         vertex_color.set_view(Mat4::x_rotation(45.0 * DEG_TO_RAD) * Mat4::y_rotation(45.0 * DEG_TO_RAD));
-                         Arc::new(vertex_color)
-                    };
-                    {
-                        let mut colors : Vec<Vec4> = vec!();
-                        let p0 = Vec3::from([-10.0, -1.0, -10.0]);
-                        let p1 = Vec3::from([10.0, 1.0, 10.0]);
-                        let scale = {
-                            let b = p1 - p0;
-                            Vec3::from([1.0 / b[0], 1.0 / b[1], 1.0 / b[2]])
-                        };
-                        let width = (p1[0] - p0[0]) as i32;
-                        let height = (p1[1] - p0[1]) as i32;
-                        let depth = (p1[2] - p0[2]) as i32;
-                        for z in p0[2] as i32..p1[2] as i32 {
-                            for y in p0[1] as i32..p1[1] as i32 {
-                                for x in p0[0] as i32..p1[0] as i32 {
-                                    colors.push(Vec4::from([(x).abs() as f32 % 0.9, (y).abs() as f32 % 0.9, (z).abs() as f32 % 0.9, 1.0]))
-                                }
-                            }
-                        }
-                        for z in 0..depth - 1 {
-                            for y in 0..height - 1 {
-                                for x in 0..width - 1 {
-                                    let id = Id::new(manager, IdType::Entity);
-                                    let mut renderable = Renderable::new();
-                                    {
-                                        let mut vertex_color = RenderableVertexColor::new_from(vertex_color.clone());
-                                        vertex_color.set_vertex_id(Id::new(manager, IdType::Vertex));
-                                        vertex_color.set_vertices(vec!(
-                                            vertex_color::Vertex::new([0.0, 0.0, 0.0], match colors.get(((z + 0) * height * width + (y + 0) * width + (x + 0)) as usize) {
-                                                Some(color) => color.get_vals(),
-                                                None => return Err(Box::new(SceneErr::Get("Colors Get"))),
-                                            }),
-                                            vertex_color::Vertex::new([0.0, 1.0, 0.0], match colors.get(((z + 0) * height * width + (y + 1) * width + (x + 0)) as usize) {
-                                                Some(color) => color.get_vals(),
-                                                None => return Err(Box::new(SceneErr::Get("Colors Get"))),
-                                            }),
-                                            vertex_color::Vertex::new([1.0, 1.0, 0.0], match colors.get(((z + 0) * height * width + (y + 1) * width + (x + 1)) as usize) {
-                                                Some(color) => color.get_vals(),
-                                                None => return Err(Box::new(SceneErr::Get("Colors Get"))),
-                                            }),
-                                            vertex_color::Vertex::new([1.0, 0.0, 0.0], match colors.get(((z + 0) * height * width + (y + 0) * width + (x + 1)) as usize) {
-                                                Some(color) => color.get_vals(),
-                                                None => return Err(Box::new(SceneErr::Get("Colors Get"))),
-                                            }),
-                                            vertex_color::Vertex::new([0.0, 0.0, 1.0], match colors.get(((z + 1) * height * width + (y + 0) * width + (x + 0)) as usize) {
-                                                Some(color) => color.get_vals(),
-                                                None => return Err(Box::new(SceneErr::Get("Colors Get"))),
-                                            }),
-                                            vertex_color::Vertex::new([0.0, 1.0, 1.0], match colors.get(((z + 1) * height * width + (y + 1) * width + (x + 0)) as usize) {
-                                                Some(color) => color.get_vals(),
-                                                None => return Err(Box::new(SceneErr::Get("Colors Get"))),
-                                            }),
-                                            vertex_color::Vertex::new([1.0, 1.0, 1.0], match colors.get(((z + 1) * height * width + (y + 1) * width + (x + 1)) as usize) {
-                                                Some(color) => color.get_vals(),
-                                                None => return Err(Box::new(SceneErr::Get("Colors Get"))),
-                                            }),
-                                            vertex_color::Vertex::new([1.0, 0.0, 1.0], match colors.get(((z + 1) * height * width + (y + 0) * width + (x + 1)) as usize) {
-                                                Some(color) => color.get_vals(),
-                                                None => return Err(Box::new(SceneErr::Get("Colors Get"))),
-                                            }),
-                                        ));
-                                        vertex_color.set_model_id(Id::new(manager, IdType::Matrix));
-                                        vertex_color.set_model(Mat4::identity());
-                                        renderable.set_vertex_color(vertex_color);
-                                    }
-                                    let mut transform  = Transform::new();
-                                    let sx0 = (x as f32 + p0[0]) * scale[0];
-                                    let sy0 = (y as f32 + p0[1]) * scale[1];
-                                    let sz0 = (z as f32 + p0[2]) * scale[2];
-                                    transform.set_position(Vec3::from([sx0, sy0, sz0]));
-                                    transform.set_scalation(scale);
-
-                                    let block_coords = BlockCoords::new(x as i64, y as i64, z as i64);
-
-                                    let block = {
-                                        let mut block_map = match world.get_mut_entity_by_id(block_map_id) {
-                                            OptErr::Full(block_map_entity) => match block_map_entity.get_mut_block_map() {
-                                                OptErr::Full(block_map) => block_map,
-                                                OptErr::Empty => return Err(Box::new(SceneErr::Get("Block Map Entity Get Mut Block Map"))),
-                                                OptErr::Error(err) => return Err(Box::new(SceneErr::RogueData("Block Map Entity Get mut Block Map", err))),
-                                            },
-                                            OptErr::Empty => return Err(Box::new(SceneErr::Get("World Get Mut Entity By Id Block Map Id"))),
-                                            OptErr::Error(err) => return Err(Box::new(SceneErr::World("World Get Mut Entity By Id Block map Id", err))),
-                                        };
-                                        Block::new_with_block_map(BlockType::Dirt, id, &block_coords, &mut block_map, block_map_id)
-                                    };
-
-                                    match world.add_entity(RogueData::new(id)
-                                        .with_renderable(renderable)
-                                        .with_transform(transform)
-                                        .with_block_coords(block_coords)
-                                        .with_block(block)
-                                    ) {
-                                        Ok(()) => (),
-                                        Err(err) => return Err(Box::new(SceneErr::World("World Add Entity", err))),
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-                println!("Scene Loaded");
-                Ok(())
-            }));
+            let scene = match (save_file, level_file) {
+                (Some(save_file), _) => new_saved_scene(save_file, block_registry, monster_registry, item_registry, asset_manifest),
+                (None, Some(level_file)) => {
+                    println!("Seed: {}", seed);
+                    new_level_file_scene(level_file, block_registry, asset_manifest, seed)
+                },
+                (None, None) => {
+                    println!("Seed: {}", seed);
+                    let chunk_path = get_flag_value(&args, "--chunks").map(|chunk_path| chunk_path.to_string());
+                    new_scene(level_kind, block_registry, monster_registry, item_registry, asset_manifest, seed, chunk_path)
+                },
+            };
             world.add_entity(
                 RogueData::new(id)
                 .with_scene(
@@ -193,35 +147,75 @@ pub fn main() {
     game.run(&mut window, &mut manager).unwrap();
 }
 
-#[derive(Debug)]
-enum SceneErr {
-    World(&'static str, WorldErr),
-    Named(&'static str, NamedErr),
-    Block(&'static str, BlockErr),
-    RogueData(&'static str, RogueDataErr),
-    Get(&'static str),
+// Loads every data file and image, printing each problem instead of stopping at the first, and
+// returns how many there were
+fn check_assets() -> usize {
+    let mut problems = vec!();
+    let block_registry = match BlockRegistry::load(BLOCKS_PATH) {
+        Ok(block_registry) => Some(block_registry),
+        Err(err) => {
+            problems.push(format!("{}: {}", BLOCKS_PATH, err));
+            None
+        },
+    };
+    match MonsterRegistry::load(MONSTERS_PATH) {
+        Ok(_) => (),
+        Err(err) => problems.push(format!("{}: {}", MONSTERS_PATH, err)),
+    }
+    match ItemRegistry::load(ITEMS_PATH) {
+        Ok(_) => (),
+        Err(err) => problems.push(format!("{}: {}", ITEMS_PATH, err)),
+    }
+    match InputMap::load(INPUT_PATH) {
+        Ok(_) => (),
+        Err(err) => problems.push(format!("{}: {}", INPUT_PATH, err)),
+    }
+    let mut image_count = 0;
+    match AssetLoader::load(MANIFEST_PATH) {
+        Ok(mut asset_loader) => {
+            image_count = asset_loader.get_manifest().get_images().len();
+            // Block textures can only be checked against a registry that loaded
+            match block_registry {
+                Some(ref block_registry) => for err in asset_loader.check(block_registry) {
+                    problems.push(err.to_string());
+                },
+                None => for name in asset_loader.get_manifest().get_images().iter().map(|entry| entry.get_name().to_string()).collect::<Vec<String>>() {
+                    match asset_loader.load_image(&name) {
+                        Ok(_) => (),
+                        Err(err) => problems.push(err.to_string()),
+                    }
+                },
+            }
+        },
+        Err(err) => problems.push(err.to_string()),
+    }
+    for problem in problems.iter() {
+        println!("{}", problem);
+    }
+    println!("Checked {} assets, {} problems", image_count, problems.len());
+    problems.len()
 }
 
-impl fmt::Display for SceneErr {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            SceneErr::World(_, ref err) => err.fmt(f),
-            SceneErr::Named(_, ref err) => err.fmt(f),
-            SceneErr::Block(_, ref err) => err.fmt(f),
-            SceneErr::RogueData(_, ref err) => err.fmt(f),
-            SceneErr::Get(_) => write!(f, "Get was None"),
-        }
+fn has_flag(args: &Vec<String>, flag: &str) -> bool {
+    args.iter().any(|arg| arg == flag)
+}
+
+fn get_flag_value<'a>(args: &'a Vec<String>, flag: &str) -> Option<&'a str> {
+    match args.iter().position(|arg| arg == flag) {
+        Some(index) => match args.get(index + 1) {
+            Some(value) => Some(value.as_str()),
+            None => panic!("{} expects a path", flag),
+        },
+        None => None,
     }
 }
 
-impl Error for SceneErr {
-    fn description(&self) -> &str {
-        match *self {
-            SceneErr::World(_, ref err) => err.description(),
-            SceneErr::Named(_, ref err) => err.description(),
-            SceneErr::Block(_, ref err) => err.description(),
-            SceneErr::RogueData(_, ref err) => err.description(),
-            SceneErr::Get(_) => "Get was None",
-        }
+fn parse_flag<T: FromStr>(args: &Vec<String>, flag: &str, offset: usize) -> Option<T> {
+    match args.iter().position(|arg| arg == flag) {
+        Some(index) => match args.get(index + 1 + offset).and_then(|value| value.parse().ok()) {
+            Some(value) => Some(value),
+            None => panic!("{} expects a number", flag),
+        },
+        None => None,
     }
 }
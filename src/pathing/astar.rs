@@ -0,0 +1,86 @@
+use std::cmp::{Ordering};
+use std::collections::{BinaryHeap, HashMap};
+
+use components::{BlockCoords};
+use pathing::{NavGrid};
+
+// Heap entry ordered so the lowest priority pops first, ties by volume index for determinism
+#[derive(PartialEq, Eq)]
+pub struct OpenNode {
+    pub priority: i64,
+    pub index: usize,
+}
+
+impl Ord for OpenNode {
+    fn cmp(&self, other: &OpenNode) -> Ordering {
+        other.priority.cmp(&self.priority).then(other.index.cmp(&self.index))
+    }
+}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &OpenNode) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Every step moves one column and costs at least one, so the column distance never overestimates
+fn heuristic(from: &BlockCoords, to: &BlockCoords) -> u32 {
+    ((from.get_x() - to.get_x()).abs() + (from.get_z() - to.get_z()).abs()) as u32
+}
+
+// The cheapest path from one standing cell to another, both included, with its cost
+pub fn find_path(nav_grid: &NavGrid, from: &BlockCoords, to: &BlockCoords) -> Option<(Vec<BlockCoords>, u32)> {
+    let voxel_volume = nav_grid.get_voxel_volume();
+    let start = match voxel_volume.get_index(from) {
+        Some(start) => start,
+        None => return None,
+    };
+    let goal = match voxel_volume.get_index(to) {
+        Some(goal) => goal,
+        None => return None,
+    };
+    if !nav_grid.can_stand(to) {
+        return None;
+    }
+    let mut open = BinaryHeap::new();
+    let mut costs: HashMap<usize, u32> = HashMap::new();
+    let mut came_from: HashMap<usize, usize> = HashMap::new();
+    costs.insert(start, 0);
+    open.push(OpenNode {
+        priority: heuristic(from, to) as i64,
+        index: start,
+    });
+    while let Some(node) = open.pop() {
+        if node.index == goal {
+            let mut path = vec!(*to);
+            let mut index = goal;
+            while let Some(previous) = came_from.get(&index) {
+                index = *previous;
+                path.push(voxel_volume.get_coords(index));
+            }
+            path.reverse();
+            return Some((path, costs[&goal]));
+        }
+        let cell = voxel_volume.get_coords(node.index);
+        let cost = costs[&node.index];
+        if node.priority > (cost + heuristic(&cell, to)) as i64 {
+            continue;
+        }
+        for (next, step_cost) in nav_grid.get_steps(&cell) {
+            let next_index = match voxel_volume.get_index(&next) {
+                Some(next_index) => next_index,
+                None => continue,
+            };
+            let next_cost = cost + step_cost;
+            if costs.get(&next_index).map_or(true, |known| next_cost < *known) {
+                costs.insert(next_index, next_cost);
+                came_from.insert(next_index, node.index);
+                open.push(OpenNode {
+                    priority: (next_cost + heuristic(&next, to)) as i64,
+                    index: next_index,
+                });
+            }
+        }
+    }
+    None
+}
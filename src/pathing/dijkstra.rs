@@ -0,0 +1,111 @@
+use std::collections::{BinaryHeap, HashMap};
+
+use components::{BlockCoords};
+use pathing::{NavGrid, OpenNode};
+
+// How much further a fleeing walker prefers open ground over corners
+pub static FLEE_FACTOR: f64 = -1.2;
+
+// Cost of getting from each standing cell to the nearest goal, for many walkers chasing or
+// fleeing the same targets without a search each
+pub struct DijkstraMap {
+    values: HashMap<BlockCoords, i64>,
+    max_cost: i64,
+}
+
+impl DijkstraMap {
+    // Chasing map, zero at every goal and growing with the walk cost away from them
+    pub fn new(nav_grid: &NavGrid, goals: &[BlockCoords], max_cost: i64) -> DijkstraMap {
+        let seeds = goals.iter().filter(|goal| nav_grid.can_stand(goal)).map(|goal| (*goal, 0)).collect();
+        DijkstraMap::from_seeds(nav_grid, seeds, max_cost)
+    }
+
+    // Fleeing map, the chasing map scaled by a negative factor and rescanned so walkers head
+    // for ground far from the goals rather than into the nearest dead end
+    pub fn flee(nav_grid: &NavGrid, chase: &DijkstraMap, factor: f64) -> DijkstraMap {
+        let seeds = chase.values.iter().map(|(cell, value)| (*cell, (*value as f64 * factor).round() as i64)).collect();
+        DijkstraMap::from_seeds(nav_grid, seeds, chase.max_cost)
+    }
+
+    fn from_seeds(nav_grid: &NavGrid, seeds: Vec<(BlockCoords, i64)>, max_cost: i64) -> DijkstraMap {
+        let voxel_volume = nav_grid.get_voxel_volume();
+        let mut values: HashMap<BlockCoords, i64> = HashMap::new();
+        let mut open = BinaryHeap::new();
+        for (cell, value) in seeds {
+            let index = match voxel_volume.get_index(&cell) {
+                Some(index) => index,
+                None => continue,
+            };
+            if values.get(&cell).map_or(true, |known| value < *known) {
+                values.insert(cell, value);
+                open.push(OpenNode {
+                    priority: value,
+                    index: index,
+                });
+            }
+        }
+        while let Some(node) = open.pop() {
+            let cell = voxel_volume.get_coords(node.index);
+            if node.priority > values[&cell] {
+                continue;
+            }
+            // Walk the steps backwards since falls only go one way
+            for (from, cost) in nav_grid.get_reverse_steps(&cell) {
+                let value = node.priority + cost as i64;
+                if value > max_cost {
+                    continue;
+                }
+                if values.get(&from).map_or(true, |known| value < *known) {
+                    let index = match voxel_volume.get_index(&from) {
+                        Some(index) => index,
+                        None => continue,
+                    };
+                    values.insert(from, value);
+                    open.push(OpenNode {
+                        priority: value,
+                        index: index,
+                    });
+                }
+            }
+        }
+        DijkstraMap {
+            values: values,
+            max_cost: max_cost,
+        }
+    }
+
+    pub fn get(&self, block_coords: &BlockCoords) -> Option<i64> {
+        self.values.get(block_coords).cloned()
+    }
+
+    pub fn get_values(&self) -> &HashMap<BlockCoords, i64> {
+        &self.values
+    }
+
+    pub fn get_max_cost(&self) -> i64 {
+        self.max_cost
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    // The step that lowers the value the most, None at a goal or out of the map
+    pub fn get_downhill(&self, nav_grid: &NavGrid, block_coords: &BlockCoords) -> Option<BlockCoords> {
+        let mut best = match self.get(block_coords) {
+            Some(value) => value,
+            None => return None,
+        };
+        let mut downhill = None;
+        for (next, _) in nav_grid.get_steps(block_coords) {
+            match self.get(&next) {
+                Some(value) if value < best => {
+                    best = value;
+                    downhill = Some(next);
+                },
+                _ => (),
+            }
+        }
+        downhill
+    }
+}
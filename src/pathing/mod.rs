@@ -0,0 +1,7 @@
+pub mod nav_grid;
+pub mod astar;
+pub mod dijkstra;
+
+pub use self::nav_grid::{NavGrid, MAX_FALL};
+pub use self::astar::{OpenNode, find_path};
+pub use self::dijkstra::{DijkstraMap, FLEE_FACTOR};
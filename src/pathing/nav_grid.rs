@@ -0,0 +1,128 @@
+use core::{BlockCoordSize, Direction};
+use components::{BlockCoords, BlockRegistry, BlockType, VoxelVolume};
+
+pub static MAX_FALL: BlockCoordSize = 8;
+// Extra cost of climbing onto the next block
+static CLIMB_COST: u32 = 1;
+
+static DIRECTIONS: [Direction; 4] = [Direction::North, Direction::East, Direction::South, Direction::West];
+
+// Where a one block tall walker can stand and move within a volume. It steps to the four
+// neighbouring columns, climbing one block up or dropping any height up to max_fall, and never
+// enters solid blocks apart from the ones made passable, like doors for those that open them.
+// Doors that stay shut can't be climbed over either
+pub struct NavGrid<'a> {
+    voxel_volume: &'a VoxelVolume,
+    block_registry: &'a BlockRegistry,
+    passable: Vec<BlockType>,
+    max_fall: BlockCoordSize,
+}
+
+impl<'a> NavGrid<'a> {
+    pub fn new(voxel_volume: &'a VoxelVolume, block_registry: &'a BlockRegistry) -> NavGrid<'a> {
+        NavGrid {
+            voxel_volume: voxel_volume,
+            block_registry: block_registry,
+            passable: vec!(),
+            max_fall: MAX_FALL,
+        }
+    }
+
+    pub fn with_passable(mut self, block_type: BlockType) -> NavGrid<'a> {
+        if !self.passable.contains(&block_type) {
+            self.passable.push(block_type);
+        }
+        self
+    }
+
+    pub fn with_max_fall(mut self, max_fall: BlockCoordSize) -> NavGrid<'a> {
+        self.max_fall = max_fall.max(0);
+        self
+    }
+
+    pub fn get_voxel_volume(&self) -> &VoxelVolume {
+        self.voxel_volume
+    }
+
+    pub fn get_max_fall(&self) -> BlockCoordSize {
+        self.max_fall
+    }
+
+    pub fn is_solid(&self, block_coords: &BlockCoords) -> bool {
+        let block_type = self.voxel_volume.get(block_coords);
+        self.block_registry.is_solid(block_type) && !self.passable.contains(&block_type)
+    }
+
+    // Inside the volume and not solid
+    pub fn is_open(&self, block_coords: &BlockCoords) -> bool {
+        self.voxel_volume.contains(block_coords) && !self.is_solid(block_coords)
+    }
+
+    // Open with ground below, or inside something like water that holds the walker up. A shut
+    // door fills its whole doorway, so its top is no ground to climb onto and drop past it
+    pub fn can_stand(&self, block_coords: &BlockCoords) -> bool {
+        let below = block_coords.offset(0, -1, 0);
+        let on_ground = self.is_solid(&below) && self.voxel_volume.get(&below) != BlockType::DOOR;
+        self.is_open(block_coords) && (on_ground || self.voxel_volume.get(block_coords) != BlockType::AIR)
+    }
+
+    pub fn get_cost(&self, block_coords: &BlockCoords) -> u32 {
+        self.block_registry.get_walk_cost(self.voxel_volume.get(block_coords)).max(1)
+    }
+
+    // Where a walker stepping into an open cell comes to rest, if it lands within max_fall
+    fn land(&self, block_coords: &BlockCoords) -> Option<BlockCoords> {
+        let mut cell = *block_coords;
+        for _ in 0..self.max_fall + 1 {
+            if self.can_stand(&cell) {
+                return Some(cell);
+            }
+            cell = cell.offset(0, -1, 0);
+            if !self.is_open(&cell) {
+                return None;
+            }
+        }
+        None
+    }
+
+    // Where one step in direction from a standing cell ends up and the cost of the move, None
+    // when the way is blocked or the drop is too far
+    pub fn get_step(&self, block_coords: &BlockCoords, direction: Direction) -> Option<(BlockCoords, u32)> {
+        let beside = direction.step(block_coords);
+        if self.is_open(&beside) {
+            self.land(&beside).map(|landing| (landing, self.get_cost(&landing)))
+        } else {
+            let above = beside.offset(0, 1, 0);
+            if self.can_stand(&above) && self.is_open(&block_coords.offset(0, 1, 0)) {
+                Some((above, self.get_cost(&above) + CLIMB_COST))
+            } else {
+                None
+            }
+        }
+    }
+
+    // Every cell reachable in one step from a standing cell with the cost of the move
+    pub fn get_steps(&self, block_coords: &BlockCoords) -> Vec<(BlockCoords, u32)> {
+        DIRECTIONS.iter().filter_map(|direction| self.get_step(block_coords, *direction)).collect()
+    }
+
+    // Every standing cell with a step into block_coords, the reverse of get_steps
+    pub fn get_reverse_steps(&self, block_coords: &BlockCoords) -> Vec<(BlockCoords, u32)> {
+        let mut steps = vec!();
+        for direction in DIRECTIONS.iter() {
+            let (x, _, z) = direction.get_offset();
+            for dy in -1..self.max_fall + 1 {
+                let from = block_coords.offset(-x, dy, -z);
+                if !self.can_stand(&from) {
+                    continue;
+                }
+                for (to, cost) in self.get_steps(&from) {
+                    if to == *block_coords {
+                        steps.push((from, cost));
+                    }
+                }
+            }
+        }
+        steps
+    }
+}
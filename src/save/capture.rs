@@ -0,0 +1,131 @@
+use dorp::{Id};
+
+use core::{RogueWorld, BLOCK_REGISTRY_NAME, VOXEL_VOLUME_NAME, SCHEDULER_NAME, ITEM_MAP_NAME};
+use components::{FieldOfView};
+use save::{SaveFile, SavedLevel, SavedActor, SavedSight, SavedAi, SavedInventory, SavedItem, SaveErr};
+
+// Captures the level, every scheduled actor with what it carries and the items on the floor.
+// Actors that have died since the scheduler last looked are left out
+pub fn save_world(world: &RogueWorld) -> Result<SaveFile, SaveErr> {
+    let block_registry = match world.get_entity_by_name(BLOCK_REGISTRY_NAME) {
+        Some(entity) => match entity.get_block_registry() {
+            Some(block_registry) => block_registry,
+            None => return Err(SaveErr::Get("Block Registry Entity Get Block Registry")),
+        },
+        None => return Err(SaveErr::Get("World Get Entity By Name Block Registry Name")),
+    };
+    let voxel_volume = match world.get_entity_by_name(VOXEL_VOLUME_NAME) {
+        Some(entity) => match entity.get_voxel_volume() {
+            Some(voxel_volume) => voxel_volume,
+            None => return Err(SaveErr::Get("Voxel Volume Entity Get Voxel Volume")),
+        },
+        None => return Err(SaveErr::Get("World Get Entity By Name Voxel Volume Name")),
+    };
+    let scheduler = match world.get_entity_by_name(SCHEDULER_NAME) {
+        Some(entity) => match entity.get_scheduler() {
+            Some(scheduler) => scheduler,
+            None => return Err(SaveErr::Get("Scheduler Entity Get Scheduler")),
+        },
+        None => return Err(SaveErr::Get("World Get Entity By Name Scheduler Name")),
+    };
+
+    let palette = block_registry.get_block_types().into_iter()
+        .map(|block_type| block_registry.get(block_type).map_or(String::new(), |block_def| block_def.get_name().to_string()))
+        .collect();
+    let blocks = voxel_volume.get_blocks().iter().map(|block_type| block_type.get_id()).collect();
+    let level = SavedLevel::new(
+        voxel_volume.get_origin(),
+        (voxel_volume.get_width(), voxel_volume.get_height(), voxel_volume.get_depth()),
+        palette,
+        blocks
+    );
+
+    let mut saved_ids: Vec<Id> = vec!();
+    let mut actors = vec!();
+    for id in scheduler.get_actors().iter() {
+        match save_actor(*id, world) {
+            Ok(Some(actor)) => {
+                saved_ids.push(*id);
+                actors.push(actor);
+            },
+            Ok(None) => (),
+            Err(err) => return Err(err),
+        }
+    }
+    let ready = scheduler.get_ready().iter()
+        .filter_map(|id| saved_ids.iter().position(|saved| saved == id))
+        .collect();
+
+    let mut items = vec!();
+    match world.get_entity_by_name(ITEM_MAP_NAME) {
+        Some(entity) => match entity.get_item_map() {
+            Some(item_map) => for block_coords in item_map.get_cells() {
+                for id in item_map.get_at(&block_coords) {
+                    match world.get_entity_by_id(id).and_then(|entity| entity.get_item()) {
+                        Some(item) => items.push(SavedItem::new(item.get_name(), block_coords)),
+                        None => return Err(SaveErr::Get("Item Entity Get Item")),
+                    }
+                }
+            },
+            None => return Err(SaveErr::Get("Item Map Entity Get Item Map")),
+        },
+        None => (),
+    }
+
+    Ok(SaveFile::new(scheduler.get_turn(), ready, level, actors, items))
+}
+
+// None when the actor is gone or isn't the player or a monster
+fn save_actor(id: Id, world: &RogueWorld) -> Result<Option<SavedActor>, SaveErr> {
+    let entity = match world.get_entity_by_id(id) {
+        Some(entity) => entity,
+        None => return Ok(None),
+    };
+    let player = entity.get_player();
+    let ai = entity.get_ai();
+    if player.is_none() && ai.is_none() {
+        return Ok(None);
+    }
+    let block_coords = match entity.get_block_coords() {
+        Some(block_coords) => *block_coords,
+        None => return Err(SaveErr::Get("Actor Entity Get Block Coords")),
+    };
+    let energy = match entity.get_actor() {
+        Some(actor) => actor.get_energy(),
+        None => return Err(SaveErr::Get("Actor Entity Get Actor")),
+    };
+    let combat = match entity.get_combat() {
+        Some(combat) => combat,
+        None => return Err(SaveErr::Get("Actor Entity Get Combat")),
+    };
+    let sight = match entity.get_field_of_view() {
+        Some(field_of_view) => save_sight(&field_of_view),
+        None => SavedSight::new(None, vec!(), vec!()),
+    };
+
+    let mut actor = SavedActor::new(block_coords, energy, combat.get_health(), combat.get_rng().get_state(), sight);
+    if let Some(player) = player {
+        actor = actor.with_facing(player.get_facing());
+    }
+    if let Some(ai) = ai {
+        actor = actor.with_ai(SavedAi::new(ai.get_monster_def().get_name(), ai.get_state(), ai.get_home(), ai.get_facing(), ai.get_rng().get_state())
+            .with_last_seen(ai.get_last_seen(), ai.get_forget_in())
+            .with_path(ai.get_path().clone(), ai.get_path_goal()));
+    }
+    if let Some(inventory) = entity.get_inventory() {
+        let items = inventory.get_items().iter().map(|&(_, ref item)| item.get_name().to_string()).collect();
+        let equipped = inventory.get_equipped().iter()
+            .filter_map(|&(_, id)| inventory.get_items().iter().position(|&(other, _)| other == id))
+            .collect();
+        actor = actor.with_inventory(SavedInventory::new(items, equipped));
+    }
+    Ok(Some(actor))
+}
+
+fn save_sight(field_of_view: &FieldOfView) -> SavedSight {
+    SavedSight::new(
+        field_of_view.get_origin(),
+        field_of_view.get_visible().iter().cloned().collect(),
+        field_of_view.get_remembered().iter().cloned().collect()
+    )
+}
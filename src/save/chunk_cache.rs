@@ -0,0 +1,74 @@
+use std::io;
+use std::fmt;
+
+use components::{BlockCoords, BlockRegistry, VoxelVolume, ChunkCoords};
+use generation::{LevelKind};
+use save::{ChunkFile, ChunkFileErr};
+
+// How load_level_chunks came by the level's blocks
+#[derive(Debug)]
+pub enum ChunkLoad {
+    // There was no file yet, so the level was generated and written out
+    Generated,
+    // Read from the file, with the chunks that had to be made again from the seed
+    Loaded(Vec<ChunkCoords>),
+    // The file was for another seed, kind of level or level size and has been replaced
+    Stale,
+    // The file couldn't be read at all and has been replaced
+    Rebuilt(ChunkFileErr),
+}
+
+impl fmt::Display for ChunkLoad {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ChunkLoad::Generated => write!(f, "generated"),
+            ChunkLoad::Loaded(ref regenerated) => write!(f, "loaded, {} regenerated", regenerated.len()),
+            ChunkLoad::Stale => write!(f, "stale, generated"),
+            ChunkLoad::Rebuilt(ref err) => write!(f, "unreadable ({}), generated", err),
+        }
+    }
+}
+
+// Keeps a generated level's blocks in a chunk file at path so later runs with the same seed and
+// level settings skip generation. Anything wrong with the file costs a regeneration, never the
+// run, and the file is rewritten whenever what's on disk didn't match what came back
+pub fn load_level_chunks(path: &str, level_kind: &LevelKind, seed: u64, block_registry: &BlockRegistry) -> Result<(VoxelVolume, BlockCoords, ChunkLoad), ChunkFileErr> {
+    let loaded = match ChunkFile::load(path) {
+        Ok(chunk_file) => {
+            let size = (level_kind.get_width(), level_kind.get_height(), level_kind.get_depth());
+            let same_level = chunk_file.get_seed() == seed && chunk_file.get_fingerprint() == level_kind.get_fingerprint();
+            if !same_level || chunk_file.get_origin() != level_kind.get_origin() || chunk_file.get_size() != size {
+                Err(ChunkLoad::Stale)
+            } else {
+                match chunk_file.to_volume(block_registry, |chunk_coords| level_kind.generate_chunk(seed, chunk_coords)) {
+                    Ok((voxel_volume, regenerated)) => Ok((voxel_volume, regenerated)),
+                    Err(err) => Err(ChunkLoad::Rebuilt(err)),
+                }
+            }
+        },
+        Err(ChunkFileErr::Io(_, ref err)) if err.kind() == io::ErrorKind::NotFound => Err(ChunkLoad::Generated),
+        Err(err) => Err(ChunkLoad::Rebuilt(err)),
+    };
+
+    let (voxel_volume, spawn, chunk_load) = match loaded {
+        Ok((voxel_volume, regenerated)) => {
+            let spawn = level_kind.find_spawn(seed);
+            // The file is already right, nothing to write back
+            if regenerated.is_empty() {
+                return Ok((voxel_volume, spawn, ChunkLoad::Loaded(regenerated)));
+            }
+            (voxel_volume, spawn, ChunkLoad::Loaded(regenerated))
+        },
+        Err(chunk_load) => {
+            let (voxel_volume, spawn) = level_kind.build_volume(seed);
+            (voxel_volume, spawn, chunk_load)
+        },
+    };
+    match ChunkFile::from_volume(&voxel_volume, block_registry, seed) {
+        Ok(chunk_file) => match chunk_file.with_fingerprint(level_kind.get_fingerprint()).write(path) {
+            Ok(()) => Ok((voxel_volume, spawn, chunk_load)),
+            Err(err) => Err(err),
+        },
+        Err(err) => Err(err),
+    }
+}
@@ -0,0 +1,471 @@
+use std::collections::{HashMap};
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::error::Error;
+use std::fmt;
+
+use core::{BlockCoordSize};
+use components::{BlockCoords, BlockType, BlockRegistry, VoxelVolume, ChunkCoords, CHUNK_VOLUME};
+
+pub static CHUNK_FILE_MAGIC: &'static [u8; 4] = b"RCHK";
+// Bumped whenever the layout changes, older files are refused rather than misread
+pub static CHUNK_FILE_VERSION: u16 = 2;
+
+// Block data for a whole level, little endian:
+//   magic, version, seed, level fingerprint as u32, origin as 3 i64, size as 3 u32
+//   palette: a u16 count, then each block name as a u8 length and its bytes
+//   chunk table: a u32 count, then per chunk its coords as 3 i64, payload length and checksum as u32
+//   a checksum over everything above
+//   chunk payloads in table order, each a list of runs of a u16 palette index and a u16 count
+// A bad header makes the whole file unreadable. A chunk whose payload fails its checksum or
+// doesn't decode to exactly one chunk of blocks is only marked corrupt, so the rest still loads
+// and the seed the level was generated from can make that chunk again
+pub struct ChunkFile {
+    seed: u64,
+    // Tells apart levels of another kind or made with other settings from the same seed
+    fingerprint: u32,
+    origin: BlockCoords,
+    size: (usize, usize, usize),
+    palette: Vec<String>,
+    chunks: Vec<SavedChunk>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SavedChunk {
+    chunk_coords: ChunkCoords,
+    // Palette indices in the order Chunk uses, None when the chunk came back corrupt
+    blocks: Option<Vec<u16>>,
+}
+
+impl ChunkFile {
+    pub fn from_volume(voxel_volume: &VoxelVolume, block_registry: &BlockRegistry, seed: u64) -> Result<ChunkFile, ChunkFileErr> {
+        let mut palette = vec!();
+        let mut indices: HashMap<BlockType, u16> = HashMap::new();
+        let mut chunks = vec!();
+        for chunk_coords in voxel_volume.get_chunks() {
+            let mut blocks = Vec::with_capacity(CHUNK_VOLUME);
+            for block_type in voxel_volume.get_chunk(&chunk_coords) {
+                if !indices.contains_key(&block_type) {
+                    match block_registry.get(block_type) {
+                        Some(block_def) => palette.push(block_def.get_name().to_string()),
+                        None => return Err(ChunkFileErr::UnknownBlockType(block_type.get_id())),
+                    }
+                    indices.insert(block_type, palette.len() as u16 - 1);
+                }
+                blocks.push(indices[&block_type]);
+            }
+            chunks.push(SavedChunk::new(chunk_coords, blocks));
+        }
+        Ok(ChunkFile {
+            seed: seed,
+            fingerprint: 0,
+            origin: voxel_volume.get_origin(),
+            size: (voxel_volume.get_width(), voxel_volume.get_height(), voxel_volume.get_depth()),
+            palette: palette,
+            chunks: chunks,
+        })
+    }
+
+    pub fn with_fingerprint(mut self, fingerprint: u32) -> ChunkFile {
+        self.fingerprint = fingerprint;
+        self
+    }
+
+    pub fn load(path: &str) -> Result<ChunkFile, ChunkFileErr> {
+        let mut bytes = vec!();
+        match File::open(path) {
+            Ok(mut file) => match file.read_to_end(&mut bytes) {
+                Ok(_) => (),
+                Err(err) => return Err(ChunkFileErr::Io(path.to_string(), err)),
+            },
+            Err(err) => return Err(ChunkFileErr::Io(path.to_string(), err)),
+        }
+        ChunkFile::from_bytes(&bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<ChunkFile, ChunkFileErr> {
+        let mut reader = ByteReader::new(bytes);
+        match reader.read_bytes(CHUNK_FILE_MAGIC.len()) {
+            Some(magic) if magic == &CHUNK_FILE_MAGIC[..] => (),
+            _ => return Err(ChunkFileErr::Magic),
+        }
+        let version = match reader.read_u16() {
+            Some(version) => version,
+            None => return Err(ChunkFileErr::Truncated("Version")),
+        };
+        if version != CHUNK_FILE_VERSION {
+            return Err(ChunkFileErr::Version(version));
+        }
+        let seed = match reader.read_u64() {
+            Some(seed) => seed,
+            None => return Err(ChunkFileErr::Truncated("Seed")),
+        };
+        let fingerprint = match reader.read_u32() {
+            Some(fingerprint) => fingerprint,
+            None => return Err(ChunkFileErr::Truncated("Fingerprint")),
+        };
+        let origin = match (reader.read_i64(), reader.read_i64(), reader.read_i64()) {
+            (Some(x), Some(y), Some(z)) => BlockCoords::new(x, y, z),
+            _ => return Err(ChunkFileErr::Truncated("Origin")),
+        };
+        let size = match (reader.read_u32(), reader.read_u32(), reader.read_u32()) {
+            (Some(width), Some(height), Some(depth)) => (width as usize, height as usize, depth as usize),
+            _ => return Err(ChunkFileErr::Truncated("Size")),
+        };
+
+        let palette_len = match reader.read_u16() {
+            Some(palette_len) => palette_len,
+            None => return Err(ChunkFileErr::Truncated("Palette")),
+        };
+        let mut palette = vec!();
+        for _ in 0..palette_len {
+            let name = match reader.read_u8() {
+                Some(len) => reader.read_bytes(len as usize).map(|name| String::from_utf8_lossy(name).into_owned()),
+                None => None,
+            };
+            match name {
+                Some(name) => palette.push(name),
+                None => return Err(ChunkFileErr::Truncated("Palette")),
+            }
+        }
+
+        let chunk_count = match reader.read_u32() {
+            Some(chunk_count) => chunk_count,
+            None => return Err(ChunkFileErr::Truncated("Chunk Table")),
+        };
+        let mut table = vec!();
+        for _ in 0..chunk_count {
+            match (reader.read_i64(), reader.read_i64(), reader.read_i64(), reader.read_u32(), reader.read_u32()) {
+                (Some(x), Some(y), Some(z), Some(len), Some(checksum)) => table.push((ChunkCoords::new(x, y, z), len as usize, checksum)),
+                _ => return Err(ChunkFileErr::Truncated("Chunk Table")),
+            }
+        }
+        let header_len = reader.get_position();
+        match reader.read_u32() {
+            Some(header_checksum) => if header_checksum != checksum(&bytes[..header_len]) {
+                return Err(ChunkFileErr::Checksum);
+            },
+            None => return Err(ChunkFileErr::Truncated("Header Checksum")),
+        }
+
+        // A payload cut short by the end of the file is as corrupt as one that fails its checksum
+        let mut chunks = vec!();
+        for (chunk_coords, len, chunk_checksum) in table {
+            let blocks = match reader.read_bytes(len) {
+                Some(payload) if checksum(payload) == chunk_checksum => decode_runs(payload, palette.len()),
+                _ => None,
+            };
+            chunks.push(SavedChunk {
+                chunk_coords: chunk_coords,
+                blocks: blocks,
+            });
+        }
+
+        Ok(ChunkFile {
+            seed: seed,
+            fingerprint: fingerprint,
+            origin: origin,
+            size: size,
+            palette: palette,
+            chunks: chunks,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec!();
+        bytes.extend_from_slice(&CHUNK_FILE_MAGIC[..]);
+        push_u16(&mut bytes, CHUNK_FILE_VERSION);
+        push_u64(&mut bytes, self.seed);
+        push_u32(&mut bytes, self.fingerprint);
+        push_i64(&mut bytes, self.origin.get_x());
+        push_i64(&mut bytes, self.origin.get_y());
+        push_i64(&mut bytes, self.origin.get_z());
+        push_u32(&mut bytes, self.size.0 as u32);
+        push_u32(&mut bytes, self.size.1 as u32);
+        push_u32(&mut bytes, self.size.2 as u32);
+        push_u16(&mut bytes, self.palette.len() as u16);
+        for name in self.palette.iter() {
+            bytes.push(name.len() as u8);
+            bytes.extend_from_slice(name.as_bytes());
+        }
+
+        // Corrupt chunks have nothing left to write and are left out, the reader makes them again
+        let payloads: Vec<(ChunkCoords, Vec<u8>)> = self.chunks.iter()
+            .filter_map(|chunk| chunk.blocks.as_ref().map(|blocks| (chunk.chunk_coords, encode_runs(blocks))))
+            .collect();
+        push_u32(&mut bytes, payloads.len() as u32);
+        for &(chunk_coords, ref payload) in payloads.iter() {
+            push_i64(&mut bytes, chunk_coords.get_x());
+            push_i64(&mut bytes, chunk_coords.get_y());
+            push_i64(&mut bytes, chunk_coords.get_z());
+            push_u32(&mut bytes, payload.len() as u32);
+            push_u32(&mut bytes, checksum(payload));
+        }
+        let header_checksum = checksum(&bytes);
+        push_u32(&mut bytes, header_checksum);
+        for &(_, ref payload) in payloads.iter() {
+            bytes.extend_from_slice(payload);
+        }
+        bytes
+    }
+
+    // Writes next to path first and then moves it over, so a failed write can't eat the last file
+    pub fn write(&self, path: &str) -> Result<(), ChunkFileErr> {
+        let temp_path = format!("{}.tmp", path);
+        match File::create(&temp_path) {
+            Ok(mut file) => match file.write_all(&self.to_bytes()) {
+                Ok(()) => (),
+                Err(err) => return Err(ChunkFileErr::Io(temp_path, err)),
+            },
+            Err(err) => return Err(ChunkFileErr::Io(temp_path, err)),
+        }
+        match fs::rename(&temp_path, path) {
+            Ok(()) => Ok(()),
+            Err(err) => Err(ChunkFileErr::Io(path.to_string(), err)),
+        }
+    }
+
+    // Builds the volume back, asking regenerate for every chunk that came back corrupt or is
+    // missing from the file. Returns the chunks that had to be regenerated alongside it
+    pub fn to_volume<F>(&self, block_registry: &BlockRegistry, regenerate: F) -> Result<(VoxelVolume, Vec<ChunkCoords>), ChunkFileErr>
+        where F: Fn(&ChunkCoords) -> Vec<BlockType>
+    {
+        let mut block_types = vec!();
+        for name in self.palette.iter() {
+            match block_registry.get_by_name(name) {
+                Some(block_type) => block_types.push(block_type),
+                None => return Err(ChunkFileErr::UnknownBlock(name.clone())),
+            }
+        }
+        let (width, height, depth) = self.size;
+        let mut voxel_volume = VoxelVolume::new(self.origin, width, height, depth);
+        let mut regenerated = vec!();
+        for chunk_coords in voxel_volume.get_chunks() {
+            let blocks = match self.chunks.iter().find(|chunk| chunk.chunk_coords == chunk_coords).and_then(|chunk| chunk.blocks.as_ref()) {
+                Some(blocks) => blocks.iter().map(|index| block_types[*index as usize]).collect(),
+                None => {
+                    regenerated.push(chunk_coords);
+                    regenerate(&chunk_coords)
+                },
+            };
+            voxel_volume.set_chunk(&chunk_coords, &blocks);
+        }
+        Ok((voxel_volume, regenerated))
+    }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn get_fingerprint(&self) -> u32 {
+        self.fingerprint
+    }
+
+    pub fn get_origin(&self) -> BlockCoords {
+        self.origin
+    }
+
+    // Width, height and depth
+    pub fn get_size(&self) -> (usize, usize, usize) {
+        self.size
+    }
+
+    pub fn get_palette(&self) -> &Vec<String> {
+        &self.palette
+    }
+
+    pub fn get_chunks(&self) -> &Vec<SavedChunk> {
+        &self.chunks
+    }
+
+    pub fn get_corrupt(&self) -> Vec<ChunkCoords> {
+        self.chunks.iter().filter(|chunk| chunk.blocks.is_none()).map(|chunk| chunk.chunk_coords).collect()
+    }
+}
+
+impl SavedChunk {
+    pub fn new(chunk_coords: ChunkCoords, blocks: Vec<u16>) -> SavedChunk {
+        SavedChunk {
+            chunk_coords: chunk_coords,
+            blocks: Some(blocks),
+        }
+    }
+
+    pub fn get_chunk_coords(&self) -> ChunkCoords {
+        self.chunk_coords
+    }
+
+    pub fn get_blocks(&self) -> Option<&Vec<u16>> {
+        self.blocks.as_ref()
+    }
+
+    pub fn is_corrupt(&self) -> bool {
+        self.blocks.is_none()
+    }
+}
+
+// CRC-32 as used by zip and png
+pub fn checksum(bytes: &[u8]) -> u32 {
+    let mut crc = 0xffffffffu32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb88320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn encode_runs(blocks: &[u16]) -> Vec<u8> {
+    let mut bytes = vec!();
+    let mut index = 0;
+    while index < blocks.len() {
+        let block = blocks[index];
+        let mut count = 1;
+        while index + count < blocks.len() && blocks[index + count] == block && count < u16::max_value() as usize {
+            count += 1;
+        }
+        push_u16(&mut bytes, block);
+        push_u16(&mut bytes, count as u16);
+        index += count;
+    }
+    bytes
+}
+
+// None unless the runs cover exactly one chunk with indices inside the palette
+fn decode_runs(bytes: &[u8], palette_len: usize) -> Option<Vec<u16>> {
+    let mut reader = ByteReader::new(bytes);
+    let mut blocks = Vec::with_capacity(CHUNK_VOLUME);
+    while reader.get_position() < bytes.len() {
+        match (reader.read_u16(), reader.read_u16()) {
+            (Some(block), Some(count)) if (block as usize) < palette_len && count > 0 && blocks.len() + count as usize <= CHUNK_VOLUME => {
+                for _ in 0..count {
+                    blocks.push(block);
+                }
+            },
+            _ => return None,
+        }
+    }
+    if blocks.len() == CHUNK_VOLUME {
+        Some(blocks)
+    } else {
+        None
+    }
+}
+
+fn push_u16(bytes: &mut Vec<u8>, value: u16) {
+    for shift in 0..2 {
+        bytes.push((value >> (shift * 8)) as u8);
+    }
+}
+
+fn push_u32(bytes: &mut Vec<u8>, value: u32) {
+    for shift in 0..4 {
+        bytes.push((value >> (shift * 8)) as u8);
+    }
+}
+
+fn push_u64(bytes: &mut Vec<u8>, value: u64) {
+    for shift in 0..8 {
+        bytes.push((value >> (shift * 8)) as u8);
+    }
+}
+
+fn push_i64(bytes: &mut Vec<u8>, value: BlockCoordSize) {
+    push_u64(bytes, value as u64);
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(bytes: &'a [u8]) -> ByteReader<'a> {
+        ByteReader {
+            bytes: bytes,
+            position: 0,
+        }
+    }
+
+    fn get_position(&self) -> usize {
+        self.position
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        if len > self.bytes.len() - self.position {
+            return None;
+        }
+        let bytes = &self.bytes[self.position..self.position + len];
+        self.position += len;
+        Some(bytes)
+    }
+
+    // Little endian, len bytes wide
+    fn read_uint(&mut self, len: usize) -> Option<u64> {
+        self.read_bytes(len).map(|bytes| bytes.iter().rev().fold(0, |value, byte| (value << 8) | *byte as u64))
+    }
+
+    fn read_u8(&mut self) -> Option<u8> {
+        self.read_uint(1).map(|value| value as u8)
+    }
+
+    fn read_u16(&mut self) -> Option<u16> {
+        self.read_uint(2).map(|value| value as u16)
+    }
+
+    fn read_u32(&mut self) -> Option<u32> {
+        self.read_uint(4).map(|value| value as u32)
+    }
+
+    fn read_u64(&mut self) -> Option<u64> {
+        self.read_uint(8)
+    }
+
+    fn read_i64(&mut self) -> Option<BlockCoordSize> {
+        self.read_uint(8).map(|value| value as BlockCoordSize)
+    }
+}
+
+#[derive(Debug)]
+pub enum ChunkFileErr {
+    Io(String, io::Error),
+    Magic,
+    Version(u16),
+    Truncated(&'static str),
+    Checksum,
+    UnknownBlockType(u16),
+    UnknownBlock(String),
+}
+
+impl fmt::Display for ChunkFileErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ChunkFileErr::Io(ref path, ref err) => write!(f, "{}: {}", path, err),
+            ChunkFileErr::Magic => write!(f, "Not a chunk file"),
+            ChunkFileErr::Version(version) => write!(f, "Chunk file is version {}, expected {}", version, CHUNK_FILE_VERSION),
+            ChunkFileErr::Truncated(part) => write!(f, "Chunk file ends inside its {}", part),
+            ChunkFileErr::Checksum => write!(f, "Chunk file header failed its checksum"),
+            ChunkFileErr::UnknownBlockType(id) => write!(f, "Block type {} isn't in the block registry", id),
+            ChunkFileErr::UnknownBlock(ref name) => write!(f, "Unknown block '{}'", name),
+        }
+    }
+}
+
+impl Error for ChunkFileErr {
+    fn description(&self) -> &str {
+        match *self {
+            ChunkFileErr::Io(_, ref err) => err.description(),
+            ChunkFileErr::Magic => "Not a chunk file",
+            ChunkFileErr::Version(_) => "Unsupported chunk file version",
+            ChunkFileErr::Truncated(_) => "Chunk file truncated",
+            ChunkFileErr::Checksum => "Chunk file header failed its checksum",
+            ChunkFileErr::UnknownBlockType(_) => "Unknown block type",
+            ChunkFileErr::UnknownBlock(_) => "Unknown block",
+        }
+    }
+}
@@ -0,0 +1,13 @@
+pub mod save_file;
+pub mod capture;
+pub mod restore;
+pub mod chunk_file;
+pub mod chunk_cache;
+
+pub use self::save_file::{
+    SaveFile, SavedLevel, SavedActor, SavedSight, SavedAi, SavedInventory, SavedItem, SaveErr, SAVE_PATH, SAVE_VERSION
+};
+pub use self::capture::{save_world};
+pub use self::restore::{RestoreErr, new_saved_scene, restore_world, restore_volume};
+pub use self::chunk_file::{ChunkFile, SavedChunk, ChunkFileErr, CHUNK_FILE_MAGIC, CHUNK_FILE_VERSION, checksum};
+pub use self::chunk_cache::{ChunkLoad, load_level_chunks};
@@ -0,0 +1,316 @@
+use std::collections::{HashSet};
+use std::error::Error;
+use std::fmt;
+
+use dorp::{IdManager, Id, IdType, OptErr, Scene, WorldErr};
+
+use core::{
+    RogueData, RogueDataErr, RogueWorld, BlockCoordSize, Scheduler, Player, Ai, Rng, SceneErr, SCHEDULER_NAME, BEING_SPRITE, build_level,
+    get_volume_scale, new_being_sprite, new_item_sprite
+};
+use components::{
+    BlockCoords, BlockType, BlockRegistry, MonsterRegistry, ItemRegistry, Item, FieldOfView, InventoryErr, VoxelVolume
+};
+use assets::{AssetManifest, AssetLoader};
+use entities::{
+    new_player_entity, PlayerEntityErr, new_monster_entity, MonsterEntityErr, new_scheduler_entity, SchedulerEntityErr,
+    new_item_entity, ItemEntityErr, new_item_map_entity, ItemMapEntityErr
+};
+use save::{SaveFile, SavedLevel, SavedActor, SavedSight};
+
+pub fn new_saved_scene(save_file: SaveFile, block_registry: BlockRegistry, monster_registry: MonsterRegistry, item_registry: ItemRegistry, asset_manifest: AssetManifest) -> Scene<RogueData> {
+    Scene::new(Box::new(move |manager, world| {
+        match restore_world(&save_file, &block_registry, &monster_registry, &item_registry, &asset_manifest, manager, world) {
+            Ok(_) => {
+                println!("Save Loaded");
+                Ok(())
+            },
+            Err(err) => Err(Box::new(err)),
+        }
+    }))
+}
+
+// Builds the saved level the same way a generated one is built, so the block map, block
+// entities and chunk views come back too, then puts the actors and items back where they were.
+// Names are looked up in the registries, so a save outlives changes to the data files as long
+// as nothing it uses is removed
+pub fn restore_world(save_file: &SaveFile, block_registry: &BlockRegistry, monster_registry: &MonsterRegistry, item_registry: &ItemRegistry, asset_manifest: &AssetManifest, manager: &mut IdManager, world: &mut RogueWorld) -> Result<Vec<Id>, RestoreErr> {
+    let voxel_volume = match restore_volume(save_file.get_level(), block_registry) {
+        Ok(voxel_volume) => voxel_volume,
+        Err(err) => return Err(err),
+    };
+    let scale = get_volume_scale(&voxel_volume);
+    let focus = match save_file.get_actors().iter().find(|actor| actor.get_facing().is_some()) {
+        Some(player) => player.get_block_coords(),
+        None => voxel_volume.get_origin(),
+    };
+
+    let mut asset_loader = AssetLoader::new(asset_manifest.clone());
+    let mut ids = match build_level(voxel_volume, focus, block_registry, &mut asset_loader, manager, world) {
+        Ok(ids) => ids,
+        Err(err) => return Err(RestoreErr::Scene("Build Level", err)),
+    };
+
+    match new_scheduler_entity(Scheduler::new().with_turn(save_file.get_turn()), manager, world) {
+        Ok(id) => ids.push(id),
+        Err(err) => return Err(RestoreErr::SchedulerEntity("New Scheduler Entity", err)),
+    }
+
+    match new_item_map_entity(manager, world) {
+        Ok(id) => ids.push(id),
+        Err(err) => return Err(RestoreErr::ItemMapEntity("New Item Map Entity", err)),
+    }
+
+    // Actors register with the scheduler as they are made, so saved order is turn order again
+    let mut actor_ids = vec!();
+    for saved_actor in save_file.get_actors() {
+        match restore_actor(saved_actor, monster_registry, item_registry, scale, &mut asset_loader, manager, world) {
+            Ok(restored) => {
+                actor_ids.push(restored[0]);
+                ids.extend(restored);
+            },
+            Err(err) => return Err(err),
+        }
+    }
+
+    let ready = save_file.get_ready().iter().map(|index| actor_ids[*index]).collect();
+    match world.get_mut_entity_by_name(SCHEDULER_NAME) {
+        OptErr::Full(entity) => match entity.get_mut_scheduler() {
+            OptErr::Full(scheduler) => scheduler.set_ready(ready),
+            OptErr::Empty => return Err(RestoreErr::Get("Scheduler Entity Get Mut Scheduler")),
+            OptErr::Error(err) => return Err(RestoreErr::RogueData("Scheduler Entity Get Mut Scheduler", err)),
+        },
+        OptErr::Empty => return Err(RestoreErr::Get("World Get Mut Entity By Name Scheduler Name")),
+        OptErr::Error(err) => return Err(RestoreErr::World("World Get Mut Entity By Name Scheduler Name", err)),
+    }
+
+    for saved_item in save_file.get_items() {
+        let item_def = match item_registry.get(saved_item.get_name()) {
+            Some(item_def) => item_def,
+            None => return Err(RestoreErr::UnknownItem(saved_item.get_name().to_string())),
+        };
+        let sprite = match new_item_sprite(item_def, scale, &mut asset_loader, manager) {
+            Ok(sprite) => sprite,
+            Err(err) => return Err(RestoreErr::Scene("New Item Sprite", err)),
+        };
+        match new_item_entity(item_def, saved_item.get_block_coords(), Some(sprite), manager, world) {
+            Ok(id) => ids.push(id),
+            Err(err) => return Err(RestoreErr::ItemEntity("New Item Entity", err)),
+        }
+    }
+    Ok(ids)
+}
+
+pub fn restore_volume(level: &SavedLevel, block_registry: &BlockRegistry) -> Result<VoxelVolume, RestoreErr> {
+    let mut block_types = vec!();
+    for name in level.get_palette() {
+        match block_registry.get_by_name(name) {
+            Some(block_type) => block_types.push(block_type),
+            None => return Err(RestoreErr::UnknownBlock(name.to_string())),
+        }
+    }
+    let (width, height, depth) = level.get_size();
+    let mut voxel_volume = VoxelVolume::new(level.get_origin(), width, height, depth);
+    for (index, block) in level.get_blocks().iter().enumerate() {
+        let block_type = block_types.get(*block as usize).cloned().unwrap_or(BlockType::AIR);
+        if block_type != BlockType::AIR {
+            let block_coords = voxel_volume.get_coords(index);
+            voxel_volume.set(&block_coords, block_type);
+        }
+    }
+    Ok(voxel_volume)
+}
+
+// Makes the actor the usual way and then winds its state forward to the saved one. Returns
+// the actor's id followed by the items it carries
+fn restore_actor(saved_actor: &SavedActor, monster_registry: &MonsterRegistry, item_registry: &ItemRegistry, scale: [f32; 3], asset_loader: &mut AssetLoader, manager: &mut IdManager, world: &mut RogueWorld) -> Result<Vec<Id>, RestoreErr> {
+    let block_coords = saved_actor.get_block_coords();
+    let id = match saved_actor.get_ai() {
+        Some(saved_ai) => {
+            let monster_def = match monster_registry.get(saved_ai.get_monster()) {
+                Some(monster_def) => monster_def,
+                None => return Err(RestoreErr::UnknownMonster(saved_ai.get_monster().to_string())),
+            };
+            let sprite = match new_being_sprite(monster_def.get_sprite().unwrap_or(BEING_SPRITE), scale, asset_loader, manager) {
+                Ok(sprite) => sprite,
+                Err(err) => return Err(RestoreErr::Scene("New Being Sprite", err)),
+            };
+            match new_monster_entity(monster_def, block_coords, Some(sprite), 0, manager, world) {
+                Ok(id) => id,
+                Err(err) => return Err(RestoreErr::MonsterEntity("New Monster Entity", err)),
+            }
+        },
+        None => {
+            let sprite = match new_being_sprite(BEING_SPRITE, scale, asset_loader, manager) {
+                Ok(sprite) => sprite,
+                Err(err) => return Err(RestoreErr::Scene("New Being Sprite", err)),
+            };
+            match new_player_entity(block_coords, Some(sprite), manager, world) {
+                Ok(id) => id,
+                Err(err) => return Err(RestoreErr::PlayerEntity("New Player Entity", err)),
+            }
+        },
+    };
+    let mut ids = vec!(id);
+
+    // Carried items are entities without block coords, the same as after a pick up
+    let mut carried = vec!();
+    if let Some(saved_inventory) = saved_actor.get_inventory() {
+        for name in saved_inventory.get_items() {
+            let item_def = match item_registry.get(name) {
+                Some(item_def) => item_def,
+                None => return Err(RestoreErr::UnknownItem(name.to_string())),
+            };
+            let sprite = match new_item_sprite(item_def, scale, asset_loader, manager) {
+                Ok(sprite) => sprite,
+                Err(err) => return Err(RestoreErr::Scene("New Item Sprite", err)),
+            };
+            let item_id = Id::new(manager, IdType::Entity);
+            match world.add_entity(RogueData::new(item_id)
+                .with_item(Item::new(item_def.clone()))
+                .with_sprite(sprite)
+            ) {
+                Ok(()) => (),
+                Err(err) => return Err(RestoreErr::World("World Add Entity", err)),
+            }
+            ids.push(item_id);
+            carried.push((item_id, Item::new(item_def.clone())));
+        }
+    }
+
+    let entity = match world.get_mut_entity_by_id(id) {
+        OptErr::Full(entity) => entity,
+        OptErr::Empty => return Err(RestoreErr::Get("World Get Mut Entity By Id")),
+        OptErr::Error(err) => return Err(RestoreErr::World("World Get Mut Entity By Id", err)),
+    };
+    match entity.get_mut_actor() {
+        OptErr::Full(actor) => *actor = actor.with_energy(saved_actor.get_energy()),
+        OptErr::Empty => return Err(RestoreErr::Get("Actor Entity Get Mut Actor")),
+        OptErr::Error(err) => return Err(RestoreErr::RogueData("Actor Entity Get Mut Actor", err)),
+    }
+    match entity.get_mut_field_of_view() {
+        OptErr::Full(field_of_view) => *field_of_view = restore_sight(field_of_view.get_radius(), saved_actor.get_sight()),
+        OptErr::Empty => (),
+        OptErr::Error(err) => return Err(RestoreErr::RogueData("Actor Entity Get Mut Field Of View", err)),
+    }
+    if let Some(facing) = saved_actor.get_facing() {
+        match entity.get_mut_player() {
+            OptErr::Full(player) => *player = Player::new().with_facing(facing),
+            OptErr::Empty => return Err(RestoreErr::Get("Actor Entity Get Mut Player")),
+            OptErr::Error(err) => return Err(RestoreErr::RogueData("Actor Entity Get Mut Player", err)),
+        }
+    }
+    if let Some(saved_ai) = saved_actor.get_ai() {
+        match entity.get_mut_ai() {
+            OptErr::Full(ai) => *ai = Ai::new(ai.get_monster_def().clone(), saved_ai.get_home(), 0)
+                .with_state(saved_ai.get_state())
+                .with_facing(saved_ai.get_facing())
+                .with_last_seen(saved_ai.get_last_seen(), saved_ai.get_forget_in())
+                .with_path(saved_ai.get_path().clone(), saved_ai.get_path_goal())
+                .with_rng(Rng::from_state(saved_ai.get_rng())),
+            OptErr::Empty => return Err(RestoreErr::Get("Actor Entity Get Mut Ai")),
+            OptErr::Error(err) => return Err(RestoreErr::RogueData("Actor Entity Get Mut Ai", err)),
+        }
+    }
+    let bonus = match saved_actor.get_inventory() {
+        Some(saved_inventory) => match entity.get_mut_inventory() {
+            OptErr::Full(inventory) => {
+                for (item_id, item) in carried.iter().cloned() {
+                    match inventory.add(item_id, item) {
+                        Ok(()) => (),
+                        Err(err) => return Err(RestoreErr::Inventory("Inventory Add", err)),
+                    }
+                }
+                for index in saved_inventory.get_equipped() {
+                    match inventory.equip(carried[*index].0) {
+                        Ok(_) => (),
+                        Err(err) => return Err(RestoreErr::Inventory("Inventory Equip", err)),
+                    }
+                }
+                Some(inventory.get_bonus())
+            },
+            OptErr::Empty => return Err(RestoreErr::Get("Actor Entity Get Mut Inventory")),
+            OptErr::Error(err) => return Err(RestoreErr::RogueData("Actor Entity Get Mut Inventory", err)),
+        },
+        None => None,
+    };
+    match entity.get_mut_combat() {
+        OptErr::Full(combat) => {
+            *combat = combat.clone()
+                .with_health(saved_actor.get_health())
+                .with_rng(Rng::from_state(saved_actor.get_combat_rng()));
+            if let Some(bonus) = bonus {
+                combat.set_bonus(bonus);
+            }
+        },
+        OptErr::Empty => return Err(RestoreErr::Get("Actor Entity Get Mut Combat")),
+        OptErr::Error(err) => return Err(RestoreErr::RogueData("Actor Entity Get Mut Combat", err)),
+    }
+    Ok(ids)
+}
+
+fn restore_sight(radius: BlockCoordSize, saved_sight: &SavedSight) -> FieldOfView {
+    let remembered: HashSet<BlockCoords> = saved_sight.get_remembered().iter().cloned().collect();
+    let mut field_of_view = FieldOfView::new(radius).with_remembered(remembered);
+    if let Some(origin) = saved_sight.get_origin() {
+        field_of_view.set_visible(origin, saved_sight.get_visible().iter().cloned().collect());
+    }
+    field_of_view
+}
+
+#[derive(Debug)]
+pub enum RestoreErr {
+    Scene(&'static str, SceneErr),
+    SchedulerEntity(&'static str, SchedulerEntityErr),
+    ItemMapEntity(&'static str, ItemMapEntityErr),
+    ItemEntity(&'static str, ItemEntityErr),
+    PlayerEntity(&'static str, PlayerEntityErr),
+    MonsterEntity(&'static str, MonsterEntityErr),
+    Inventory(&'static str, InventoryErr),
+    RogueData(&'static str, RogueDataErr),
+    World(&'static str, WorldErr),
+    UnknownBlock(String),
+    UnknownMonster(String),
+    UnknownItem(String),
+    Get(&'static str),
+}
+
+impl fmt::Display for RestoreErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RestoreErr::Scene(_, ref err) => err.fmt(f),
+            RestoreErr::SchedulerEntity(_, ref err) => err.fmt(f),
+            RestoreErr::ItemMapEntity(_, ref err) => err.fmt(f),
+            RestoreErr::ItemEntity(_, ref err) => err.fmt(f),
+            RestoreErr::PlayerEntity(_, ref err) => err.fmt(f),
+            RestoreErr::MonsterEntity(_, ref err) => err.fmt(f),
+            RestoreErr::Inventory(_, ref err) => err.fmt(f),
+            RestoreErr::RogueData(_, ref err) => err.fmt(f),
+            RestoreErr::World(_, ref err) => err.fmt(f),
+            RestoreErr::UnknownBlock(ref name) => write!(f, "Save uses unknown block {}", name),
+            RestoreErr::UnknownMonster(ref name) => write!(f, "Save uses unknown monster {}", name),
+            RestoreErr::UnknownItem(ref name) => write!(f, "Save uses unknown item {}", name),
+            RestoreErr::Get(_) => write!(f, "Get was None"),
+        }
+    }
+}
+
+impl Error for RestoreErr {
+    fn description(&self) -> &str {
+        match *self {
+            RestoreErr::Scene(_, ref err) => err.description(),
+            RestoreErr::SchedulerEntity(_, ref err) => err.description(),
+            RestoreErr::ItemMapEntity(_, ref err) => err.description(),
+            RestoreErr::ItemEntity(_, ref err) => err.description(),
+            RestoreErr::PlayerEntity(_, ref err) => err.description(),
+            RestoreErr::MonsterEntity(_, ref err) => err.description(),
+            RestoreErr::Inventory(_, ref err) => err.description(),
+            RestoreErr::RogueData(_, ref err) => err.description(),
+            RestoreErr::World(_, ref err) => err.description(),
+            RestoreErr::UnknownBlock(_) => "Unknown block",
+            RestoreErr::UnknownMonster(_) => "Unknown monster",
+            RestoreErr::UnknownItem(_) => "Unknown item",
+            RestoreErr::Get(_) => "Get was None",
+        }
+    }
+}
@@ -0,0 +1,756 @@
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::str::{FromStr};
+use std::error::Error;
+use std::fmt;
+
+use core::{Config, ConfigSection, ConfigEntry, ConfigErr, Direction, AiState};
+use components::{BlockCoords};
+
+pub static SAVE_PATH: &'static str = "rogue.sav";
+// Bumped whenever the layout changes, older files are refused rather than misread
+pub static SAVE_VERSION: u32 = 1;
+
+// Everything needed to put a world back the way it was. Entities are kept in a fixed order,
+// scheduler actors first with what they carry and then items on the floor, and refer to each
+// other by that order since ids are handed out afresh on load. Written in the same text format
+// as the data files: a [save] header, the [level] blocks as runs over a palette of block
+// names, then an [actor.N] and [item.N] section per entity with keys named after components
+#[derive(PartialEq)]
+pub struct SaveFile {
+    version: u32,
+    turn: u64,
+    ready: Vec<usize>,
+    level: SavedLevel,
+    actors: Vec<SavedActor>,
+    items: Vec<SavedItem>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SavedLevel {
+    origin: BlockCoords,
+    size: (usize, usize, usize),
+    palette: Vec<String>,
+    blocks: Vec<u16>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SavedActor {
+    block_coords: BlockCoords,
+    energy: i32,
+    health: i32,
+    combat_rng: u64,
+    sight: SavedSight,
+    facing: Option<Direction>,
+    ai: Option<SavedAi>,
+    inventory: Option<SavedInventory>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SavedSight {
+    origin: Option<BlockCoords>,
+    visible: Vec<BlockCoords>,
+    remembered: Vec<BlockCoords>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SavedAi {
+    monster: String,
+    state: AiState,
+    home: BlockCoords,
+    facing: Direction,
+    last_seen: Option<BlockCoords>,
+    forget_in: u32,
+    path: Vec<BlockCoords>,
+    path_goal: Option<BlockCoords>,
+    rng: u64,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SavedInventory {
+    items: Vec<String>,
+    // Positions in items, in the order they were put on
+    equipped: Vec<usize>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SavedItem {
+    name: String,
+    block_coords: BlockCoords,
+}
+
+impl SaveFile {
+    pub fn new(turn: u64, ready: Vec<usize>, level: SavedLevel, actors: Vec<SavedActor>, items: Vec<SavedItem>) -> SaveFile {
+        SaveFile {
+            version: SAVE_VERSION,
+            turn: turn,
+            ready: ready,
+            level: level,
+            actors: actors,
+            items: items,
+        }
+    }
+
+    pub fn load(path: &str) -> Result<SaveFile, SaveErr> {
+        match Config::load(path) {
+            Ok(config) => SaveFile::from_config(&config),
+            Err(err) => Err(SaveErr::Config("Config Load Path", err)),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<SaveFile, SaveErr> {
+        match Config::parse(text) {
+            Ok(config) => SaveFile::from_config(&config),
+            Err(err) => Err(SaveErr::Config("Config Parse Text", err)),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Result<SaveFile, SaveErr> {
+        let header = match config.get_section("save") {
+            Some(section) => section,
+            None => return Err(SaveErr::Missing("save")),
+        };
+        let version = match header.get_required("version") {
+            Ok(version) => version,
+            Err(err) => return Err(SaveErr::Config("Save Section Version", err)),
+        };
+        if version != SAVE_VERSION {
+            return Err(SaveErr::Version(version));
+        }
+        let turn = match header.get_required("turn") {
+            Ok(turn) => turn,
+            Err(err) => return Err(SaveErr::Config("Save Section Turn", err)),
+        };
+        let level = match config.get_section("level") {
+            Some(section) => match SavedLevel::from_section(section) {
+                Ok(level) => level,
+                Err(err) => return Err(SaveErr::Config("Saved Level From Section", err)),
+            },
+            None => return Err(SaveErr::Missing("level")),
+        };
+        let mut actors = vec!();
+        let mut items = vec!();
+        for section in config.get_sections() {
+            if section.get_name() == format!("actor.{}", actors.len()) {
+                match SavedActor::from_section(section) {
+                    Ok(actor) => actors.push(actor),
+                    Err(err) => return Err(SaveErr::Config("Saved Actor From Section", err)),
+                }
+            } else if section.get_name() == format!("item.{}", items.len()) {
+                match SavedItem::from_section(section) {
+                    Ok(item) => items.push(item),
+                    Err(err) => return Err(SaveErr::Config("Saved Item From Section", err)),
+                }
+            } else if section.get_name() != "save" && section.get_name() != "level" {
+                return Err(SaveErr::Config("Save File Sections", ConfigErr::Syntax(section.get_line(), "Unexpected section, entities must be numbered in order")));
+            }
+        }
+        let ready: Vec<usize> = match parse_list_or_empty(header, "ready") {
+            Ok(ready) => ready,
+            Err(err) => return Err(SaveErr::Config("Save Section Ready", err)),
+        };
+        for index in ready.iter() {
+            if *index >= actors.len() {
+                return Err(SaveErr::Config("Save Section Ready", ConfigErr::Value(header.get_line(), "ready".to_string(), index.to_string())));
+            }
+        }
+        Ok(SaveFile {
+            version: version,
+            turn: turn,
+            ready: ready,
+            level: level,
+            actors: actors,
+            items: items,
+        })
+    }
+
+    pub fn get_version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn get_turn(&self) -> u64 {
+        self.turn
+    }
+
+    // Positions in actors of those still to act this turn, the next one first
+    pub fn get_ready(&self) -> &Vec<usize> {
+        &self.ready
+    }
+
+    pub fn get_level(&self) -> &SavedLevel {
+        &self.level
+    }
+
+    // In scheduler order
+    pub fn get_actors(&self) -> &Vec<SavedActor> {
+        &self.actors
+    }
+
+    // Items on the floor, carried ones are saved with their actor
+    pub fn get_items(&self) -> &Vec<SavedItem> {
+        &self.items
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        text.push_str("[save]\n");
+        push_entry(&mut text, "version", &self.version);
+        push_entry(&mut text, "turn", &self.turn);
+        push_list(&mut text, "ready", &self.ready);
+        text.push_str("\n[level]\n");
+        self.level.push_entries(&mut text);
+        for (index, actor) in self.actors.iter().enumerate() {
+            text.push_str(&format!("\n[actor.{}]\n", index));
+            actor.push_entries(&mut text);
+        }
+        for (index, item) in self.items.iter().enumerate() {
+            text.push_str(&format!("\n[item.{}]\n", index));
+            push_entry(&mut text, "name", &item.name);
+            push_entry(&mut text, "coords", &item.block_coords);
+        }
+        text
+    }
+
+    // Writes next to path first and then moves it over, so a failed write can't eat the last save
+    pub fn write(&self, path: &str) -> Result<(), SaveErr> {
+        let temp_path = format!("{}.tmp", path);
+        match File::create(&temp_path) {
+            Ok(mut file) => match file.write_all(self.to_text().as_bytes()) {
+                Ok(()) => (),
+                Err(err) => return Err(SaveErr::Io(temp_path, err)),
+            },
+            Err(err) => return Err(SaveErr::Io(temp_path, err)),
+        }
+        match fs::rename(&temp_path, path) {
+            Ok(()) => Ok(()),
+            Err(err) => Err(SaveErr::Io(path.to_string(), err)),
+        }
+    }
+}
+
+impl fmt::Debug for SaveFile {
+    // The level is far too long to be useful in test failures
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SaveFile {{ version: {}, turn: {}, ready: {:?}, actors: {:?}, items: {:?} }}", self.version, self.turn, self.ready, self.actors, self.items)
+    }
+}
+
+impl SavedLevel {
+    pub fn new(origin: BlockCoords, size: (usize, usize, usize), palette: Vec<String>, blocks: Vec<u16>) -> SavedLevel {
+        SavedLevel {
+            origin: origin,
+            size: size,
+            palette: palette,
+            blocks: blocks,
+        }
+    }
+
+    fn from_section(section: &ConfigSection) -> Result<SavedLevel, ConfigErr> {
+        let origin = match section.get_required("origin") {
+            Ok(origin) => origin,
+            Err(err) => return Err(err),
+        };
+        let size = match section.get_entry("size") {
+            Some(entry) => match entry.parse_list::<usize>() {
+                Ok(ref size) if size.len() == 3 => (size[0], size[1], size[2]),
+                Ok(_) => return Err(ConfigErr::Value(entry.get_line(), entry.get_key().to_string(), entry.get_value().to_string())),
+                Err(err) => return Err(err),
+            },
+            None => return Err(ConfigErr::Missing(section.get_line(), "size".to_string())),
+        };
+        let palette: Vec<String> = match parse_list_or_empty(section, "palette") {
+            Ok(palette) => palette,
+            Err(err) => return Err(err),
+        };
+        let entry = match section.get_entry("blocks") {
+            Some(entry) => entry,
+            None => return Err(ConfigErr::Missing(section.get_line(), "blocks".to_string())),
+        };
+        let blocks = match decode_runs(entry, palette.len()) {
+            Ok(blocks) => blocks,
+            Err(err) => return Err(err),
+        };
+        if blocks.len() != size.0 * size.1 * size.2 {
+            return Err(ConfigErr::Value(entry.get_line(), "blocks".to_string(), format!("{} of {} blocks", blocks.len(), size.0 * size.1 * size.2)));
+        }
+        Ok(SavedLevel {
+            origin: origin,
+            size: size,
+            palette: palette,
+            blocks: blocks,
+        })
+    }
+
+    fn push_entries(&self, text: &mut String) {
+        push_entry(text, "origin", &self.origin);
+        text.push_str(&format!("size = {} {} {}\n", self.size.0, self.size.1, self.size.2));
+        push_list(text, "palette", &self.palette);
+        text.push_str("blocks =");
+        let mut index = 0;
+        while index < self.blocks.len() {
+            let block = self.blocks[index];
+            let mut count = 1;
+            while index + count < self.blocks.len() && self.blocks[index + count] == block {
+                count += 1;
+            }
+            if count == 1 {
+                text.push_str(&format!(" {}", block));
+            } else {
+                text.push_str(&format!(" {}*{}", block, count));
+            }
+            index += count;
+        }
+        text.push('\n');
+    }
+
+    pub fn get_origin(&self) -> BlockCoords {
+        self.origin
+    }
+
+    // Width, height and depth
+    pub fn get_size(&self) -> (usize, usize, usize) {
+        self.size
+    }
+
+    // Block names the blocks index into, so the save survives the registry changing order
+    pub fn get_palette(&self) -> &Vec<String> {
+        &self.palette
+    }
+
+    // In voxel volume order
+    pub fn get_blocks(&self) -> &Vec<u16> {
+        &self.blocks
+    }
+}
+
+impl SavedActor {
+    pub fn new(block_coords: BlockCoords, energy: i32, health: i32, combat_rng: u64, sight: SavedSight) -> SavedActor {
+        SavedActor {
+            block_coords: block_coords,
+            energy: energy,
+            health: health,
+            combat_rng: combat_rng,
+            sight: sight,
+            facing: None,
+            ai: None,
+            inventory: None,
+        }
+    }
+
+    // Only the player has a facing of its own, monsters keep theirs in the ai
+    pub fn with_facing(mut self, facing: Direction) -> SavedActor {
+        self.facing = Some(facing);
+        self
+    }
+
+    pub fn with_ai(mut self, ai: SavedAi) -> SavedActor {
+        self.ai = Some(ai);
+        self
+    }
+
+    pub fn with_inventory(mut self, inventory: SavedInventory) -> SavedActor {
+        self.inventory = Some(inventory);
+        self
+    }
+
+    fn from_section(section: &ConfigSection) -> Result<SavedActor, ConfigErr> {
+        let block_coords = match section.get_required("coords") {
+            Ok(block_coords) => block_coords,
+            Err(err) => return Err(err),
+        };
+        let energy = match section.get_required("actor.energy") {
+            Ok(energy) => energy,
+            Err(err) => return Err(err),
+        };
+        let health = match section.get_required("combat.health") {
+            Ok(health) => health,
+            Err(err) => return Err(err),
+        };
+        let combat_rng = match section.get_required("combat.rng") {
+            Ok(combat_rng) => combat_rng,
+            Err(err) => return Err(err),
+        };
+        let sight = SavedSight {
+            origin: match parse_optional(section, "sight.origin") {
+                Ok(origin) => origin,
+                Err(err) => return Err(err),
+            },
+            visible: match parse_list_or_empty(section, "sight.visible") {
+                Ok(visible) => visible,
+                Err(err) => return Err(err),
+            },
+            remembered: match parse_list_or_empty(section, "sight.remembered") {
+                Ok(remembered) => remembered,
+                Err(err) => return Err(err),
+            },
+        };
+        let facing = match parse_optional(section, "player.facing") {
+            Ok(facing) => facing,
+            Err(err) => return Err(err),
+        };
+        let ai = match section.get("ai.monster") {
+            Some(monster) => match SavedAi::from_section(monster, section) {
+                Ok(ai) => Some(ai),
+                Err(err) => return Err(err),
+            },
+            None => None,
+        };
+        let inventory = match section.get_entry("inventory.items") {
+            Some(entry) => {
+                let items = match entry.parse_list() {
+                    Ok(items) => items,
+                    Err(err) => return Err(err),
+                };
+                let equipped: Vec<usize> = match parse_list_or_empty(section, "inventory.equipped") {
+                    Ok(equipped) => equipped,
+                    Err(err) => return Err(err),
+                };
+                for index in equipped.iter() {
+                    if *index >= items.len() {
+                        return Err(ConfigErr::Value(entry.get_line(), "inventory.equipped".to_string(), index.to_string()));
+                    }
+                }
+                Some(SavedInventory {
+                    items: items,
+                    equipped: equipped,
+                })
+            },
+            None => None,
+        };
+        Ok(SavedActor {
+            block_coords: block_coords,
+            energy: energy,
+            health: health,
+            combat_rng: combat_rng,
+            sight: sight,
+            facing: facing,
+            ai: ai,
+            inventory: inventory,
+        })
+    }
+
+    fn push_entries(&self, text: &mut String) {
+        push_entry(text, "coords", &self.block_coords);
+        push_entry(text, "actor.energy", &self.energy);
+        push_entry(text, "combat.health", &self.health);
+        push_entry(text, "combat.rng", &self.combat_rng);
+        if let Some(origin) = self.sight.origin {
+            push_entry(text, "sight.origin", &origin);
+        }
+        push_list(text, "sight.visible", &self.sight.visible);
+        push_list(text, "sight.remembered", &self.sight.remembered);
+        if let Some(facing) = self.facing {
+            push_entry(text, "player.facing", &facing.get_name());
+        }
+        if let Some(ref ai) = self.ai {
+            push_entry(text, "ai.monster", &ai.monster);
+            push_entry(text, "ai.state", &ai.state.get_name());
+            push_entry(text, "ai.home", &ai.home);
+            push_entry(text, "ai.facing", &ai.facing.get_name());
+            if let Some(last_seen) = ai.last_seen {
+                push_entry(text, "ai.last_seen", &last_seen);
+            }
+            push_entry(text, "ai.forget_in", &ai.forget_in);
+            push_list(text, "ai.path", &ai.path);
+            if let Some(path_goal) = ai.path_goal {
+                push_entry(text, "ai.path_goal", &path_goal);
+            }
+            push_entry(text, "ai.rng", &ai.rng);
+        }
+        if let Some(ref inventory) = self.inventory {
+            push_list(text, "inventory.items", &inventory.items);
+            push_list(text, "inventory.equipped", &inventory.equipped);
+        }
+    }
+
+    pub fn get_block_coords(&self) -> BlockCoords {
+        self.block_coords
+    }
+
+    pub fn get_energy(&self) -> i32 {
+        self.energy
+    }
+
+    pub fn get_health(&self) -> i32 {
+        self.health
+    }
+
+    pub fn get_combat_rng(&self) -> u64 {
+        self.combat_rng
+    }
+
+    pub fn get_sight(&self) -> &SavedSight {
+        &self.sight
+    }
+
+    // Some for the player
+    pub fn get_facing(&self) -> Option<Direction> {
+        self.facing
+    }
+
+    // Some for monsters
+    pub fn get_ai(&self) -> Option<&SavedAi> {
+        self.ai.as_ref()
+    }
+
+    pub fn get_inventory(&self) -> Option<&SavedInventory> {
+        self.inventory.as_ref()
+    }
+}
+
+impl SavedSight {
+    // Visible and remembered are sorted so the same sight always saves the same way
+    pub fn new(origin: Option<BlockCoords>, mut visible: Vec<BlockCoords>, mut remembered: Vec<BlockCoords>) -> SavedSight {
+        visible.sort_by_key(|block_coords| (block_coords.get_x(), block_coords.get_y(), block_coords.get_z()));
+        remembered.sort_by_key(|block_coords| (block_coords.get_x(), block_coords.get_y(), block_coords.get_z()));
+        SavedSight {
+            origin: origin,
+            visible: visible,
+            remembered: remembered,
+        }
+    }
+
+    pub fn get_origin(&self) -> Option<BlockCoords> {
+        self.origin
+    }
+
+    pub fn get_visible(&self) -> &Vec<BlockCoords> {
+        &self.visible
+    }
+
+    pub fn get_remembered(&self) -> &Vec<BlockCoords> {
+        &self.remembered
+    }
+}
+
+impl SavedAi {
+    pub fn new(monster: &str, state: AiState, home: BlockCoords, facing: Direction, rng: u64) -> SavedAi {
+        SavedAi {
+            monster: monster.to_string(),
+            state: state,
+            home: home,
+            facing: facing,
+            last_seen: None,
+            forget_in: 0,
+            path: vec!(),
+            path_goal: None,
+            rng: rng,
+        }
+    }
+
+    pub fn with_last_seen(mut self, last_seen: Option<BlockCoords>, forget_in: u32) -> SavedAi {
+        self.last_seen = last_seen;
+        self.forget_in = forget_in;
+        self
+    }
+
+    pub fn with_path(mut self, path: Vec<BlockCoords>, path_goal: Option<BlockCoords>) -> SavedAi {
+        self.path = path;
+        self.path_goal = path_goal;
+        self
+    }
+
+    fn from_section(monster: &str, section: &ConfigSection) -> Result<SavedAi, ConfigErr> {
+        let state = match section.get_required("ai.state") {
+            Ok(state) => state,
+            Err(err) => return Err(err),
+        };
+        let home = match section.get_required("ai.home") {
+            Ok(home) => home,
+            Err(err) => return Err(err),
+        };
+        let facing = match section.get_required("ai.facing") {
+            Ok(facing) => facing,
+            Err(err) => return Err(err),
+        };
+        let rng = match section.get_required("ai.rng") {
+            Ok(rng) => rng,
+            Err(err) => return Err(err),
+        };
+        let last_seen = match parse_optional(section, "ai.last_seen") {
+            Ok(last_seen) => last_seen,
+            Err(err) => return Err(err),
+        };
+        let forget_in = match section.get_parsed("ai.forget_in", 0) {
+            Ok(forget_in) => forget_in,
+            Err(err) => return Err(err),
+        };
+        let path = match parse_list_or_empty(section, "ai.path") {
+            Ok(path) => path,
+            Err(err) => return Err(err),
+        };
+        let path_goal = match parse_optional(section, "ai.path_goal") {
+            Ok(path_goal) => path_goal,
+            Err(err) => return Err(err),
+        };
+        Ok(SavedAi::new(monster, state, home, facing, rng)
+            .with_last_seen(last_seen, forget_in)
+            .with_path(path, path_goal))
+    }
+
+    pub fn get_monster(&self) -> &str {
+        &self.monster
+    }
+
+    pub fn get_state(&self) -> AiState {
+        self.state
+    }
+
+    pub fn get_home(&self) -> BlockCoords {
+        self.home
+    }
+
+    pub fn get_facing(&self) -> Direction {
+        self.facing
+    }
+
+    pub fn get_last_seen(&self) -> Option<BlockCoords> {
+        self.last_seen
+    }
+
+    pub fn get_forget_in(&self) -> u32 {
+        self.forget_in
+    }
+
+    pub fn get_path(&self) -> &Vec<BlockCoords> {
+        &self.path
+    }
+
+    pub fn get_path_goal(&self) -> Option<BlockCoords> {
+        self.path_goal
+    }
+
+    pub fn get_rng(&self) -> u64 {
+        self.rng
+    }
+}
+
+impl SavedInventory {
+    pub fn new(items: Vec<String>, equipped: Vec<usize>) -> SavedInventory {
+        SavedInventory {
+            items: items,
+            equipped: equipped,
+        }
+    }
+
+    pub fn get_items(&self) -> &Vec<String> {
+        &self.items
+    }
+
+    pub fn get_equipped(&self) -> &Vec<usize> {
+        &self.equipped
+    }
+}
+
+impl SavedItem {
+    pub fn new(name: &str, block_coords: BlockCoords) -> SavedItem {
+        SavedItem {
+            name: name.to_string(),
+            block_coords: block_coords,
+        }
+    }
+
+    fn from_section(section: &ConfigSection) -> Result<SavedItem, ConfigErr> {
+        let name = match section.get_entry("name") {
+            Some(entry) => entry.get_value().to_string(),
+            None => return Err(ConfigErr::Missing(section.get_line(), "name".to_string())),
+        };
+        match section.get_required("coords") {
+            Ok(block_coords) => Ok(SavedItem::new(&name, block_coords)),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_block_coords(&self) -> BlockCoords {
+        self.block_coords
+    }
+}
+
+fn push_entry<T: fmt::Display>(text: &mut String, key: &str, value: &T) {
+    text.push_str(&format!("{} = {}\n", key, value));
+}
+
+fn push_list<T: fmt::Display>(text: &mut String, key: &str, values: &Vec<T>) {
+    text.push_str(key);
+    text.push_str(" =");
+    for value in values.iter() {
+        text.push_str(&format!(" {}", value));
+    }
+    text.push('\n');
+}
+
+fn parse_optional<T: FromStr>(section: &ConfigSection, key: &str) -> Result<Option<T>, ConfigErr> {
+    match section.get_entry(key) {
+        Some(entry) => match entry.parse() {
+            Ok(value) => Ok(Some(value)),
+            Err(err) => Err(err),
+        },
+        None => Ok(None),
+    }
+}
+
+fn parse_list_or_empty<T: FromStr>(section: &ConfigSection, key: &str) -> Result<Vec<T>, ConfigErr> {
+    match section.get_entry(key) {
+        Some(entry) => entry.parse_list(),
+        None => Ok(vec!()),
+    }
+}
+
+// Runs are 'block*count', or just 'block' for one
+fn decode_runs(entry: &ConfigEntry, palette_len: usize) -> Result<Vec<u16>, ConfigErr> {
+    let mut blocks = vec!();
+    for run in entry.get_value().split_whitespace() {
+        let mut parts = run.splitn(2, '*');
+        let block = parts.next().and_then(|block| block.parse::<u16>().ok());
+        let count = match parts.next() {
+            Some(count) => count.parse::<usize>().ok(),
+            None => Some(1),
+        };
+        match (block, count) {
+            (Some(block), Some(count)) if (block as usize) < palette_len => {
+                for _ in 0..count {
+                    blocks.push(block);
+                }
+            },
+            _ => return Err(ConfigErr::Value(entry.get_line(), entry.get_key().to_string(), run.to_string())),
+        }
+    }
+    Ok(blocks)
+}
+
+#[derive(Debug)]
+pub enum SaveErr {
+    Io(String, io::Error),
+    Config(&'static str, ConfigErr),
+    Version(u32),
+    Missing(&'static str),
+    Get(&'static str),
+}
+
+impl fmt::Display for SaveErr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SaveErr::Io(ref path, ref err) => write!(f, "{}: {}", path, err),
+            SaveErr::Config(_, ref err) => err.fmt(f),
+            SaveErr::Version(version) => write!(f, "Save is version {}, expected {}", version, SAVE_VERSION),
+            SaveErr::Missing(section) => write!(f, "Save is missing its [{}] section", section),
+            SaveErr::Get(_) => write!(f, "Get was None"),
+        }
+    }
+}
+
+impl Error for SaveErr {
+    fn description(&self) -> &str {
+        match *self {
+            SaveErr::Io(_, ref err) => err.description(),
+            SaveErr::Config(_, ref err) => err.description(),
+            SaveErr::Version(_) => "Unsupported save version",
+            SaveErr::Missing(_) => "Missing save section",
+            SaveErr::Get(_) => "Get was None",
+        }
+    }
+}
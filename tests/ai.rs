@@ -0,0 +1,229 @@
+extern crate dorp;
+extern crate rogue;
+
+use dorp::{World, IdManager, Id, OptErr, Vec2};
+
+use rogue::{
+    RogueData, RogueWorld, BlockCoords, BlockType, BlockRegistry, VoxelVolume, MonsterRegistry, Scheduler, PlayerAction,
+    Direction, AiState, AiAction, LevelKind, DungeonParams, BLOCKS_PATH, MONSTERS_PATH, new_block_map_entity,
+    new_block_registry_entity, new_voxel_volume_entity, new_scheduler_entity, new_player_entity, new_monster_entity,
+    pick_monster_spawns, tick_mut_entity
+};
+
+static MONSTERS: &'static str = "
+[hunter]
+sight = 8
+memory = 2
+leash = 30
+
+[slow_hunter]
+speed = 50
+sight = 12
+memory = 2
+leash = 30
+
+[coward]
+sight = 8
+timid = true
+
+[drifter]
+wander_chance = 1
+wander_radius = 3
+";
+
+// A stone floor at y = -1 under a 16 by 16 room
+fn room() -> VoxelVolume {
+    let mut voxel_volume = VoxelVolume::new(BlockCoords::new(0, -2, 0), 16, 8, 16);
+    voxel_volume.fill(&BlockCoords::new(0, -2, 0), &BlockCoords::new(15, -1, 15), BlockType::STONE);
+    voxel_volume
+}
+
+struct Encounter {
+    manager: IdManager,
+    world: RogueWorld,
+    scheduler_id: Id,
+    player_id: Option<Id>,
+}
+
+impl Encounter {
+    fn new(voxel_volume: VoxelVolume, player: Option<BlockCoords>) -> Encounter {
+        let mut manager = IdManager::new();
+        let mut world = World::<RogueData>::new(Vec2::from([1.0, 1.0]));
+        new_block_registry_entity(BlockRegistry::load(BLOCKS_PATH).unwrap(), &mut manager, &mut world).unwrap();
+        new_voxel_volume_entity(voxel_volume, &mut manager, &mut world).unwrap();
+        new_block_map_entity(&mut manager, &mut world).unwrap();
+        let scheduler_id = new_scheduler_entity(Scheduler::new(), &mut manager, &mut world).unwrap();
+        let player_id = player.map(|block_coords| new_player_entity(block_coords, None, &mut manager, &mut world).unwrap());
+        Encounter {
+            manager: manager,
+            world: world,
+            scheduler_id: scheduler_id,
+            player_id: player_id,
+        }
+    }
+
+    fn spawn(&mut self, name: &str, block_coords: BlockCoords) -> Id {
+        let monster_registry = MonsterRegistry::parse(MONSTERS).unwrap();
+        new_monster_entity(monster_registry.get(name).unwrap(), block_coords, None, 7, &mut self.manager, &mut self.world).unwrap()
+    }
+
+    // Runs until the player's next turn, with player_action as its move this turn
+    fn turn(&mut self, player_action: PlayerAction) {
+        match self.player_id {
+            Some(player_id) => match self.world.get_mut_entity_by_id(player_id) {
+                OptErr::Full(entity) => match entity.get_mut_player() {
+                    OptErr::Full(player) => player.set_next_action(player_action),
+                    _ => panic!("player entity has no player"),
+                },
+                _ => panic!("player entity missing"),
+            },
+            None => (),
+        }
+        tick_mut_entity(self.scheduler_id, 0, &mut self.manager, &mut self.world).unwrap();
+    }
+
+    fn coords(&self, id: Id) -> BlockCoords {
+        *self.world.get_entity_by_id(id).unwrap().get_block_coords().unwrap()
+    }
+
+    fn state(&self, id: Id) -> AiState {
+        self.world.get_entity_by_id(id).unwrap().get_ai().unwrap().get_state()
+    }
+
+    fn last_action(&self, id: Id) -> Option<AiAction> {
+        self.world.get_entity_by_id(id).unwrap().get_ai().unwrap().get_last_action()
+    }
+}
+
+fn distance(a: &BlockCoords, b: &BlockCoords) -> i64 {
+    (a.get_x() - b.get_x()).abs() + (a.get_z() - b.get_z()).abs()
+}
+
+#[test]
+fn monster_types_come_from_data() {
+    let monster_registry = MonsterRegistry::load(MONSTERS_PATH).unwrap();
+    assert!(monster_registry.len() > 0);
+    let monster_registry = MonsterRegistry::parse(MONSTERS).unwrap();
+    let drifter = monster_registry.get("drifter").unwrap();
+    assert_eq!(drifter.get_speed(), 100);
+    assert_eq!(drifter.get_sight(), 8);
+    assert!(!drifter.is_timid() && !drifter.opens_doors());
+    assert!(monster_registry.get("coward").unwrap().is_timid());
+    assert!(MonsterRegistry::parse("[rat]\nspeed = 0\n").is_err());
+    assert!(MonsterRegistry::parse("[rat]\nwander_chance = 2\n").is_err());
+    assert!(MonsterRegistry::parse("[rat]\nsight = far\n").is_err());
+}
+
+#[test]
+fn hunter_closes_in_and_attacks() {
+    let mut encounter = Encounter::new(room(), Some(BlockCoords::new(0, 0, 8)));
+    let player_id = encounter.player_id.unwrap();
+    let hunter = encounter.spawn("hunter", BlockCoords::new(14, 0, 8));
+
+    // Out of sight range it stays put
+    encounter.turn(PlayerAction::Wait);
+    assert_eq!(encounter.state(hunter), AiState::Idle);
+    assert_eq!(encounter.coords(hunter), BlockCoords::new(14, 0, 8));
+
+    let mut turns = 0;
+    while encounter.last_action(hunter) != Some(AiAction::Attack(player_id)) {
+        // Walking into the hunter would keep the turn, so stand and wait once next to it
+        if distance(&encounter.coords(hunter), &encounter.coords(player_id)) > 1 {
+            encounter.turn(PlayerAction::Move(Direction::East));
+        } else {
+            encounter.turn(PlayerAction::Wait);
+        }
+        turns += 1;
+        assert!(turns < 16, "hunter never attacked");
+        if encounter.state(hunter) == AiState::Hunt {
+            assert!(encounter.coords(hunter) != encounter.coords(player_id));
+        }
+    }
+    assert_eq!(encounter.state(hunter), AiState::Hunt);
+    assert_eq!(distance(&encounter.coords(hunter), &encounter.coords(player_id)), 1);
+}
+
+#[test]
+fn hunter_loses_track_and_goes_home() {
+    // A wall across the room with a gap at the east end
+    let mut voxel_volume = room();
+    voxel_volume.fill(&BlockCoords::new(0, 0, 6), &BlockCoords::new(11, 1, 6), BlockType::STONE);
+    let mut encounter = Encounter::new(voxel_volume, Some(BlockCoords::new(13, 0, 10)));
+    let home = BlockCoords::new(3, 0, 10);
+    let hunter = encounter.spawn("slow_hunter", home);
+
+    // Slow, so it only gets a turn every other one
+    encounter.turn(PlayerAction::Wait);
+    encounter.turn(PlayerAction::Wait);
+    assert_eq!(encounter.state(hunter), AiState::Hunt);
+    // Round the wall and away along the far side
+    for _ in 0..5 {
+        encounter.turn(PlayerAction::Move(Direction::North));
+    }
+    for _ in 0..10 {
+        encounter.turn(PlayerAction::Move(Direction::West));
+    }
+
+    let mut states = vec!();
+    for _ in 0..40 {
+        encounter.turn(PlayerAction::Wait);
+        let state = encounter.state(hunter);
+        if states.last() != Some(&state) {
+            states.push(state);
+        }
+        assert!(match encounter.last_action(hunter) {
+            Some(AiAction::Attack(_)) => false,
+            _ => true,
+        });
+    }
+    assert!(states.contains(&AiState::ReturnHome));
+    assert_eq!(states.last(), Some(&AiState::Idle));
+    assert_eq!(encounter.coords(hunter), home);
+}
+
+#[test]
+fn timid_monsters_keep_away() {
+    let mut encounter = Encounter::new(room(), Some(BlockCoords::new(4, 0, 8)));
+    let player_id = encounter.player_id.unwrap();
+    let coward = encounter.spawn("coward", BlockCoords::new(7, 0, 8));
+    let start = distance(&encounter.coords(coward), &encounter.coords(player_id));
+    for _ in 0..4 {
+        encounter.turn(PlayerAction::Move(Direction::East));
+        assert_eq!(encounter.state(coward), AiState::Flee);
+        assert!(distance(&encounter.coords(coward), &encounter.coords(player_id)) >= start);
+    }
+}
+
+#[test]
+fn wanderers_stay_near_home() {
+    let mut encounter = Encounter::new(room(), None);
+    let home = BlockCoords::new(8, 0, 8);
+    let drifter = encounter.spawn("drifter", home);
+    let mut moved = false;
+    for _ in 0..4 {
+        encounter.turn(PlayerAction::Wait);
+        let here = encounter.coords(drifter);
+        moved = moved || here != home;
+        assert!((here.get_x() - home.get_x()).abs() <= 3 && (here.get_z() - home.get_z()).abs() <= 3);
+        assert_eq!(here.get_y(), 0);
+    }
+    assert!(moved);
+}
+
+#[test]
+fn monsters_spawn_away_from_the_player() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let monster_registry = MonsterRegistry::load(MONSTERS_PATH).unwrap();
+    let (voxel_volume, spawn) = LevelKind::Dungeon(DungeonParams::new(48, 48)).build_volume(3);
+    let spawns = pick_monster_spawns(&voxel_volume, &block_registry, &monster_registry, &spawn, 6, 3);
+    assert_eq!(spawns.len(), 6);
+    for &(block_coords, _, _) in spawns.iter() {
+        assert!(distance(&block_coords, &spawn) >= 4);
+        assert!(!block_registry.is_solid(voxel_volume.get(&block_coords)));
+        assert!(block_registry.is_solid(voxel_volume.get(&block_coords.offset(0, -1, 0))));
+    }
+    let again = pick_monster_spawns(&voxel_volume, &block_registry, &monster_registry, &spawn, 6, 3);
+    let coords: Vec<BlockCoords> = spawns.iter().map(|&(block_coords, _, _)| block_coords).collect();
+    let coords_again: Vec<BlockCoords> = again.iter().map(|&(block_coords, _, _)| block_coords).collect();
+    assert_eq!(coords, coords_again);
+}
@@ -0,0 +1,67 @@
+extern crate rogue;
+
+use std::env;
+use std::fs::{self, File};
+use std::io::{Write};
+use std::path::{PathBuf};
+use std::sync::{Arc};
+
+use rogue::{AssetManifest, AssetLoader, AssetErr, BlockRegistry, BLOCKS_PATH, MANIFEST_PATH};
+
+// A scratch directory per test so parallel tests don't trip over each other's files
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("rogue_assets_{}", name));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn shipped_manifest_checks_clean() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let mut asset_loader = AssetLoader::load(MANIFEST_PATH).unwrap();
+    assert!(asset_loader.check(&block_registry).is_empty());
+    assert!(asset_loader.is_cached("sprite.being"));
+}
+
+#[test]
+fn images_are_decoded_once() {
+    let mut asset_loader = AssetLoader::load(MANIFEST_PATH).unwrap();
+    assert!(!asset_loader.is_cached("block.brick"));
+    let first = asset_loader.load_image("block.brick").unwrap();
+    let second = asset_loader.load_image("block.brick").unwrap();
+    assert!(Arc::ptr_eq(&first, &second));
+    assert_eq!(first.dimensions(), (16, 16));
+}
+
+#[test]
+fn missing_and_corrupt_files_are_typed() {
+    let dir = scratch_dir("broken");
+    File::create(dir.join("corrupt.png")).unwrap().write_all(b"not a png").unwrap();
+    let manifest = AssetManifest::parse("[images]\nblock.gone = gone.png\nblock.corrupt = corrupt.png\n", dir.clone()).unwrap();
+    let mut asset_loader = AssetLoader::new(manifest);
+    match asset_loader.load_image("block.gone") {
+        Err(AssetErr::Missing(name, _)) => assert_eq!(name, "block.gone"),
+        _ => panic!("Expected missing file"),
+    }
+    match asset_loader.load_image("block.corrupt") {
+        Err(AssetErr::Corrupt(name, _, _)) => assert_eq!(name, "block.corrupt"),
+        _ => panic!("Expected corrupt file"),
+    }
+    match asset_loader.load_image("block.never") {
+        Err(AssetErr::Unknown(name)) => assert_eq!(name, "block.never"),
+        _ => panic!("Expected unknown name"),
+    }
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let errs = asset_loader.check(&block_registry);
+    // Both broken files, plus the block textures this manifest doesn't list
+    assert_eq!(errs.iter().filter(|err| match **err { AssetErr::UnknownTexture(_, _) => true, _ => false }).count(), 4);
+    assert_eq!(errs.len(), 6);
+}
+
+#[test]
+fn manifest_rejects_unknown_sections() {
+    assert!(AssetManifest::parse("[sounds]\nsound.step = step.ogg\n", PathBuf::new()).is_err());
+    let manifest = AssetManifest::parse("[images]\nblock.brick = brick.png # red\n", PathBuf::from("assets")).unwrap();
+    assert_eq!(manifest.get_path("block.brick"), Some(PathBuf::from("assets").join("brick.png")));
+    assert_eq!(manifest.get_image("block.brick").unwrap().get_line(), 2);
+}
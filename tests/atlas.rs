@@ -0,0 +1,119 @@
+extern crate image;
+extern crate rogue;
+
+use image::{RgbaImage, Rgba};
+
+use rogue::{
+    Atlas, AtlasErr, UvRect, VoxelVolume, BlockCoords, BlockType, BlockRegistry, AssetLoader, ChunkCoords,
+    Cutaway, BLOCKS_PATH, MANIFEST_PATH, mesh_chunk_textured
+};
+use rogue::graphics::{ATLAS_PADDING};
+
+fn sprite(width: u32, height: u32, shade: u8) -> RgbaImage {
+    RgbaImage::from_pixel(width, height, Rgba { data: [shade, shade, shade, 255] })
+}
+
+fn pixel_rect(atlas: &Atlas, name: &str) -> (u32, u32, u32, u32) {
+    let uv_rect = atlas.get_uv_rect(name).unwrap();
+    let (width, height) = atlas.get_image().dimensions();
+    (
+        (uv_rect.get_min()[0] * width as f32).round() as u32,
+        (uv_rect.get_min()[1] * height as f32).round() as u32,
+        (uv_rect.get_max()[0] * width as f32).round() as u32,
+        (uv_rect.get_max()[1] * height as f32).round() as u32
+    )
+}
+
+#[test]
+fn uv_rect_maps_sprite_space() {
+    let uv_rect = UvRect::new([0.25, 0.5], [0.5, 1.0]);
+    assert_eq!(uv_rect.get_uv(0.0, 0.0), [0.25, 0.5]);
+    assert_eq!(uv_rect.get_uv(1.0, 1.0), [0.5, 1.0]);
+    assert_eq!(uv_rect.get_uv(0.5, 0.5), [0.375, 0.75]);
+}
+
+#[test]
+fn shelves_pack_without_overlap() {
+    let atlas = Atlas::pack(vec!(
+        ("a".to_string(), sprite(16, 16, 10)),
+        ("b".to_string(), sprite(32, 32, 20)),
+        ("c".to_string(), sprite(16, 8, 30)),
+        ("d".to_string(), sprite(16, 16, 40)),
+    ), 64).unwrap();
+    assert_eq!(atlas.get_image().width(), 64);
+    assert!(atlas.get_image().height().is_power_of_two());
+    // Tallest first: b then a on the first shelf, d no longer fits and starts the next one
+    assert_eq!(pixel_rect(&atlas, "b"), (1, 1, 33, 33));
+    assert_eq!(pixel_rect(&atlas, "a"), (35, 1, 51, 17));
+    assert_eq!(pixel_rect(&atlas, "d"), (1, 35, 17, 51));
+    assert_eq!(pixel_rect(&atlas, "c"), (19, 35, 35, 43));
+    let names = ["a", "b", "c", "d"];
+    for first in names.iter() {
+        for second in names.iter().filter(|name| *name != first) {
+            let a = pixel_rect(&atlas, first);
+            let b = pixel_rect(&atlas, second);
+            assert!(a.2 + ATLAS_PADDING <= b.0 || b.2 + ATLAS_PADDING <= a.0 || a.3 + ATLAS_PADDING <= b.1 || b.3 + ATLAS_PADDING <= a.1);
+        }
+    }
+}
+
+#[test]
+fn padding_repeats_the_sprite_edge() {
+    let atlas = Atlas::pack(vec!(("a".to_string(), sprite(4, 4, 99))), 16).unwrap();
+    let (left, top, right, bottom) = pixel_rect(&atlas, "a");
+    assert_eq!(atlas.get_image().get_pixel(left - 1, top - 1).data, [99, 99, 99, 255]);
+    assert_eq!(atlas.get_image().get_pixel(right, bottom).data, [99, 99, 99, 255]);
+    assert_eq!(atlas.get_image().get_pixel(right + 1, top).data, [0, 0, 0, 0]);
+}
+
+#[test]
+fn oversized_sprites_are_rejected() {
+    match Atlas::pack(vec!(("wide".to_string(), sprite(64, 4, 0))), 64) {
+        Err(AtlasErr::TooWide(name, 64)) => assert_eq!(name, "wide"),
+        _ => panic!("Expected sprite to be too wide"),
+    }
+}
+
+#[test]
+fn every_visible_block_has_a_rect() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let atlas = Atlas::for_blocks(&block_registry, &mut AssetLoader::load(MANIFEST_PATH).unwrap()).unwrap();
+    assert!(atlas.get_uv_rect("block.brick").is_some());
+    assert!(atlas.get_block_uv_rect(&block_registry, BlockType::AIR).is_none());
+    for block_type in block_registry.get_block_types().into_iter().filter(|block_type| *block_type != BlockType::AIR) {
+        assert!(atlas.get_block_uv_rect(&block_registry, block_type).is_some());
+    }
+    let brick = block_registry.get_by_name("brick").unwrap();
+    let wood = block_registry.get_by_name("wood").unwrap();
+    assert_eq!(atlas.get_block_uv_rect(&block_registry, BlockType::DOOR), atlas.get_block_uv_rect(&block_registry, wood));
+    assert!(atlas.get_block_uv_rect(&block_registry, brick) != atlas.get_block_uv_rect(&block_registry, wood));
+}
+
+#[test]
+fn textured_faces_are_culled_and_upright() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let atlas = Atlas::for_blocks(&block_registry, &mut AssetLoader::load(MANIFEST_PATH).unwrap()).unwrap();
+    let brick = block_registry.get_by_name("brick").unwrap();
+    let mut voxel_volume = VoxelVolume::new(BlockCoords::new(0, 0, 0), 4, 4, 4);
+    voxel_volume.set(&BlockCoords::new(0, 0, 0), brick);
+    voxel_volume.set(&BlockCoords::new(1, 0, 0), brick);
+    let chunk_mesh = mesh_chunk_textured(&voxel_volume, &block_registry, &atlas, &Cutaway::off(), None, &ChunkCoords::new(0, 0, 0));
+    // No merging, so two cubes sharing a face keep five faces each
+    assert_eq!(chunk_mesh.get_quad_count(), 10);
+    let uv_rect = atlas.get_uv_rect("block.brick").unwrap();
+    for vertex in chunk_mesh.get_vertices() {
+        let tex_coord = vertex.get_tex_coord();
+        assert!(tex_coord[0] >= uv_rect.get_min()[0] && tex_coord[0] <= uv_rect.get_max()[0]);
+        assert!(tex_coord[1] >= uv_rect.get_min()[1] && tex_coord[1] <= uv_rect.get_max()[1]);
+    }
+    // On side faces the top of the block samples the top of the sprite
+    for quad in chunk_mesh.get_vertices().chunks(4) {
+        let is_side = quad.iter().any(|vertex| vertex.get_position()[1] == 0.0) && quad.iter().any(|vertex| vertex.get_position()[1] == 1.0);
+        if is_side {
+            for vertex in quad {
+                let expected = if vertex.get_position()[1] == 1.0 { uv_rect.get_min()[1] } else { uv_rect.get_max()[1] };
+                assert_eq!(vertex.get_tex_coord()[1], expected);
+            }
+        }
+    }
+}
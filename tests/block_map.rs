@@ -0,0 +1,46 @@
+extern crate dorp;
+extern crate rogue;
+
+use dorp::{World, IdManager, Vec2, EntityData};
+
+use rogue::{RogueData, RogueWorld, BlockCoords, BlockType, BLOCK_MAP_NAME, new_block_entity};
+
+fn new_world() -> RogueWorld {
+    World::<RogueData>::new(Vec2::from([1.0, 1.0]))
+}
+
+#[test]
+fn new_block_entity_creates_block_map() {
+    let mut manager = IdManager::new();
+    let mut world = new_world();
+    assert!(world.get_entity_by_name(BLOCK_MAP_NAME).is_none());
+    new_block_entity(BlockType::DIRT, BlockCoords::new(0, 0, 0), &mut manager, &mut world).unwrap();
+    let block_map_entity = world.get_entity_by_name(BLOCK_MAP_NAME).unwrap();
+    assert!(block_map_entity.get_block_map().is_some());
+}
+
+#[test]
+fn blocks_register_at_their_coords() {
+    let mut manager = IdManager::new();
+    let mut world = new_world();
+    let a = new_block_entity(BlockType::DIRT, BlockCoords::new(0, 0, 0), &mut manager, &mut world).unwrap();
+    let b = new_block_entity(BlockType::AIR, BlockCoords::new(3, -2, 7), &mut manager, &mut world).unwrap();
+
+    let block_map = world.get_entity_by_name(BLOCK_MAP_NAME).unwrap().get_block_map().unwrap();
+    assert_eq!(block_map.get(0, 0, 0), Some(a));
+    assert_eq!(block_map.get(3, -2, 7), Some(b));
+    assert_eq!(block_map.get(1, 0, 0), None);
+
+    let block = world.get_entity_by_id(b).unwrap().get_block().unwrap();
+    assert_eq!(block.get_block_type(), BlockType::AIR);
+}
+
+#[test]
+fn blocks_share_one_block_map() {
+    let mut manager = IdManager::new();
+    let mut world = new_world();
+    new_block_entity(BlockType::DIRT, BlockCoords::new(0, 0, 0), &mut manager, &mut world).unwrap();
+    let block_map_id = world.get_entity_by_name(BLOCK_MAP_NAME).unwrap().get_id();
+    new_block_entity(BlockType::DIRT, BlockCoords::new(1, 0, 0), &mut manager, &mut world).unwrap();
+    assert_eq!(world.get_entity_by_name(BLOCK_MAP_NAME).unwrap().get_id(), block_map_id);
+}
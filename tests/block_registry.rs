@@ -0,0 +1,91 @@
+extern crate rogue;
+
+use rogue::{BlockRegistry, BlockRegistryErr, BlockType, Config, ConfigErr, BLOCKS_PATH};
+
+static CORE: &'static str = "
+[air]
+solid = false
+color = 0 0 0 0
+[dirt]
+[door]
+special = true
+[chest]
+special = true
+[stone]
+[grass]
+";
+
+#[test]
+fn shipped_blocks_load_with_core_ids() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    assert_eq!(block_registry.get_by_name("air"), Some(BlockType::AIR));
+    assert_eq!(block_registry.get_by_name("grass"), Some(BlockType::GRASS));
+    assert!(!block_registry.is_solid(BlockType::AIR));
+    assert!(block_registry.is_solid(BlockType::STONE));
+    assert!(block_registry.is_special(BlockType::DOOR));
+    let water = block_registry.get_by_name("water").unwrap();
+    assert!(!block_registry.is_solid(water));
+    assert!(!block_registry.is_opaque(water));
+    assert!(block_registry.get_walk_cost(water) > 1);
+    let brick = block_registry.get(block_registry.get_by_name("brick").unwrap()).unwrap();
+    assert_eq!(brick.get_texture(), Some("block.brick"));
+    assert_eq!(brick.get_drop(), Some("brick"));
+}
+
+#[test]
+fn core_blocks_keep_ids_in_any_order() {
+    let text = format!("[water]\nsolid = false\n{}", CORE);
+    let block_registry = BlockRegistry::parse(&text).unwrap();
+    assert_eq!(block_registry.len(), 7);
+    assert_eq!(block_registry.get_by_name("dirt"), Some(BlockType::DIRT));
+    assert_eq!(block_registry.get_by_name("water"), Some(BlockType::new(6)));
+    assert_eq!(block_registry.get(BlockType::DIRT).unwrap().get_name(), "dirt");
+    assert!(block_registry.get(BlockType::new(7)).is_none());
+}
+
+#[test]
+fn defaults_follow_solidity() {
+    let block_registry = BlockRegistry::parse(CORE).unwrap();
+    assert!(block_registry.is_opaque(BlockType::DIRT));
+    assert!(!block_registry.is_opaque(BlockType::AIR));
+    assert!(!block_registry.is_visible(BlockType::AIR));
+    assert_eq!(block_registry.get_walk_cost(BlockType::DIRT), 1);
+}
+
+#[test]
+fn missing_core_block_is_rejected() {
+    match BlockRegistry::parse("[air]\n[dirt]\n") {
+        Err(BlockRegistryErr::MissingCore(name)) => assert_eq!(name, "door"),
+        other => panic!("Expected missing core block, got {:?}", other.map(|block_registry| block_registry.len())),
+    }
+}
+
+#[test]
+fn bad_values_report_their_line() {
+    let text = format!("{}[lava]\nwalk_cost = lots\n", CORE);
+    match BlockRegistry::parse(&text) {
+        Err(BlockRegistryErr::Config(_, ConfigErr::Value(line, ref key, _))) => {
+            assert_eq!(line, CORE.lines().count() + 2);
+            assert_eq!(key, "walk_cost");
+        },
+        other => panic!("Expected bad value, got {:?}", other.map(|block_registry| block_registry.len())),
+    }
+    match BlockRegistry::parse(&format!("{}[mud]\ndrop = clay\n", CORE)) {
+        Err(BlockRegistryErr::UnknownDrop(ref name, ref drop)) => assert_eq!((name.as_str(), drop.as_str()), ("mud", "clay")),
+        other => panic!("Expected unknown drop, got {:?}", other.map(|block_registry| block_registry.len())),
+    }
+}
+
+#[test]
+fn config_syntax_errors_report_their_line() {
+    match Config::parse("# blocks\n[air]\nsolid false\n") {
+        Err(ConfigErr::Syntax(line, _)) => assert_eq!(line, 3),
+        _ => panic!("Expected syntax error"),
+    }
+    match Config::parse("[air]\n[air]\n") {
+        Err(ConfigErr::Duplicate(line, _)) => assert_eq!(line, 2),
+        _ => panic!("Expected duplicate section"),
+    }
+    let config = Config::parse("[air] # nothing\nsolid = false # walk through\n").unwrap();
+    assert_eq!(config.get_section("air").unwrap().get("solid"), Some("false"));
+}
@@ -0,0 +1,102 @@
+extern crate rogue;
+
+use rogue::{Camera, CutawayMode, InputMap, InputState, InputEvent};
+
+fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-4
+}
+
+#[test]
+fn rotation_snaps_by_quarter_turns() {
+    let mut camera = Camera::new(1.0);
+    assert!(close(camera.get_yaw().to_degrees(), 45.0));
+    camera.rotate(1);
+    assert!(close(camera.get_yaw().to_degrees(), 135.0));
+    camera.rotate(-2);
+    assert_eq!(camera.get_quarter_turns(), 3);
+    assert!(close(camera.get_yaw().to_degrees(), 315.0));
+    camera.rotate(5);
+    assert_eq!(camera.get_quarter_turns(), 0);
+    assert_eq!(camera.get_billboard_view().get_yaw(), camera.get_yaw());
+}
+
+#[test]
+fn zoom_is_clamped() {
+    let mut camera = Camera::new(1.0);
+    let size = camera.get_view_size();
+    camera.zoom_by(2.0);
+    assert!(close(camera.get_view_size(), size / 2.0));
+    camera.zoom_by(1000.0);
+    assert!(close(camera.get_zoom(), 8.0));
+    camera.zoom_by(0.0);
+    assert!(close(camera.get_zoom(), 0.25));
+}
+
+#[test]
+fn panning_follows_the_screen() {
+    let mut camera = Camera::new(1.0);
+    camera.pan(0.0, 1.0);
+    // Up the screen is along the ground away from the camera
+    let forward = camera.get_billboard_view().get_forward();
+    let pan = camera.get_pan();
+    assert!(pan[0] * forward[0] + pan[2] * forward[2] > 0.0);
+    assert!(close(pan[0] * pan[0] + pan[2] * pan[2], 1.0));
+    assert_eq!(pan[1], 0.0);
+    camera.reset_pan();
+    camera.rotate(2);
+    camera.pan(1.0, 0.0);
+    // Half a turn flips which way is right
+    let right = Camera::new(1.0).get_billboard_view().get_right();
+    assert!(close(camera.get_pan()[0], -right[0]) && close(camera.get_pan()[2], -right[2]));
+}
+
+#[test]
+fn follow_eases_in_and_settles() {
+    let mut camera = Camera::new(1.0).with_follow_rate(0.5);
+    camera.follow([4.0, 0.0, -2.0]);
+    assert!(close(camera.get_focus()[0], 2.0) && close(camera.get_focus()[2], -1.0));
+    for _ in 0..32 {
+        camera.follow([4.0, 0.0, -2.0]);
+    }
+    assert_eq!(camera.get_focus(), [4.0, 0.0, -2.0]);
+    let version = camera.get_version();
+    camera.follow([4.0, 0.0, -2.0]);
+    assert_eq!(camera.get_version(), version);
+}
+
+#[test]
+fn changes_bump_the_version() {
+    let mut camera = Camera::new(1.5);
+    let version = camera.get_version();
+    camera.set_aspect_ratio(1.5);
+    assert_eq!(camera.get_version(), version);
+    camera.set_aspect_ratio(2.0);
+    assert!(camera.get_version() > version);
+    let version = camera.get_version();
+    camera.rotate(1);
+    assert!(camera.get_version() > version);
+}
+
+#[test]
+fn input_zooms_rotates_and_slices_once_per_frame() {
+    let input_map = InputMap::parse("[bindings]\nzoom_in = WheelUp\nrotate_right = E\nslice_down = PageDown\npan_right = Numpad6\n").unwrap();
+    let mut input_state = InputState::new(input_map);
+    for input in ["WheelUp", "E", "PageDown", "Numpad6"].iter() {
+        input_state.push_event(InputEvent::Pressed(input.to_string()));
+    }
+    input_state.update();
+
+    let mut camera = Camera::new(1.0);
+    camera.read_input(&input_state);
+    camera.read_input(&input_state);
+    assert!(close(camera.get_zoom(), 1.25));
+    assert_eq!(camera.get_quarter_turns(), 1);
+    assert_eq!(camera.get_cutaway_mode(), CutawayMode::Slice(-1));
+    // Held pans keep moving every tick
+    assert!(close(camera.get_pan()[0].hypot(camera.get_pan()[2]), 1.0));
+
+    camera.toggle_cutaway();
+    assert_eq!(camera.get_cutaway_mode(), CutawayMode::Off);
+    camera.toggle_cutaway();
+    assert_eq!(camera.get_cutaway_mode(), CutawayMode::Auto);
+}
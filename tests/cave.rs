@@ -0,0 +1,59 @@
+extern crate rogue;
+
+use rogue::{CaveParams, LevelKind, Layout, Tile, generate_cave};
+
+fn cave(params: CaveParams, seed: u64) -> Layout {
+    generate_cave(&params, seed)
+}
+
+#[test]
+fn same_seed_same_cave() {
+    let params = CaveParams::new(40, 30);
+    assert_eq!(cave(params.clone(), 11).get_tiles(), cave(params.clone(), 11).get_tiles());
+    assert!(cave(params.clone(), 11).get_tiles() != cave(params, 12).get_tiles());
+}
+
+#[test]
+fn every_open_tile_is_connected() {
+    for seed in 0..30 {
+        let layout = cave(CaveParams::new(40, 30), seed);
+        let entrance = layout.get_entrance();
+        assert_eq!(layout.get(entrance.0, entrance.1), Tile::Floor, "seed {}\n{}", seed, layout);
+        assert!(layout.count(Tile::Floor) > 40 * 30 / 10, "seed {}\n{}", seed, layout);
+        let distances = layout.get_distances(entrance);
+        for (index, tile) in layout.get_tiles().iter().enumerate() {
+            assert_eq!(*tile == Tile::Floor, distances[index].is_some(), "seed {}\n{}", seed, layout);
+        }
+        assert!(layout.is_connected(entrance, layout.get_exit()));
+    }
+}
+
+#[test]
+fn border_stays_wall() {
+    let layout = cave(CaveParams::new(30, 30).with_fill_ratio(0.3), 4);
+    for i in 0..30 {
+        assert_eq!(layout.get(i, 0), Tile::Wall);
+        assert_eq!(layout.get(i, 29), Tile::Wall);
+        assert_eq!(layout.get(0, i), Tile::Wall);
+        assert_eq!(layout.get(29, i), Tile::Wall);
+    }
+}
+
+#[test]
+fn fill_ratio_and_iterations_are_tunable() {
+    let open = cave(CaveParams::new(50, 50).with_fill_ratio(0.35), 8).count(Tile::Floor);
+    let closed = cave(CaveParams::new(50, 50).with_fill_ratio(0.5), 8).count(Tile::Floor);
+    assert!(open > closed);
+
+    let raw = cave(CaveParams::new(50, 50).with_iterations(0), 8);
+    let smooth = cave(CaveParams::new(50, 50).with_iterations(6), 8);
+    assert!(raw.get_tiles() != smooth.get_tiles());
+}
+
+#[test]
+fn level_kind_selects_generator() {
+    let params = CaveParams::new(32, 24);
+    let layout = LevelKind::Cave(params.clone()).generate(3).unwrap();
+    assert_eq!(layout.get_tiles(), cave(params, 3).get_tiles());
+    assert_eq!((layout.get_width(), layout.get_depth()), (32, 24));
+}
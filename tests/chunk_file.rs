@@ -0,0 +1,195 @@
+extern crate rogue;
+
+use std::env;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+
+use rogue::{
+    BlockRegistry, BlockCoords, BlockType, ChunkCoords, VoxelVolume, LevelKind, DungeonParams, CaveParams, TerrainParams, ChunkFile, ChunkFileErr,
+    ChunkLoad, CHUNK_VOLUME, BLOCKS_PATH, checksum, load_level_chunks
+};
+
+fn terrain() -> LevelKind {
+    LevelKind::Terrain(TerrainParams::new(40, 24, 40))
+}
+
+fn no_regenerate(_: &ChunkCoords) -> Vec<BlockType> {
+    panic!("nothing should need regenerating")
+}
+
+fn temp_path(name: &str) -> String {
+    let path = env::temp_dir().join(name);
+    let _ = fs::remove_file(&path);
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn checksum_is_crc32() {
+    assert_eq!(checksum(b"123456789"), 0xcbf43926);
+    assert_eq!(checksum(b""), 0);
+}
+
+#[test]
+fn volumes_round_trip_through_bytes() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let (voxel_volume, _) = terrain().build_volume(9);
+    let bytes = ChunkFile::from_volume(&voxel_volume, &block_registry, 9).unwrap().to_bytes();
+    // Runs keep the file well under the two bytes a cell the volume itself takes
+    assert!(bytes.len() < voxel_volume.get_len() / 2);
+
+    let chunk_file = ChunkFile::from_bytes(&bytes).unwrap();
+    assert_eq!(chunk_file.get_seed(), 9);
+    assert_eq!(chunk_file.get_origin(), voxel_volume.get_origin());
+    assert_eq!(chunk_file.get_size(), (40, 24, 40));
+    assert!(chunk_file.get_corrupt().is_empty());
+    assert_eq!(chunk_file.get_chunks().len(), voxel_volume.get_chunks().len());
+    let (loaded, regenerated) = chunk_file.to_volume(&block_registry, no_regenerate).unwrap();
+    assert!(regenerated.is_empty());
+    assert_eq!(loaded.get_blocks(), voxel_volume.get_blocks());
+    assert_eq!(chunk_file.to_bytes(), bytes);
+}
+
+#[test]
+fn generated_chunks_match_the_whole_level() {
+    let dungeon = LevelKind::Dungeon(DungeonParams::new(24, 24));
+    for level_kind in [terrain(), dungeon].iter() {
+        let (voxel_volume, spawn) = level_kind.build_volume(3);
+        assert_eq!(level_kind.find_spawn(3), spawn);
+        for chunk_coords in voxel_volume.get_chunks() {
+            let blocks = level_kind.generate_chunk(3, &chunk_coords);
+            assert_eq!(blocks.len(), CHUNK_VOLUME);
+            assert!(blocks == voxel_volume.get_chunk(&chunk_coords), "chunk {:?} differs", chunk_coords);
+        }
+    }
+}
+
+#[test]
+fn corrupt_chunks_are_regenerated() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let level_kind = terrain();
+    let (voxel_volume, _) = level_kind.build_volume(5);
+    let mut bytes = ChunkFile::from_volume(&voxel_volume, &block_registry, 5).unwrap().to_bytes();
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0xff;
+
+    let chunk_file = ChunkFile::from_bytes(&bytes).unwrap();
+    let corrupt = chunk_file.get_corrupt();
+    assert_eq!(corrupt.len(), 1);
+    let (loaded, regenerated) = chunk_file.to_volume(&block_registry, |chunk_coords| level_kind.generate_chunk(5, chunk_coords)).unwrap();
+    assert_eq!(regenerated, corrupt);
+    assert_eq!(loaded.get_blocks(), voxel_volume.get_blocks());
+
+    // Cut off partway through the payloads, every chunk past the cut is made again
+    let cut = ChunkFile::from_bytes(&bytes[..bytes.len() - 40]).unwrap();
+    assert!(cut.get_corrupt().len() >= 1);
+    let (loaded, _) = cut.to_volume(&block_registry, |chunk_coords| level_kind.generate_chunk(5, chunk_coords)).unwrap();
+    assert_eq!(loaded.get_blocks(), voxel_volume.get_blocks());
+}
+
+#[test]
+fn bad_headers_are_refused() {
+    let mut blocks_text = String::new();
+    File::open(BLOCKS_PATH).unwrap().read_to_string(&mut blocks_text).unwrap();
+    let block_registry = BlockRegistry::parse(&(blocks_text + "\n[marble]\n")).unwrap();
+    let mut voxel_volume = VoxelVolume::new(BlockCoords::new(0, 0, 0), 20, 4, 20);
+    voxel_volume.fill(&BlockCoords::new(0, 0, 0), &BlockCoords::new(19, 0, 19), block_registry.get_by_name("marble").unwrap());
+    let bytes = ChunkFile::from_volume(&voxel_volume, &block_registry, 0).unwrap().to_bytes();
+
+    match ChunkFile::from_bytes(b"PNG!") {
+        Err(ChunkFileErr::Magic) => (),
+        other => panic!("expected a magic error, got {:?}", other.map(|_| ())),
+    }
+    let mut future = bytes.clone();
+    future[4] = 9;
+    match ChunkFile::from_bytes(&future) {
+        Err(ChunkFileErr::Version(9)) => (),
+        other => panic!("expected a version error, got {:?}", other.map(|_| ())),
+    }
+    match ChunkFile::from_bytes(&bytes[..30]) {
+        Err(ChunkFileErr::Truncated(_)) => (),
+        other => panic!("expected a truncated header, got {:?}", other.map(|_| ())),
+    }
+    let mut flipped = bytes.clone();
+    flipped[8] ^= 1;
+    match ChunkFile::from_bytes(&flipped) {
+        Err(ChunkFileErr::Checksum) => (),
+        other => panic!("expected a checksum error, got {:?}", other.map(|_| ())),
+    }
+
+    let without_marble = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    match ChunkFile::from_bytes(&bytes).unwrap().to_volume(&without_marble, no_regenerate) {
+        Err(ChunkFileErr::UnknownBlock(ref name)) if name == "marble" => (),
+        other => panic!("expected an unknown block, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn level_chunks_are_cached_and_healed() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let level_kind = terrain();
+    let (voxel_volume, spawn) = level_kind.build_volume(2);
+    let path = temp_path("rogue_level_chunks_test.chk");
+
+    let (first, first_spawn, chunk_load) = load_level_chunks(&path, &level_kind, 2, &block_registry).unwrap();
+    match chunk_load {
+        ChunkLoad::Generated => (),
+        other => panic!("expected a generated level, got {}", other),
+    }
+    assert_eq!(first.get_blocks(), voxel_volume.get_blocks());
+    assert_eq!(first_spawn, spawn);
+
+    let (second, second_spawn, chunk_load) = load_level_chunks(&path, &level_kind, 2, &block_registry).unwrap();
+    match chunk_load {
+        ChunkLoad::Loaded(ref regenerated) if regenerated.is_empty() => (),
+        other => panic!("expected a clean load, got {}", other),
+    }
+    assert_eq!(second.get_blocks(), voxel_volume.get_blocks());
+    assert_eq!(second_spawn, spawn);
+
+    match load_level_chunks(&path, &level_kind, 3, &block_registry).unwrap().2 {
+        ChunkLoad::Stale => (),
+        other => panic!("expected a stale file, got {}", other),
+    }
+
+    File::create(&path).unwrap().write_all(b"not a chunk file").unwrap();
+    let (rebuilt, _, chunk_load) = load_level_chunks(&path, &level_kind, 2, &block_registry).unwrap();
+    match chunk_load {
+        ChunkLoad::Rebuilt(ChunkFileErr::Magic) => (),
+        other => panic!("expected a rebuilt level, got {}", other),
+    }
+    assert_eq!(rebuilt.get_blocks(), voxel_volume.get_blocks());
+    assert!(ChunkFile::load(&path).unwrap().get_corrupt().is_empty());
+    let _ = fs::remove_file(&path);
+}
+
+#[test]
+fn other_kinds_of_level_are_stale() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let dungeon = LevelKind::Dungeon(DungeonParams::new(32, 32));
+    let cave = LevelKind::Cave(CaveParams::new(32, 32));
+    let denser = LevelKind::Cave(CaveParams::new(32, 32).with_fill_ratio(0.55));
+    assert_eq!(dungeon.get_fingerprint(), dungeon.clone().get_fingerprint());
+    assert!(dungeon.get_fingerprint() != cave.get_fingerprint());
+    assert!(cave.get_fingerprint() != denser.get_fingerprint());
+    let path = temp_path("rogue_level_kinds_test.chk");
+
+    load_level_chunks(&path, &dungeon, 4, &block_registry).unwrap();
+    let (voxel_volume, spawn, chunk_load) = load_level_chunks(&path, &cave, 4, &block_registry).unwrap();
+    match chunk_load {
+        ChunkLoad::Stale => (),
+        other => panic!("expected a stale file, got {}", other),
+    }
+    let (cave_volume, cave_spawn) = cave.build_volume(4);
+    assert_eq!(voxel_volume.get_blocks(), cave_volume.get_blocks());
+    assert_eq!(spawn, cave_spawn);
+
+    match load_level_chunks(&path, &denser, 4, &block_registry).unwrap().2 {
+        ChunkLoad::Stale => (),
+        other => panic!("expected a stale file, got {}", other),
+    }
+    match load_level_chunks(&path, &denser, 4, &block_registry).unwrap().2 {
+        ChunkLoad::Loaded(ref regenerated) if regenerated.is_empty() => (),
+        other => panic!("expected a clean load, got {}", other),
+    }
+    let _ = fs::remove_file(&path);
+}
@@ -0,0 +1,102 @@
+extern crate dorp;
+extern crate rogue;
+
+use dorp::{IdManager, Id, IdType};
+
+use rogue::{BlockMap, BlockCoords, ChunkCoords, CHUNK_SIZE};
+
+#[test]
+fn chunk_coords_floor_negative_blocks() {
+    assert_eq!(BlockCoords::new(0, 0, 0).get_chunk_coords(), ChunkCoords::new(0, 0, 0));
+    assert_eq!(BlockCoords::new(CHUNK_SIZE - 1, 0, 0).get_chunk_coords(), ChunkCoords::new(0, 0, 0));
+    assert_eq!(BlockCoords::new(CHUNK_SIZE, 0, 0).get_chunk_coords(), ChunkCoords::new(1, 0, 0));
+    assert_eq!(BlockCoords::new(-1, -CHUNK_SIZE, -CHUNK_SIZE - 1).get_chunk_coords(), ChunkCoords::new(-1, -1, -2));
+    assert_eq!(BlockCoords::new(-1, 0, 0).get_local(), (CHUNK_SIZE as usize - 1, 0, 0));
+}
+
+#[test]
+fn lookups_cross_chunks() {
+    let mut manager = IdManager::new();
+    let mut block_map = BlockMap::new();
+    let a = Id::new(&mut manager, IdType::Entity);
+    let b = Id::new(&mut manager, IdType::Entity);
+    block_map.insert(&BlockCoords::new(-1, 0, 0), a);
+    block_map.insert(&BlockCoords::new(100, 40, -70), b);
+    assert_eq!(block_map.get(-1, 0, 0), Some(a));
+    assert_eq!(block_map.get_at(&BlockCoords::new(100, 40, -70)), Some(b));
+    assert_eq!(block_map.get(0, 0, 0), None);
+    assert_eq!(block_map.get_loaded_chunks().len(), 2);
+}
+
+#[test]
+fn unload_drops_lookups() {
+    let mut manager = IdManager::new();
+    let mut block_map = BlockMap::new();
+    let a = Id::new(&mut manager, IdType::Entity);
+    block_map.insert(&BlockCoords::new(3, 3, 3), a);
+    let chunk = block_map.unload_chunk(&ChunkCoords::new(0, 0, 0)).unwrap();
+    assert_eq!(chunk.get_ids(), vec!(a));
+    assert_eq!(block_map.get(3, 3, 3), None);
+    assert!(!block_map.is_chunk_loaded(&ChunkCoords::new(0, 0, 0)));
+
+    block_map.insert_chunk(ChunkCoords::new(0, 0, 0), chunk);
+    assert_eq!(block_map.get(3, 3, 3), Some(a));
+}
+
+#[test]
+fn dirty_flags_reach_adjacent_chunks() {
+    let mut manager = IdManager::new();
+    let mut block_map = BlockMap::new();
+    block_map.insert(&BlockCoords::new(5, 5, 5), Id::new(&mut manager, IdType::Entity));
+    block_map.insert(&BlockCoords::new(CHUNK_SIZE + 5, 5, 5), Id::new(&mut manager, IdType::Entity));
+    for chunk_coords in block_map.get_loaded_chunks() {
+        block_map.clean_chunk(&chunk_coords);
+    }
+    assert!(block_map.get_dirty_chunks().is_empty());
+
+    block_map.insert(&BlockCoords::new(4, 4, 4), Id::new(&mut manager, IdType::Entity));
+    assert_eq!(block_map.get_dirty_chunks(), vec!(ChunkCoords::new(0, 0, 0)));
+    block_map.clean_chunk(&ChunkCoords::new(0, 0, 0));
+
+    block_map.insert(&BlockCoords::new(CHUNK_SIZE - 1, 4, 4), Id::new(&mut manager, IdType::Entity));
+    let mut dirty = block_map.get_dirty_chunks();
+    dirty.sort_by_key(|chunk_coords| chunk_coords.get_x());
+    assert_eq!(dirty, vec!(ChunkCoords::new(0, 0, 0), ChunkCoords::new(1, 0, 0)));
+
+    // A corner block touches the seven chunks around that corner, diagonals included
+    for z in 0..2 {
+        for y in 0..2 {
+            for x in 0..2 {
+                block_map.load_chunk(ChunkCoords::new(x, y, z));
+            }
+        }
+    }
+    for chunk_coords in block_map.get_loaded_chunks() {
+        block_map.clean_chunk(&chunk_coords);
+    }
+    block_map.insert(&BlockCoords::new(CHUNK_SIZE - 1, CHUNK_SIZE - 1, CHUNK_SIZE - 1), Id::new(&mut manager, IdType::Entity));
+    let dirty = block_map.get_dirty_chunks();
+    assert_eq!(dirty.len(), 8);
+    assert!(dirty.contains(&ChunkCoords::new(1, 1, 1)));
+    assert!(dirty.contains(&ChunkCoords::new(1, 1, 0)));
+}
+
+#[test]
+fn neighbors_span_chunk_boundary() {
+    let mut manager = IdManager::new();
+    let mut block_map = BlockMap::new();
+    let mut ids = vec!();
+    for z in -1..2 {
+        for y in -1..2 {
+            for x in -1..2 {
+                let id = Id::new(&mut manager, IdType::Entity);
+                block_map.insert(&BlockCoords::new(x, y, z), id);
+                ids.push(id);
+            }
+        }
+    }
+    assert_eq!(block_map.get_loaded_chunks().len(), 8);
+    let neighbors = block_map.get_neighbors(&BlockCoords::new(0, 0, 0));
+    assert_eq!(neighbors.len(), 26);
+    assert!(!neighbors.contains(&ids[13]));
+}
@@ -0,0 +1,239 @@
+extern crate dorp;
+extern crate rogue;
+
+use dorp::{World, IdManager, Id, IdType, OptErr, Vec2};
+
+use rogue::{
+    RogueData, RogueWorld, BlockCoords, BlockType, BlockRegistry, VoxelVolume, MonsterRegistry, Scheduler, Actor, Combat,
+    DamageType, AttackResult, Player, PlayerAction, Direction, AiAction, AiState, NORMAL_SPEED, SCHEDULER_NAME, BLOCKS_PATH,
+    new_block_map_entity, new_block_registry_entity, new_voxel_volume_entity, new_scheduler_entity, new_player_entity,
+    new_monster_entity, tick_mut_entity, trace_shot, blocks_shot, strike
+};
+
+static MONSTERS: &'static str = "
+[dummy]
+sight = 0
+health = 1
+damage = 0
+
+[archer]
+sight = 8
+range = 6
+accuracy = 0
+";
+
+fn room() -> VoxelVolume {
+    let mut voxel_volume = VoxelVolume::new(BlockCoords::new(0, -2, 0), 16, 8, 16);
+    voxel_volume.fill(&BlockCoords::new(0, -2, 0), &BlockCoords::new(15, -1, 15), BlockType::STONE);
+    voxel_volume
+}
+
+fn new_world(voxel_volume: VoxelVolume, manager: &mut IdManager) -> (RogueWorld, Id) {
+    let mut world = World::<RogueData>::new(Vec2::from([1.0, 1.0]));
+    new_block_registry_entity(BlockRegistry::load(BLOCKS_PATH).unwrap(), manager, &mut world).unwrap();
+    new_voxel_volume_entity(voxel_volume, manager, &mut world).unwrap();
+    new_block_map_entity(manager, &mut world).unwrap();
+    let scheduler_id = new_scheduler_entity(Scheduler::new(), manager, &mut world).unwrap();
+    (world, scheduler_id)
+}
+
+fn spawn(name: &str, block_coords: BlockCoords, manager: &mut IdManager, world: &mut RogueWorld) -> Id {
+    let monster_registry = MonsterRegistry::parse(MONSTERS).unwrap();
+    new_monster_entity(monster_registry.get(name).unwrap(), block_coords, None, 5, manager, world).unwrap()
+}
+
+fn turn(player_action: PlayerAction, player_id: Id, scheduler_id: Id, manager: &mut IdManager, world: &mut RogueWorld) {
+    match world.get_mut_entity_by_id(player_id) {
+        OptErr::Full(entity) => match entity.get_mut_player() {
+            OptErr::Full(player) => player.set_next_action(player_action),
+            _ => panic!("player entity has no player"),
+        },
+        _ => panic!("player entity missing"),
+    }
+    tick_mut_entity(scheduler_id, 0, manager, world).unwrap();
+}
+
+// Keeps attacking until something lands
+fn land_hit(attacker: &mut Combat, defender: &Combat) -> (i32, bool) {
+    for _ in 0..100 {
+        match attacker.roll_attack(defender) {
+            AttackResult::Hit { damage, critical, .. } => return (damage, critical),
+            AttackResult::Miss => (),
+        }
+    }
+    panic!("attack never landed");
+}
+
+#[test]
+fn hit_chance_is_clamped() {
+    assert_eq!(Combat::get_hit_chance(10, 10), 0.5);
+    assert_eq!(Combat::get_hit_chance(10, 0), 0.95);
+    assert_eq!(Combat::get_hit_chance(0, 10), 0.05);
+    assert_eq!(Combat::get_hit_chance(0, 0), 0.95);
+}
+
+#[test]
+fn resistances_and_criticals_scale_damage() {
+    let mut attacker = Combat::new(10, 1).with_damage(10, 10).with_damage_type(DamageType::Fire).with_critical(0.0, 2.0);
+    assert_eq!(land_hit(&mut attacker, &Combat::new(10, 2)), (10, false));
+    assert_eq!(land_hit(&mut attacker, &Combat::new(10, 2).with_resistance(DamageType::Fire, 50)), (5, false));
+    assert_eq!(land_hit(&mut attacker, &Combat::new(10, 2).with_resistance(DamageType::Fire, -50)), (15, false));
+    assert_eq!(land_hit(&mut attacker, &Combat::new(10, 2).with_resistance(DamageType::Cold, 100)), (10, false));
+    assert_eq!(land_hit(&mut attacker, &Combat::new(10, 2).with_resistance(DamageType::Fire, 100)), (0, false));
+
+    let mut attacker = Combat::new(10, 1).with_damage(4, 4).with_critical(1.0, 3.0);
+    assert_eq!(land_hit(&mut attacker, &Combat::new(10, 2)), (12, true));
+    assert_eq!("cold".parse::<DamageType>(), Ok(DamageType::Cold));
+    assert!("acid".parse::<DamageType>().is_err());
+}
+
+#[test]
+fn taking_attacks_tracks_health() {
+    let mut defender = Combat::new(10, 1);
+    let hit = AttackResult::Hit { damage: 6, damage_type: DamageType::Physical, critical: false, killed: false };
+    assert_eq!(defender.take_attack(AttackResult::Miss), AttackResult::Miss);
+    assert_eq!(defender.take_attack(hit), hit);
+    assert_eq!(defender.get_health(), 4);
+    defender.heal(100);
+    assert_eq!(defender.get_health(), 10);
+    defender.take_attack(hit);
+    match defender.take_attack(hit) {
+        AttackResult::Hit { killed, .. } => assert!(killed),
+        AttackResult::Miss => panic!("hit became a miss"),
+    }
+    assert!(defender.is_dead());
+}
+
+#[test]
+fn killed_entities_leave_the_world() {
+    let mut manager = IdManager::new();
+    let (mut world, scheduler_id) = new_world(room(), &mut manager);
+    let id = Id::new(&mut manager, IdType::Entity);
+    world.add_entity(RogueData::new(id).with_actor(Actor::new(NORMAL_SPEED)).with_combat(Combat::new(5, 1))).unwrap();
+    Scheduler::register_entity(id, &mut world).unwrap();
+
+    let mut attacker = Combat::new(10, 2).with_damage(2, 2);
+    let mut strikes = 0;
+    loop {
+        match strike(&mut attacker, id, &mut world).unwrap() {
+            Some(AttackResult::Hit { killed: true, .. }) => break,
+            Some(_) => (),
+            None => panic!("target lost before it died"),
+        }
+        strikes += 1;
+        assert!(strikes < 50, "target never died");
+    }
+    assert!(world.get_entity_by_id(id).is_none());
+    assert_eq!(strike(&mut attacker, id, &mut world).unwrap(), None);
+    tick_mut_entity(scheduler_id, 0, &mut manager, &mut world).unwrap();
+    let scheduler = world.get_entity_by_name(SCHEDULER_NAME).unwrap().get_scheduler().unwrap();
+    assert!(scheduler.get_actors().is_empty());
+}
+
+#[test]
+fn shots_stop_at_walls_and_hit_the_first_actor() {
+    let mut voxel_volume = room();
+    voxel_volume.fill(&BlockCoords::new(6, 0, 0), &BlockCoords::new(6, 1, 15), BlockType::STONE);
+    let mut manager = IdManager::new();
+    let (mut world, _) = new_world(voxel_volume, &mut manager);
+    let from = BlockCoords::new(1, 0, 4);
+
+    let shot = trace_shot(&from, &BlockCoords::new(10, 0, 4), 12, &vec!(), &world).unwrap();
+    assert_eq!(shot.get_end(), Some(BlockCoords::new(5, 0, 4)));
+    assert_eq!(shot.get_target(), None);
+    let shot = trace_shot(&from, &BlockCoords::new(10, 0, 4), 2, &vec!(), &world).unwrap();
+    assert_eq!(shot.get_path().len(), 2);
+
+    let near = spawn("dummy", BlockCoords::new(3, 0, 4), &mut manager, &mut world);
+    let far = spawn("dummy", BlockCoords::new(4, 0, 4), &mut manager, &mut world);
+    let shot = trace_shot(&from, &BlockCoords::new(4, 0, 4), 12, &vec!(far, near), &world).unwrap();
+    assert_eq!(shot.get_target(), Some(near));
+    assert_eq!(shot.get_end(), Some(BlockCoords::new(3, 0, 4)));
+}
+
+#[test]
+fn shots_stop_at_closed_doors() {
+    let mut voxel_volume = room();
+    voxel_volume.fill(&BlockCoords::new(6, 0, 0), &BlockCoords::new(6, 1, 15), BlockType::STONE);
+    voxel_volume.set(&BlockCoords::new(6, 0, 4), BlockType::DOOR);
+    voxel_volume.set(&BlockCoords::new(6, 1, 4), BlockType::AIR);
+    let mut manager = IdManager::new();
+    let (mut world, _) = new_world(voxel_volume, &mut manager);
+    let behind = spawn("dummy", BlockCoords::new(8, 0, 4), &mut manager, &mut world);
+
+    // The player can walk through the door but not shoot through it
+    assert!(Player::can_enter(&BlockCoords::new(6, 0, 4), &world).unwrap());
+    assert!(blocks_shot(&BlockCoords::new(6, 0, 4), &world).unwrap());
+    let shot = trace_shot(&BlockCoords::new(2, 0, 4), &BlockCoords::new(8, 0, 4), 12, &vec!(behind), &world).unwrap();
+    assert_eq!(shot.get_target(), None);
+    assert_eq!(shot.get_end(), Some(BlockCoords::new(5, 0, 4)));
+}
+
+#[test]
+fn bumping_a_monster_attacks_it() {
+    let mut manager = IdManager::new();
+    let (mut world, scheduler_id) = new_world(room(), &mut manager);
+    let player_id = new_player_entity(BlockCoords::new(4, 0, 8), None, &mut manager, &mut world).unwrap();
+    let dummy = spawn("dummy", BlockCoords::new(5, 0, 8), &mut manager, &mut world);
+
+    let mut turns = 0;
+    while world.get_entity_by_id(dummy).is_some() {
+        turn(PlayerAction::Move(Direction::East), player_id, scheduler_id, &mut manager, &mut world);
+        assert_eq!(*world.get_entity_by_id(player_id).unwrap().get_block_coords().unwrap(), BlockCoords::new(4, 0, 8));
+        turns += 1;
+        assert!(turns < 50, "monster never died");
+    }
+    turn(PlayerAction::Move(Direction::East), player_id, scheduler_id, &mut manager, &mut world);
+    assert_eq!(*world.get_entity_by_id(player_id).unwrap().get_block_coords().unwrap(), BlockCoords::new(5, 0, 8));
+}
+
+#[test]
+fn ranged_monsters_shoot_from_a_distance() {
+    let mut manager = IdManager::new();
+    let (mut world, scheduler_id) = new_world(room(), &mut manager);
+    let player_coords = BlockCoords::new(2, 0, 8);
+    let player_id = new_player_entity(player_coords, None, &mut manager, &mut world).unwrap();
+    let archer = spawn("archer", BlockCoords::new(6, 0, 8), &mut manager, &mut world);
+
+    turn(PlayerAction::Wait, player_id, scheduler_id, &mut manager, &mut world);
+    let entity = world.get_entity_by_id(archer).unwrap();
+    assert_eq!(entity.get_ai().unwrap().get_state(), AiState::Hunt);
+    assert_eq!(entity.get_ai().unwrap().get_last_action(), Some(AiAction::Fire(player_coords)));
+    assert_eq!(*entity.get_block_coords().unwrap(), BlockCoords::new(6, 0, 8));
+}
+
+#[test]
+fn the_game_ends_when_the_player_dies() {
+    let mut manager = IdManager::new();
+    let (mut world, scheduler_id) = new_world(room(), &mut manager);
+    let player_id = new_player_entity(BlockCoords::new(4, 0, 8), None, &mut manager, &mut world).unwrap();
+    spawn("dummy", BlockCoords::new(5, 0, 8), &mut manager, &mut world);
+
+    let mut attacker = Combat::new(10, 3).with_damage(1000, 1000);
+    for _ in 0..100 {
+        match strike(&mut attacker, player_id, &mut world).unwrap() {
+            Some(AttackResult::Hit { killed, .. }) => {
+                assert!(killed);
+                break;
+            },
+            Some(AttackResult::Miss) => (),
+            None => panic!("player lost before it died"),
+        }
+    }
+    // The body stays so the level is still drawn from what the player last saw
+    assert!(world.get_entity_by_id(player_id).is_some());
+
+    turn(PlayerAction::Wait, player_id, scheduler_id, &mut manager, &mut world);
+    let turn_count = {
+        let scheduler = world.get_entity_by_name(SCHEDULER_NAME).unwrap().get_scheduler().unwrap();
+        assert!(scheduler.is_game_over());
+        assert!(scheduler.get_acted().is_empty());
+        scheduler.get_turn()
+    };
+    for _ in 0..3 {
+        tick_mut_entity(scheduler_id, 0, &mut manager, &mut world).unwrap();
+    }
+    let scheduler = world.get_entity_by_name(SCHEDULER_NAME).unwrap().get_scheduler().unwrap();
+    assert_eq!(scheduler.get_turn(), turn_count);
+    assert!(scheduler.get_acted().is_empty());
+}
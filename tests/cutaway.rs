@@ -0,0 +1,105 @@
+extern crate rogue;
+
+use rogue::{
+    VoxelVolume, BlockCoords, BlockType, BlockRegistry, ChunkCoords, Atlas, AssetLoader, BillboardView, Cutaway,
+    CutawayMode, BLOCKS_PATH, MANIFEST_PATH, mesh_chunk_textured
+};
+
+fn auto(focus: BlockCoords) -> Cutaway {
+    Cutaway::new(CutawayMode::Auto, BillboardView::isometric()).with_focus(focus)
+}
+
+fn visible(cutaway: &Cutaway, x: i64, y: i64, z: i64) -> bool {
+    cutaway.is_visible(&BlockCoords::new(x, y, z))
+}
+
+#[test]
+fn off_shows_everything() {
+    let cutaway = Cutaway::off();
+    assert!(visible(&cutaway, 0, 0, 0));
+    assert!(visible(&cutaway, -3, 40, 7));
+}
+
+#[test]
+fn slice_shows_layers_up_to_y() {
+    let cutaway = Cutaway::new(CutawayMode::Slice(2), BillboardView::isometric());
+    assert!(visible(&cutaway, 0, -5, 0));
+    assert!(visible(&cutaway, 0, 2, 0));
+    assert!(!visible(&cutaway, 0, 3, 0));
+    assert!(!visible(&cutaway, 9, 30, -9));
+}
+
+#[test]
+fn auto_without_focus_shows_everything() {
+    let cutaway = Cutaway::new(CutawayMode::Auto, BillboardView::isometric());
+    assert!(visible(&cutaway, 0, 5, 0));
+}
+
+#[test]
+fn auto_cuts_above_and_in_front_of_focus() {
+    let cutaway = auto(BlockCoords::new(0, 0, 0));
+    // The isometric camera sits towards -x, +y and +z
+    assert!(!visible(&cutaway, -1, 0, 1));
+    assert!(!visible(&cutaway, -2, 3, 2));
+    assert!(!visible(&cutaway, 0, 2, 0));
+    assert!(!visible(&cutaway, -1, 1, 1));
+}
+
+#[test]
+fn auto_keeps_floor_behind_and_far_blocks() {
+    let cutaway = auto(BlockCoords::new(0, 0, 0));
+    assert!(visible(&cutaway, 0, 0, 0));
+    assert!(visible(&cutaway, -1, -1, 1));
+    assert!(visible(&cutaway, 1, 0, -1));
+    assert!(visible(&cutaway, 3, 0, 3));
+    assert!(visible(&cutaway, -4, 0, 0));
+    // Low blocks far in front sit below the focus on screen
+    assert!(visible(&cutaway, -5, 0, 5));
+}
+
+#[test]
+fn auto_radius_widens_the_cut() {
+    let cutaway = auto(BlockCoords::new(0, 0, 0));
+    assert!(visible(&cutaway, -4, 0, 0));
+    assert!(!visible(&cutaway.with_radius(4.0), -4, 0, 0));
+}
+
+#[test]
+fn auto_follows_focus_layer() {
+    let cutaway = auto(BlockCoords::new(0, 4, 0));
+    assert!(visible(&cutaway, -1, 3, 1));
+    assert!(!visible(&cutaway, -1, 4, 1));
+}
+
+#[test]
+fn changes_chunk_only_when_visible_blocks_change() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let mut voxel_volume = VoxelVolume::new(BlockCoords::new(0, 0, 0), 48, 8, 16);
+    voxel_volume.fill(&BlockCoords::new(0, 0, 0), &BlockCoords::new(47, 0, 15), BlockType::STONE);
+    let chunk_coords = ChunkCoords::new(0, 0, 0);
+    let low = Cutaway::new(CutawayMode::Slice(0), BillboardView::isometric());
+    let high = Cutaway::new(CutawayMode::Slice(5), BillboardView::isometric());
+    assert!(!low.changes_chunk(&high, &voxel_volume, &block_registry, &chunk_coords));
+    assert!(!low.changes_chunk(&low, &voxel_volume, &block_registry, &chunk_coords));
+    let below = Cutaway::new(CutawayMode::Slice(-1), BillboardView::isometric());
+    assert!(low.changes_chunk(&below, &voxel_volume, &block_registry, &chunk_coords));
+    // A focus far away along x leaves this chunk alone
+    assert!(!auto(BlockCoords::new(40, 0, 8)).changes_chunk(&auto(BlockCoords::new(41, 0, 8)), &voxel_volume, &block_registry, &chunk_coords));
+}
+
+#[test]
+fn sliced_wall_shows_its_cut_top() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let mut asset_loader = AssetLoader::load(MANIFEST_PATH).unwrap();
+    let atlas = Atlas::for_blocks(&block_registry, &mut asset_loader).unwrap();
+    let mut voxel_volume = VoxelVolume::new(BlockCoords::new(0, 0, 0), 16, 16, 16);
+    voxel_volume.fill(&BlockCoords::new(2, 0, 2), &BlockCoords::new(2, 3, 2), BlockType::STONE);
+    let chunk_coords = ChunkCoords::new(0, 0, 0);
+
+    let whole = mesh_chunk_textured(&voxel_volume, &block_registry, &atlas, &Cutaway::off(), None, &chunk_coords);
+    assert_eq!(whole.get_quad_count(), 4 * 4 + 2);
+    let sliced = Cutaway::new(CutawayMode::Slice(1), BillboardView::isometric());
+    let cut = mesh_chunk_textured(&voxel_volume, &block_registry, &atlas, &sliced, None, &chunk_coords);
+    assert_eq!(cut.get_quad_count(), 2 * 4 + 2);
+    assert!(cut.get_vertices().iter().all(|vertex| vertex.get_position()[1] <= 2.0));
+}
@@ -0,0 +1,111 @@
+extern crate dorp;
+extern crate rogue;
+
+use std::collections::{HashSet, VecDeque};
+
+use dorp::{World, IdManager, Vec2};
+
+use rogue::{
+    RogueData, DungeonParams, Layout, Tile, VoxelVolume, BlockCoords, BlockType, BlockRegistry, MonsterRegistry, ItemRegistry,
+    AssetLoader, LevelKind, Player, Direction, BLOCKS_PATH, MONSTERS_PATH, ITEMS_PATH, MANIFEST_PATH, generate_dungeon, build_scene
+};
+
+fn dungeon(seed: u64) -> Layout {
+    generate_dungeon(&DungeonParams::new(48, 40), seed)
+}
+
+#[test]
+fn same_seed_same_layout() {
+    assert_eq!(dungeon(42).get_tiles(), dungeon(42).get_tiles());
+    assert_eq!(dungeon(42).get_exit(), dungeon(42).get_exit());
+    assert!(dungeon(1).get_tiles() != dungeon(2).get_tiles());
+}
+
+#[test]
+fn entrance_reaches_exit_and_every_room() {
+    for seed in 0..50 {
+        let layout = dungeon(seed);
+        assert!(layout.get_rooms().len() > 1, "seed {}\n{}", seed, layout);
+        assert!(layout.get_entrance() != layout.get_exit(), "seed {}\n{}", seed, layout);
+        assert!(layout.is_connected(layout.get_entrance(), layout.get_exit()), "seed {}\n{}", seed, layout);
+        for room in layout.get_rooms() {
+            assert!(layout.is_connected(layout.get_entrance(), room.get_center()), "seed {}\n{}", seed, layout);
+        }
+    }
+}
+
+#[test]
+fn border_stays_wall() {
+    let layout = dungeon(9);
+    for x in 0..layout.get_width() {
+        assert_eq!(layout.get(x, 0), Tile::Wall);
+        assert_eq!(layout.get(x, layout.get_depth() - 1), Tile::Wall);
+    }
+    for z in 0..layout.get_depth() {
+        assert_eq!(layout.get(0, z), Tile::Wall);
+        assert_eq!(layout.get(layout.get_width() - 1, z), Tile::Wall);
+    }
+}
+
+#[test]
+fn doors_sit_in_walls() {
+    let layout = dungeon(3);
+    assert!(layout.count(Tile::Door) > 0, "{}", layout);
+    for z in 1..layout.get_depth() - 1 {
+        for x in 1..layout.get_width() - 1 {
+            if layout.get(x, z) == Tile::Door {
+                let walls_x = layout.get(x - 1, z) == Tile::Wall && layout.get(x + 1, z) == Tile::Wall;
+                let walls_z = layout.get(x, z - 1) == Tile::Wall && layout.get(x, z + 1) == Tile::Wall;
+                assert!(walls_x || walls_z, "door at {} {}\n{}", x, z, layout);
+            }
+        }
+    }
+}
+
+#[test]
+fn writes_floor_walls_and_doors() {
+    let layout = dungeon(5);
+    let origin = BlockCoords::new(0, 0, 0);
+    let mut voxel_volume = VoxelVolume::new(origin, layout.get_width(), 3, layout.get_depth());
+    layout.write_to_volume(&mut voxel_volume, &origin, 2, BlockType::DIRT);
+    assert_eq!(voxel_volume.count(BlockType::DOOR), layout.count(Tile::Door));
+    let (x, z) = layout.get_entrance();
+    assert_eq!(voxel_volume.get(&BlockCoords::new(x as i64, 0, z as i64)), BlockType::DIRT);
+    assert_eq!(voxel_volume.get(&BlockCoords::new(x as i64, 1, z as i64)), BlockType::AIR);
+    assert_eq!(voxel_volume.get(&BlockCoords::new(0, 2, 0)), BlockType::DIRT);
+}
+
+#[test]
+fn player_walks_from_spawn_to_exit() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let monster_registry = MonsterRegistry::load(MONSTERS_PATH).unwrap();
+    let item_registry = ItemRegistry::load(ITEMS_PATH).unwrap();
+    let asset_manifest = AssetLoader::load(MANIFEST_PATH).unwrap().get_manifest().clone();
+    for seed in 0..5 {
+        let level_kind = LevelKind::Dungeon(DungeonParams::new(48, 40));
+        let mut manager = IdManager::new();
+        let mut world = World::<RogueData>::new(Vec2::from([1.0, 1.0]));
+        build_scene(&level_kind, &block_registry, &monster_registry, &item_registry, &asset_manifest, seed, &mut manager, &mut world).unwrap();
+        let spawn = level_kind.find_spawn(seed);
+        let (x, z) = level_kind.generate(seed).unwrap().get_exit();
+        let origin = level_kind.get_origin();
+        let exit = BlockCoords::new(origin.get_x() + x as i64, spawn.get_y(), origin.get_z() + z as i64);
+
+        // Breadth first over the steps Player::act takes
+        let mut seen = HashSet::new();
+        let mut open = VecDeque::new();
+        seen.insert(spawn);
+        open.push_back(spawn);
+        while let Some(block_coords) = open.pop_front() {
+            for direction in Direction::get_all().iter() {
+                match Player::get_step(&block_coords, *direction, &world).unwrap() {
+                    Some(next) => if seen.insert(next) {
+                        open.push_back(next);
+                    },
+                    None => (),
+                }
+            }
+        }
+        assert!(seen.contains(&exit), "seed {} exit at {:?} not reached from {:?}", seed, exit, spawn);
+    }
+}
@@ -0,0 +1,249 @@
+extern crate rogue;
+
+use std::collections::{HashSet};
+
+use rogue::{
+    BlockCoords, BlockType, BlockRegistry, VoxelVolume, ChunkCoords, Atlas, AssetLoader, Cutaway, FieldOfView,
+    Sight, BLOCKS_PATH, MANIFEST_PATH, mesh_chunk_textured
+};
+use rogue::fov::{shadowcast, shadowcast_layer, line_of_sight, line_of_sight_volume, trace_line};
+
+// '#' is opaque, '@' the origin; cells that come back unseen are blanked out
+fn fov_2d(map: &str, radius: i64) -> String {
+    let rows: Vec<Vec<char>> = map.lines().map(|line| line.trim()).filter(|line| !line.is_empty()).map(|line| line.chars().collect()).collect();
+    let mut origin = (0, 0);
+    for (z, row) in rows.iter().enumerate() {
+        for (x, cell) in row.iter().enumerate() {
+            if *cell == '@' {
+                origin = (x as i64, z as i64);
+            }
+        }
+    }
+    let is_opaque = |x: i64, z: i64| {
+        if x < 0 || z < 0 || z as usize >= rows.len() || x as usize >= rows[z as usize].len() {
+            return true;
+        }
+        rows[z as usize][x as usize] == '#'
+    };
+    let visible = shadowcast(origin, radius, &is_opaque);
+    render(&rows, &visible)
+}
+
+fn render(rows: &Vec<Vec<char>>, visible: &HashSet<(i64, i64)>) -> String {
+    let mut out = String::new();
+    for (z, row) in rows.iter().enumerate() {
+        for (x, cell) in row.iter().enumerate() {
+            out.push(if visible.contains(&(x as i64, z as i64)) { *cell } else { ' ' });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+// Fixture rows sit between '|' marks so blanks at either end survive
+fn expected(text: &str) -> String {
+    let mut out = String::new();
+    for line in text.lines().map(|line| line.trim()).filter(|line| !line.is_empty()) {
+        out.push_str(line.trim_matches('|'));
+        out.push('\n');
+    }
+    out
+}
+
+#[test]
+fn open_room_is_fully_visible() {
+    let room = "
+        #####
+        #...#
+        #.@.#
+        #...#
+        #####
+    ";
+    assert_eq!(fov_2d(room, 8), expected(room));
+}
+
+#[test]
+fn pillar_casts_a_shadow() {
+    let map = "
+        ...........
+        ...........
+        ...........
+        ..@.#......
+        ...........
+        ...........
+        ...........
+    ";
+    assert_eq!(fov_2d(map, 8), expected("
+        |.......... |
+        |.......... |
+        |........   |
+        |..@.#      |
+        |........   |
+        |.......... |
+        |.......... |
+    "));
+}
+
+#[test]
+fn doorway_shows_part_of_the_next_room() {
+    let map = "
+        ###########
+        #....#....#
+        #.@..#....#
+        #.........#
+        #....#....#
+        ###########
+    ";
+    assert_eq!(fov_2d(map, 10), expected("
+        |######     |
+        |#....#     |
+        |#.@..#     |
+        |#.........#|
+        |#....#....#|
+        |######  ###|
+    "));
+}
+
+#[test]
+fn corridor_bend_is_hidden() {
+    let map = "
+        ###########
+        #@........#
+        #####.#####
+        ....#.#....
+        ....#.#....
+    ";
+    assert_eq!(fov_2d(map, 10), expected("
+        |###########|
+        |#@........#|
+        |#####.#####|
+        |           |
+        |           |
+    "));
+}
+
+#[test]
+fn radius_limits_sight() {
+    let map = "
+        ..........
+        @.........
+        ..........
+    ";
+    assert_eq!(fov_2d(map, 3), expected("
+        |...       |
+        |@...      |
+        |...       |
+    "));
+}
+
+#[test]
+fn trace_line_covers_both_ends() {
+    let line = trace_line(&BlockCoords::new(0, 0, 0), &BlockCoords::new(4, 2, -1));
+    assert_eq!(line.len(), 5);
+    assert_eq!(line[0], BlockCoords::new(0, 0, 0));
+    assert_eq!(line[4], BlockCoords::new(4, 2, -1));
+    for pair in line.windows(2) {
+        assert!((pair[1].get_x() - pair[0].get_x()).abs() <= 1);
+        assert!((pair[1].get_y() - pair[0].get_y()).abs() <= 1);
+        assert!((pair[1].get_z() - pair[0].get_z()).abs() <= 1);
+    }
+}
+
+fn level(block_registry: &BlockRegistry) -> VoxelVolume {
+    // A floor at y = -1, a wall two high along x = 4 and a raised platform beyond it
+    let mut voxel_volume = VoxelVolume::new(BlockCoords::new(-8, -2, -8), 24, 12, 16);
+    voxel_volume.fill(&BlockCoords::new(-8, -2, -8), &BlockCoords::new(15, -1, 7), BlockType::STONE);
+    voxel_volume.fill(&BlockCoords::new(4, 0, -8), &BlockCoords::new(4, 1, 7), BlockType::STONE);
+    voxel_volume.fill(&BlockCoords::new(8, 0, -8), &BlockCoords::new(10, 4, 7), BlockType::STONE);
+    assert!(block_registry.is_opaque(BlockType::STONE));
+    voxel_volume
+}
+
+#[test]
+fn line_of_sight_lights_floor_and_stops_at_walls() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let voxel_volume = level(&block_registry);
+    let visible = line_of_sight_volume(&voxel_volume, &block_registry, &BlockCoords::new(0, 0, 0), 12);
+    assert!(visible.contains(&BlockCoords::new(0, 0, 0)));
+    assert!(visible.contains(&BlockCoords::new(3, -1, 2)));
+    assert!(visible.contains(&BlockCoords::new(4, 0, 0)));
+    assert!(!visible.contains(&BlockCoords::new(-3, -2, 0)));
+    assert!(!visible.contains(&BlockCoords::new(6, -1, 0)));
+    // The platform's top shows over the wall, its face behind the wall doesn't
+    assert!(visible.contains(&BlockCoords::new(8, 4, 0)));
+    assert!(!visible.contains(&BlockCoords::new(8, 0, 0)));
+}
+
+#[test]
+fn line_of_sight_sees_more_from_above() {
+    let is_opaque = |block_coords: &BlockCoords| block_coords.get_y() < 0 || (block_coords.get_x() == 2 && block_coords.get_y() == 0);
+    let low = line_of_sight(&BlockCoords::new(0, 0, 0), 6, &is_opaque);
+    let high = line_of_sight(&BlockCoords::new(0, 3, 0), 6, &is_opaque);
+    assert!(!low.contains(&BlockCoords::new(4, 0, 0)));
+    assert!(high.contains(&BlockCoords::new(4, 0, 0)));
+}
+
+#[test]
+fn one_storey_levels_are_seen_a_layer_at_a_time() {
+    // A floor at y = -1 and a wall two high along x = 4, as dungeons and caves are built
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let mut voxel_volume = VoxelVolume::new(BlockCoords::new(-8, -1, -8), 16, 3, 16);
+    voxel_volume.fill(&BlockCoords::new(-8, -1, -8), &BlockCoords::new(7, -1, 7), BlockType::STONE);
+    voxel_volume.fill(&BlockCoords::new(4, 0, -8), &BlockCoords::new(4, 1, 7), BlockType::STONE);
+    let origin = BlockCoords::new(0, 0, 0);
+    let mut field_of_view = FieldOfView::new(6);
+    field_of_view.update(origin, &voxel_volume, &block_registry);
+
+    let layer = shadowcast_layer(&voxel_volume, &block_registry, &origin, 6);
+    for block_coords in layer.iter() {
+        for y in -1..2 {
+            assert!(field_of_view.is_visible(&BlockCoords::new(block_coords.get_x(), y, block_coords.get_z())));
+        }
+    }
+    assert_eq!(field_of_view.get_visible().len(), layer.len() * 3);
+    // The wall and the floor up to it show, nothing behind it does
+    assert!(field_of_view.is_visible(&BlockCoords::new(4, 1, 0)));
+    assert!(field_of_view.is_visible(&BlockCoords::new(3, -1, 2)));
+    assert!(!field_of_view.is_visible(&BlockCoords::new(5, -1, 0)));
+}
+
+#[test]
+fn field_of_view_remembers_what_left_sight() {
+    let mut field_of_view = FieldOfView::new(4);
+    let first: Vec<BlockCoords> = vec!(BlockCoords::new(0, 0, 0), BlockCoords::new(1, 0, 0));
+    field_of_view.set_visible(BlockCoords::new(0, 0, 0), first.into_iter().collect());
+    assert_eq!(field_of_view.get_sight(&BlockCoords::new(1, 0, 0)), Sight::Visible);
+    assert_eq!(field_of_view.get_sight(&BlockCoords::new(2, 0, 0)), Sight::Unseen);
+
+    let second: Vec<BlockCoords> = vec!(BlockCoords::new(1, 0, 0), BlockCoords::new(2, 0, 0));
+    field_of_view.set_visible(BlockCoords::new(1, 0, 0), second.into_iter().collect());
+    assert_eq!(field_of_view.get_sight(&BlockCoords::new(0, 0, 0)), Sight::Remembered);
+    assert_eq!(field_of_view.get_sight(&BlockCoords::new(1, 0, 0)), Sight::Visible);
+    assert_eq!(field_of_view.get_sight(&BlockCoords::new(2, 0, 0)), Sight::Visible);
+    assert_eq!(field_of_view.get_version(), 2);
+    let changed: HashSet<BlockCoords> = vec!(BlockCoords::new(0, 0, 0), BlockCoords::new(2, 0, 0)).into_iter().collect();
+    assert_eq!(field_of_view.get_changed(), &changed);
+}
+
+#[test]
+fn unseen_blocks_are_not_drawn_and_remembered_ones_are_dimmed() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let mut asset_loader = AssetLoader::load(MANIFEST_PATH).unwrap();
+    let atlas = Atlas::for_blocks(&block_registry, &mut asset_loader).unwrap().with_dimmed(0.5);
+    let mut voxel_volume = VoxelVolume::new(BlockCoords::new(0, 0, 0), 16, 16, 16);
+    voxel_volume.set(&BlockCoords::new(1, 0, 0), BlockType::STONE);
+    voxel_volume.set(&BlockCoords::new(5, 0, 0), BlockType::STONE);
+    let chunk_coords = ChunkCoords::new(0, 0, 0);
+
+    let mut field_of_view = FieldOfView::new(4);
+    field_of_view.set_visible(BlockCoords::new(1, 0, 0), vec!(BlockCoords::new(1, 0, 0)).into_iter().collect());
+    let seen = mesh_chunk_textured(&voxel_volume, &block_registry, &atlas, &Cutaway::off(), Some(&field_of_view), &chunk_coords);
+    assert_eq!(seen.get_quad_count(), 6);
+    assert!(seen.get_vertices().iter().all(|vertex| vertex.get_tex_coord()[1] <= 0.5));
+
+    field_of_view.set_visible(BlockCoords::new(5, 0, 0), vec!(BlockCoords::new(5, 0, 0)).into_iter().collect());
+    let both = mesh_chunk_textured(&voxel_volume, &block_registry, &atlas, &Cutaway::off(), Some(&field_of_view), &chunk_coords);
+    assert_eq!(both.get_quad_count(), 12);
+    let dimmed = both.get_vertices().iter().filter(|vertex| vertex.get_tex_coord()[1] > 0.5).count();
+    assert_eq!(dimmed, 24);
+}
@@ -0,0 +1,119 @@
+extern crate dorp;
+extern crate rogue;
+
+use dorp::{World, IdManager, OptErr, Vec2, EntityData};
+
+use rogue::{
+    RogueData, RogueWorld, BlockCoords, Action, InputMap, InputState, InputEvent, ConfigErr, INPUT_PATH, SCHEDULER_NAME,
+    new_block_map_entity, new_player_entity, new_input_entity, tick_mut_entity
+};
+
+static BINDINGS: &'static str = "
+[bindings]
+move_north = W Up
+zoom_in = WheelUp
+wait = Space
+pick_up = Space
+";
+
+fn pressed(input: &str) -> InputEvent {
+    InputEvent::Pressed(input.to_string())
+}
+
+fn released(input: &str) -> InputEvent {
+    InputEvent::Released(input.to_string())
+}
+
+#[test]
+fn shipped_bindings_cover_every_action() {
+    let input_map = InputMap::load(INPUT_PATH).unwrap();
+    for action in Action::get_all() {
+        assert!(!input_map.get_inputs(action).is_empty(), "{} is unbound", action.get_name());
+    }
+}
+
+#[test]
+fn bindings_map_both_ways() {
+    let input_map = InputMap::parse(BINDINGS).unwrap();
+    assert_eq!(input_map.get_inputs(Action::MoveNorth), &["W".to_string(), "Up".to_string()]);
+    assert_eq!(input_map.get_actions("Up"), vec!(Action::MoveNorth));
+    assert_eq!(input_map.get_actions("Space"), vec!(Action::Wait, Action::PickUp));
+    assert!(input_map.get_actions("X").is_empty());
+    assert!(input_map.get_inputs(Action::Fire).is_empty());
+}
+
+#[test]
+fn unknown_action_reports_its_line() {
+    match InputMap::parse("[bindings]\nmove_north = W\njump = Space\n") {
+        Err(ConfigErr::Value(3, _, ref name)) => assert_eq!(name, "jump"),
+        other => panic!("expected a bad action on line 3, got {:?}", other),
+    }
+    match InputMap::parse("[keys]\nwait = Space\n") {
+        Err(ConfigErr::Syntax(1, _)) => (),
+        other => panic!("expected an unknown section, got {:?}", other),
+    }
+}
+
+#[test]
+fn rebinding_moves_inputs() {
+    let mut input_map = InputMap::parse(BINDINGS).unwrap();
+    input_map.unbind("Space");
+    assert!(input_map.get_actions("Space").is_empty());
+    input_map.bind(Action::Wait, "Period");
+    input_map.bind(Action::Wait, "Period");
+    assert_eq!(input_map.get_inputs(Action::Wait), &["Period".to_string()]);
+    input_map.rebind(Action::MoveNorth, vec!("K".to_string()));
+    assert_eq!(input_map.get_actions("K"), vec!(Action::MoveNorth));
+    assert!(input_map.get_actions("W").is_empty());
+}
+
+#[test]
+fn presses_last_one_frame_and_holds_until_released() {
+    let mut input_state = InputState::new(InputMap::parse(BINDINGS).unwrap());
+    input_state.push_event(pressed("W"));
+    input_state.update();
+    assert_eq!(input_state.get_frame(), 1);
+    assert!(input_state.was_pressed(Action::MoveNorth));
+    assert!(input_state.is_held(Action::MoveNorth));
+
+    // Key repeat doesn't press again
+    input_state.push_event(pressed("W"));
+    input_state.update();
+    assert!(!input_state.was_pressed(Action::MoveNorth));
+    assert!(input_state.is_held(Action::MoveNorth));
+
+    input_state.push_event(released("W"));
+    input_state.push_event(pressed("WheelUp"));
+    input_state.push_event(released("WheelUp"));
+    input_state.update();
+    assert!(!input_state.is_held(Action::MoveNorth));
+    assert_eq!(input_state.get_pressed(), &vec!(Action::ZoomIn));
+    assert!(!input_state.is_held(Action::ZoomIn));
+}
+
+#[test]
+fn player_moves_once_per_frame() {
+    let mut manager = IdManager::new();
+    let mut world: RogueWorld = World::<RogueData>::new(Vec2::from([1.0, 1.0]));
+    new_block_map_entity(&mut manager, &mut world).unwrap();
+    let input_id = new_input_entity(InputState::new(InputMap::parse(BINDINGS).unwrap()), &mut manager, &mut world).unwrap();
+    let player_id = new_player_entity(BlockCoords::new(4, 0, 4), None, &mut manager, &mut world).unwrap();
+    let scheduler_id = world.get_entity_by_name(SCHEDULER_NAME).unwrap().get_id();
+
+    match world.get_mut_entity_by_id(input_id) {
+        OptErr::Full(entity) => match entity.get_mut_input_state() {
+            OptErr::Full(input_state) => {
+                input_state.push_event(pressed("Up"));
+                input_state.update();
+            },
+            _ => panic!("input entity has no input state"),
+        },
+        _ => panic!("input entity missing"),
+    }
+    for tick in 0..3 {
+        tick_mut_entity(player_id, tick, &mut manager, &mut world).unwrap();
+        tick_mut_entity(scheduler_id, tick, &mut manager, &mut world).unwrap();
+    }
+    let block_coords = world.get_entity_by_id(player_id).unwrap().get_block_coords().unwrap();
+    assert_eq!(*block_coords, BlockCoords::new(4, 0, 3));
+}
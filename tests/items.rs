@@ -0,0 +1,291 @@
+extern crate dorp;
+extern crate rogue;
+
+use std::sync::{Arc};
+
+use dorp::{World, IdManager, Id, IdType, OptErr, Vec2};
+
+use rogue::{
+    RogueData, RogueWorld, BlockCoords, BlockType, BlockRegistry, VoxelVolume, ItemRegistry, Item, Inventory, InventoryErr,
+    EquipSlot, Scheduler, PlayerAction, Direction, AttackResult, DamageType, LevelKind, DungeonParams, BLOCKS_PATH,
+    ITEMS_PATH, ITEM_MAP_NAME, SCHEDULER_NAME, new_block_map_entity, new_block_registry_entity, new_voxel_volume_entity,
+    new_scheduler_entity, new_player_entity, new_item_entity, pick_item_spawns, tick_mut_entity
+};
+
+static ITEMS: &'static str = "
+[sword]
+weight = 3
+slot = weapon
+damage = 3 5
+
+[dagger]
+slot = weapon
+damage = 1
+accuracy = 2
+
+[helm]
+weight = 2
+slot = head
+defense = 2
+resist_fire = 20
+
+[potion]
+weight = 0.5
+heal = 8
+
+[anvil]
+weight = 40
+";
+
+fn room() -> VoxelVolume {
+    let mut voxel_volume = VoxelVolume::new(BlockCoords::new(0, -2, 0), 16, 8, 16);
+    voxel_volume.fill(&BlockCoords::new(0, -2, 0), &BlockCoords::new(15, -1, 15), BlockType::STONE);
+    voxel_volume
+}
+
+struct Dungeon {
+    manager: IdManager,
+    world: RogueWorld,
+    scheduler_id: Id,
+    player_id: Id,
+    item_registry: ItemRegistry,
+}
+
+impl Dungeon {
+    fn new(player: BlockCoords) -> Dungeon {
+        let mut manager = IdManager::new();
+        let mut world = World::<RogueData>::new(Vec2::from([1.0, 1.0]));
+        new_block_registry_entity(BlockRegistry::load(BLOCKS_PATH).unwrap(), &mut manager, &mut world).unwrap();
+        new_voxel_volume_entity(room(), &mut manager, &mut world).unwrap();
+        new_block_map_entity(&mut manager, &mut world).unwrap();
+        let scheduler_id = new_scheduler_entity(Scheduler::new(), &mut manager, &mut world).unwrap();
+        let player_id = new_player_entity(player, None, &mut manager, &mut world).unwrap();
+        Dungeon {
+            manager: manager,
+            world: world,
+            scheduler_id: scheduler_id,
+            player_id: player_id,
+            item_registry: ItemRegistry::parse(ITEMS).unwrap(),
+        }
+    }
+
+    fn place(&mut self, name: &str, block_coords: BlockCoords) -> Id {
+        new_item_entity(self.item_registry.get(name).unwrap(), block_coords, None, &mut self.manager, &mut self.world).unwrap()
+    }
+
+    // Whether the player used up its turn on player_action
+    fn turn(&mut self, player_action: PlayerAction) -> bool {
+        match self.world.get_mut_entity_by_id(self.player_id) {
+            OptErr::Full(entity) => match entity.get_mut_player() {
+                OptErr::Full(player) => player.set_next_action(player_action),
+                _ => panic!("player entity has no player"),
+            },
+            _ => panic!("player entity missing"),
+        }
+        tick_mut_entity(self.scheduler_id, 0, &mut self.manager, &mut self.world).unwrap();
+        let scheduler = self.world.get_entity_by_name(SCHEDULER_NAME).unwrap().get_scheduler().unwrap();
+        scheduler.get_acted().contains(&self.player_id)
+    }
+
+    fn player(&self) -> Option<Arc<RogueData>> {
+        self.world.get_entity_by_id(self.player_id)
+    }
+
+    fn carried(&self) -> Vec<Id> {
+        self.player().unwrap().get_inventory().unwrap().get_items().iter().map(|&(id, _)| id).collect()
+    }
+
+    fn on_floor(&self, block_coords: &BlockCoords) -> Vec<Id> {
+        self.world.get_entity_by_name(ITEM_MAP_NAME).unwrap().get_item_map().unwrap().get_at(block_coords)
+    }
+}
+
+#[test]
+fn item_types_come_from_data() {
+    let item_registry = ItemRegistry::load(ITEMS_PATH).unwrap();
+    assert!(item_registry.len() > 0);
+    let item_registry = ItemRegistry::parse(ITEMS).unwrap();
+    let helm = item_registry.get("helm").unwrap();
+    assert_eq!(helm.get_slot(), Some(EquipSlot::Head));
+    assert_eq!(helm.get_bonus().get_defense(), 2);
+    assert_eq!(helm.get_bonus().get_resistance(DamageType::Fire), 20);
+    assert_eq!(item_registry.get("dagger").unwrap().get_weight(), 1.0);
+    assert!(item_registry.get("potion").unwrap().is_usable());
+    assert!(!item_registry.get("anvil").unwrap().is_usable());
+    assert!(ItemRegistry::parse("[ring]\nslot = finger\n").is_err());
+    assert!(ItemRegistry::parse("[rock]\ndefense = 2\n").is_err());
+    assert!(ItemRegistry::parse("[feather]\nweight = -1\n").is_err());
+}
+
+#[test]
+fn inventories_have_slot_and_weight_limits() {
+    let item_registry = ItemRegistry::parse(ITEMS).unwrap();
+    let item = |name: &str| Item::new(item_registry.get(name).unwrap().clone());
+    let mut manager = IdManager::new();
+    let mut id = || Id::new(&mut manager, IdType::Entity);
+    let mut inventory = Inventory::new(2, 5.0);
+    let (sword, helm) = (id(), id());
+    inventory.add(sword, item("sword")).unwrap();
+    inventory.add(helm, item("helm")).unwrap();
+    assert_eq!(inventory.get_weight(), 5.0);
+    match inventory.add(id(), item("potion")) {
+        Err(InventoryErr::Full(_)) => (),
+        other => panic!("expected a full inventory, got {:?}", other),
+    }
+    let mut light = Inventory::new(8, 4.0);
+    light.add(sword, item("sword")).unwrap();
+    match light.add(helm, item("helm")) {
+        Err(InventoryErr::TooHeavy(_)) => (),
+        other => panic!("expected too heavy, got {:?}", other),
+    }
+    match light.equip(sword) {
+        Ok(None) => (),
+        other => panic!("expected the sword to go on, got {:?}", other),
+    }
+    match light.equip(helm) {
+        Err(InventoryErr::NotCarried(_)) => (),
+        other => panic!("expected not carried, got {:?}", other),
+    }
+
+    assert_eq!(inventory.equip(sword).unwrap(), None);
+    assert_eq!(inventory.equip(helm).unwrap(), None);
+    assert_eq!(inventory.get_bonus().get_damage(), (3, 5));
+    assert_eq!(inventory.get_bonus().get_defense(), 2);
+    inventory.remove(helm).unwrap();
+    assert!(!inventory.is_equipped(helm));
+    assert_eq!(inventory.get_bonus().get_defense(), 0);
+    assert_eq!(inventory.unequip(EquipSlot::Weapon), Some(sword));
+    assert_eq!(inventory.unequip(EquipSlot::Weapon), None);
+}
+
+#[test]
+fn items_are_picked_up_and_dropped() {
+    let start = BlockCoords::new(4, 0, 4);
+    let mut dungeon = Dungeon::new(start);
+    let sword = dungeon.place("sword", start);
+    let potion = dungeon.place("potion", start);
+
+    // The first item on the cell comes up first, one per turn
+    assert!(dungeon.turn(PlayerAction::PickUp));
+    assert_eq!(dungeon.carried(), vec!(sword));
+    assert_eq!(dungeon.on_floor(&start), vec!(potion));
+    assert!(dungeon.world.get_entity_by_id(sword).unwrap().get_block_coords().is_none());
+    assert!(dungeon.turn(PlayerAction::PickUp));
+    assert_eq!(dungeon.carried(), vec!(sword, potion));
+    assert!(dungeon.on_floor(&start).is_empty());
+    // Nothing left keeps the turn
+    assert!(!dungeon.turn(PlayerAction::PickUp));
+
+    assert!(dungeon.turn(PlayerAction::Move(Direction::East)));
+    let here = BlockCoords::new(5, 0, 4);
+    assert!(dungeon.turn(PlayerAction::Drop(sword)));
+    assert_eq!(dungeon.carried(), vec!(potion));
+    assert_eq!(dungeon.on_floor(&here), vec!(sword));
+    assert_eq!(*dungeon.world.get_entity_by_id(sword).unwrap().get_block_coords().unwrap(), here);
+    assert!(!dungeon.turn(PlayerAction::Drop(sword)));
+}
+
+#[test]
+fn heavy_items_stay_on_the_floor() {
+    let start = BlockCoords::new(4, 0, 4);
+    let mut dungeon = Dungeon::new(start);
+    let anvil = dungeon.place("anvil", start);
+    assert!(!dungeon.turn(PlayerAction::PickUp));
+    assert!(dungeon.carried().is_empty());
+    // Whatever fits further down the pile is taken instead
+    let dagger = dungeon.place("dagger", start);
+    assert!(dungeon.turn(PlayerAction::PickUp));
+    assert_eq!(dungeon.carried(), vec!(dagger));
+    assert_eq!(dungeon.on_floor(&start), vec!(anvil));
+}
+
+#[test]
+fn equipment_changes_combat_stats() {
+    let start = BlockCoords::new(4, 0, 4);
+    let mut dungeon = Dungeon::new(start);
+    let base = dungeon.player().unwrap().get_combat().unwrap().get_damage();
+    let base_accuracy = dungeon.player().unwrap().get_combat().unwrap().get_accuracy();
+    let sword = dungeon.place("sword", start);
+    let dagger = dungeon.place("dagger", start);
+    let helm = dungeon.place("helm", start);
+    for _ in 0..3 {
+        assert!(dungeon.turn(PlayerAction::PickUp));
+    }
+
+    // Only carried equipment goes on
+    assert!(dungeon.turn(PlayerAction::Equip(sword)));
+    assert!(dungeon.turn(PlayerAction::Equip(helm)));
+    {
+        let player = dungeon.player().unwrap();
+        let combat = player.get_combat().unwrap();
+        assert_eq!(combat.get_damage(), (base.0 + 3, base.1 + 5));
+        assert_eq!(combat.get_resistance(DamageType::Fire), 20);
+        let inventory = player.get_inventory().unwrap();
+        assert_eq!(inventory.get_equipped_in(EquipSlot::Weapon), Some(sword));
+    }
+
+    // A second weapon replaces the first
+    assert!(dungeon.turn(PlayerAction::Equip(dagger)));
+    {
+        let player = dungeon.player().unwrap();
+        let combat = player.get_combat().unwrap();
+        assert_eq!(combat.get_damage(), (base.0 + 1, base.1 + 1));
+        assert_eq!(combat.get_accuracy(), base_accuracy + 2);
+        assert!(!player.get_inventory().unwrap().is_equipped(sword));
+    }
+
+    assert!(dungeon.turn(PlayerAction::Unequip(EquipSlot::Weapon)));
+    assert!(!dungeon.turn(PlayerAction::Unequip(EquipSlot::Weapon)));
+    assert_eq!(dungeon.player().unwrap().get_combat().unwrap().get_damage(), base);
+
+    // Dropping worn equipment takes it off
+    assert!(dungeon.turn(PlayerAction::Drop(helm)));
+    assert_eq!(dungeon.player().unwrap().get_combat().unwrap().get_resistance(DamageType::Fire), 0);
+}
+
+#[test]
+fn potions_heal_and_are_used_up() {
+    let start = BlockCoords::new(4, 0, 4);
+    let mut dungeon = Dungeon::new(start);
+    let potion = dungeon.place("potion", start);
+    let sword = dungeon.place("sword", start);
+    assert!(dungeon.turn(PlayerAction::PickUp));
+    assert!(dungeon.turn(PlayerAction::PickUp));
+
+    let wound = AttackResult::Hit { damage: 12, damage_type: DamageType::Physical, critical: false, killed: false };
+    let max_health = match dungeon.world.get_mut_entity_by_id(dungeon.player_id) {
+        OptErr::Full(entity) => match entity.get_mut_combat() {
+            OptErr::Full(combat) => {
+                combat.take_attack(wound);
+                combat.get_max_health()
+            },
+            _ => panic!("player has no combat"),
+        },
+        _ => panic!("player entity missing"),
+    };
+
+    assert!(!dungeon.turn(PlayerAction::Use(sword)));
+    assert!(dungeon.turn(PlayerAction::Use(potion)));
+    assert_eq!(dungeon.player().unwrap().get_combat().unwrap().get_health(), max_health - 4);
+    assert_eq!(dungeon.carried(), vec!(sword));
+    assert!(dungeon.world.get_entity_by_id(potion).is_none());
+    assert!(!dungeon.turn(PlayerAction::Use(potion)));
+}
+
+#[test]
+fn items_spawn_where_the_player_can_reach() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let item_registry = ItemRegistry::load(ITEMS_PATH).unwrap();
+    let (voxel_volume, spawn) = LevelKind::Dungeon(DungeonParams::new(48, 48)).build_volume(5);
+    let spawns = pick_item_spawns(&voxel_volume, &block_registry, &item_registry, &spawn, 8, 5);
+    assert_eq!(spawns.len(), 8);
+    for &(block_coords, _) in spawns.iter() {
+        assert!(block_coords != spawn);
+        assert!(!block_registry.is_solid(voxel_volume.get(&block_coords)));
+        assert!(block_registry.is_solid(voxel_volume.get(&block_coords.offset(0, -1, 0))));
+    }
+    let again = pick_item_spawns(&voxel_volume, &block_registry, &item_registry, &spawn, 8, 5);
+    let names: Vec<(BlockCoords, &str)> = spawns.iter().map(|&(block_coords, item_def)| (block_coords, item_def.get_name())).collect();
+    let names_again: Vec<(BlockCoords, &str)> = again.iter().map(|&(block_coords, item_def)| (block_coords, item_def.get_name())).collect();
+    assert_eq!(names, names_again);
+}
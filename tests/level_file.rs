@@ -0,0 +1,164 @@
+extern crate dorp;
+extern crate rogue;
+
+use std::collections::{HashSet, VecDeque};
+
+use dorp::{World, IdManager, Vec2};
+
+use rogue::{
+    RogueData, RogueWorld, BlockCoords, BlockType, BlockRegistry, MonsterRegistry, ItemRegistry, AssetLoader, LevelFile, LevelFileErr,
+    BLOCKS_PATH, MONSTERS_PATH, ITEMS_PATH, MANIFEST_PATH, BLOCK_MAP_NAME, PLAYER_NAME, Player, Direction,
+    build_level_file
+};
+
+static CRYPT_PATH: &'static str = "assets/crypt.level";
+
+struct Registries {
+    blocks: BlockRegistry,
+    monsters: MonsterRegistry,
+    items: ItemRegistry,
+}
+
+fn registries() -> Registries {
+    Registries {
+        blocks: BlockRegistry::load(BLOCKS_PATH).unwrap(),
+        monsters: MonsterRegistry::load(MONSTERS_PATH).unwrap(),
+        items: ItemRegistry::load(ITEMS_PATH).unwrap(),
+    }
+}
+
+fn parse(text: &str) -> Result<LevelFile, LevelFileErr> {
+    let registries = registries();
+    LevelFile::parse(text, &registries.blocks, &registries.monsters, &registries.items)
+}
+
+// Error text for a level with the given legend lines and a single layer of rows
+fn error_for(legend: &str, rows: &str) -> String {
+    match parse(&format!("[legend]\n{}\n[layer 0]\n{}", legend, rows)) {
+        Ok(_) => panic!("level should not parse"),
+        Err(err) => err.to_string(),
+    }
+}
+
+static LEGEND: &'static str = "'#' = block stone\n'.' = block air\n'@' = spawn\n'r' = monster rat";
+
+#[test]
+fn layers_become_blocks_and_spawns() {
+    let level_file = parse("
+# comments above the legend are fine
+[legend]
+'#' = block stone   # and after entries
+'.' = block air
+'+' = block door
+'@' = spawn
+'r' = monster rat
+'!' = item healing_potion
+
+[layer -1]
+#####
+#####
+
+[layer 1]
+#.@+#
+#r!.#
+").unwrap();
+    let voxel_volume = level_file.get_voxel_volume();
+    assert_eq!(voxel_volume.get_origin(), BlockCoords::new(0, -1, 0));
+    assert_eq!((voxel_volume.get_width(), voxel_volume.get_height(), voxel_volume.get_depth()), (5, 3, 2));
+    assert_eq!(voxel_volume.get(&BlockCoords::new(4, -1, 1)), BlockType::STONE);
+    // Layer 0 was left out and stays air
+    assert_eq!(voxel_volume.count(BlockType::STONE), 10 + 4);
+    assert_eq!(voxel_volume.get(&BlockCoords::new(3, 1, 0)), BlockType::DOOR);
+    assert_eq!(voxel_volume.get(&BlockCoords::new(2, 1, 0)), BlockType::AIR);
+    assert_eq!(level_file.get_spawn(), BlockCoords::new(2, 1, 0));
+    let monsters: Vec<(BlockCoords, &str)> = level_file.get_monsters().iter().map(|&(block_coords, ref monster_def)| (block_coords, monster_def.get_name())).collect();
+    assert_eq!(monsters, vec!((BlockCoords::new(1, 1, 1), "rat")));
+    let items: Vec<(BlockCoords, &str)> = level_file.get_items().iter().map(|&(block_coords, ref item_def)| (block_coords, item_def.get_name())).collect();
+    assert_eq!(items, vec!((BlockCoords::new(2, 1, 1), "healing_potion")));
+}
+
+#[test]
+fn crypt_builds_into_a_world() {
+    let registries = registries();
+    let level_file = LevelFile::load(CRYPT_PATH, &registries.blocks, &registries.monsters, &registries.items).unwrap();
+    assert_eq!(level_file.get_monsters().len(), 4);
+    assert_eq!(level_file.get_items().len(), 3);
+
+    let asset_manifest = AssetLoader::load(MANIFEST_PATH).unwrap().get_manifest().clone();
+    let mut manager = IdManager::new();
+    let mut world = World::<RogueData>::new(Vec2::from([1.0, 1.0]));
+    let ids = build_level_file(&level_file, &registries.blocks, &asset_manifest, 0, &mut manager, &mut world).unwrap();
+    let monsters = ids.iter().filter(|id| world.get_entity_by_id(**id).map_or(false, |entity| entity.get_ai().is_some())).count();
+    assert_eq!(monsters, 4);
+
+    let player = world.get_entity_by_name(PLAYER_NAME).unwrap();
+    assert_eq!(*player.get_block_coords().unwrap(), level_file.get_spawn());
+    let door = BlockCoords::new(6, 0, 2);
+    let block_map = world.get_entity_by_name(BLOCK_MAP_NAME).unwrap().get_block_map().unwrap();
+    let door_id = block_map.get_at(&door).unwrap();
+    assert_eq!(world.get_entity_by_id(door_id).unwrap().get_block().unwrap().get_block_type(), BlockType::DOOR);
+}
+
+// Every cell the player can walk to from start
+fn walkable_from(start: BlockCoords, world: &RogueWorld) -> HashSet<BlockCoords> {
+    let mut seen = HashSet::new();
+    let mut open = VecDeque::new();
+    seen.insert(start);
+    open.push_back(start);
+    while let Some(block_coords) = open.pop_front() {
+        for direction in Direction::get_all().iter() {
+            match Player::get_step(&block_coords, *direction, world).unwrap() {
+                Some(next) => if seen.insert(next) {
+                    open.push_back(next);
+                },
+                None => (),
+            }
+        }
+    }
+    seen
+}
+
+#[test]
+fn crypt_spawns_are_all_reachable() {
+    let registries = registries();
+    let level_file = LevelFile::load(CRYPT_PATH, &registries.blocks, &registries.monsters, &registries.items).unwrap();
+    let asset_manifest = AssetLoader::load(MANIFEST_PATH).unwrap().get_manifest().clone();
+    let mut manager = IdManager::new();
+    let mut world = World::<RogueData>::new(Vec2::from([1.0, 1.0]));
+    build_level_file(&level_file, &registries.blocks, &asset_manifest, 0, &mut manager, &mut world).unwrap();
+
+    let reachable = walkable_from(level_file.get_spawn(), &world);
+    for &(block_coords, ref monster_def) in level_file.get_monsters().iter() {
+        assert!(reachable.contains(&block_coords), "{} at {:?} can't be reached", monster_def.get_name(), block_coords);
+    }
+    for &(block_coords, ref item_def) in level_file.get_items().iter() {
+        assert!(reachable.contains(&block_coords), "{} at {:?} can't be reached", item_def.get_name(), block_coords);
+    }
+}
+
+#[test]
+fn mistakes_point_at_line_and_column() {
+    assert_eq!(error_for(LEGEND, "#.@x#"), "line 7, column 4: 'x' is not in the legend");
+    assert_eq!(error_for(LEGEND, "#.@.#\n#..#"), "line 8, column 5: row is 4 wide, expected 5");
+    assert_eq!(error_for(LEGEND, "#@.@#"), "line 7, column 4: second spawn, the first is at line 7, column 2");
+    assert_eq!(error_for("'#' = block stone\n'@' = monster orc", "#@"), "line 3, column 15: unknown monster 'orc'");
+    assert_eq!(error_for("'#' = block lava", "#"), "line 2, column 13: unknown block 'lava'");
+    assert_eq!(error_for("'#' = block stone\n  '#' = block air", "#"), "line 3, column 3: '#' is defined twice");
+    assert_eq!(error_for("x = block stone", "#"), "line 2, column 1: Legend entries must look like 'c' = what");
+    assert_eq!(error_for("'#' block stone", "#"), "line 2, column 4: Expected = after the character");
+    assert_eq!(error_for("'#' = wall", "#"), "line 2, column 6: Expected block <name>, spawn, monster <name> or item <name>");
+    assert_eq!(error_for(LEGEND, "#.#"), "Level has no spawn");
+}
+
+#[test]
+fn sections_are_checked() {
+    let message = |text: &str| match parse(text) {
+        Ok(_) => panic!("level should not parse"),
+        Err(err) => err.to_string(),
+    };
+    assert_eq!(message(".....\n[legend]"), "line 1, column 1: Expected [legend] or [layer Y] before this");
+    assert_eq!(message("[legend]\n'@' = spawn\n[layer up]\n@"), "line 3, column 1: Section header must look like [legend] or [layer Y]");
+    assert_eq!(message("[legend]\n'@' = spawn\n[layer 0]\n@\n[layer 0]\n@"), "line 5, column 1: [layer 0] is defined twice");
+    assert_eq!(message("[legend]\n'@' = spawn\n'.' = block air\n[layer 0]\n@\n[layer 1]\n.\n."), "line 6, column 1: layer has 2 rows, expected 1");
+    assert_eq!(message("[layer 0]\n@"), "Level has no [legend] section");
+}
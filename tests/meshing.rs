@@ -0,0 +1,125 @@
+extern crate rogue;
+
+use rogue::{
+    VoxelVolume, BlockCoords, BlockType, BlockRegistry, ChunkCoords, Atlas, AssetLoader, Cutaway, TexturedChunkMesh,
+    CHUNK_SIZE, BLOCKS_PATH, MANIFEST_PATH, mesh_chunk_textured, mesh_volume_textured
+};
+
+fn block_registry() -> BlockRegistry {
+    BlockRegistry::load(BLOCKS_PATH).unwrap()
+}
+
+fn atlas() -> Atlas {
+    Atlas::for_blocks(&block_registry(), &mut AssetLoader::load(MANIFEST_PATH).unwrap()).unwrap()
+}
+
+fn mesh_chunk(voxel_volume: &VoxelVolume, chunk_coords: &ChunkCoords) -> TexturedChunkMesh {
+    mesh_chunk_textured(voxel_volume, &block_registry(), &atlas(), &Cutaway::off(), None, chunk_coords)
+}
+
+fn mesh_volume(voxel_volume: &VoxelVolume) -> Vec<TexturedChunkMesh> {
+    mesh_volume_textured(voxel_volume, &block_registry(), &atlas(), &Cutaway::off(), None)
+}
+
+fn volume_with(cells: &[(i64, i64, i64, BlockType)]) -> VoxelVolume {
+    let mut voxel_volume = VoxelVolume::new(BlockCoords::new(-4, -4, -4), 40, 8, 8);
+    for &(x, y, z, block_type) in cells.iter() {
+        voxel_volume.set(&BlockCoords::new(x, y, z), block_type);
+    }
+    voxel_volume
+}
+
+fn quads(voxel_volume: &VoxelVolume) -> usize {
+    mesh_volume(voxel_volume).iter().map(|chunk_mesh| chunk_mesh.get_quad_count()).sum()
+}
+
+#[test]
+fn single_cube_has_six_faces() {
+    let voxel_volume = volume_with(&[(0, 0, 0, BlockType::DIRT)]);
+    let chunk_mesh = mesh_chunk(&voxel_volume, &ChunkCoords::new(0, 0, 0));
+    assert_eq!(chunk_mesh.get_quad_count(), 6);
+    assert_eq!(chunk_mesh.get_vertices().len(), 24);
+    assert_eq!(chunk_mesh.get_indices().len(), 36);
+}
+
+#[test]
+fn solid_box_draws_only_its_outside() {
+    let mut voxel_volume = volume_with(&[]);
+    voxel_volume.fill(&BlockCoords::new(0, 0, 0), &BlockCoords::new(2, 2, 2), BlockType::DIRT);
+    assert_eq!(quads(&voxel_volume), 6 * 9);
+
+    let mut voxel_volume = volume_with(&[]);
+    voxel_volume.fill(&BlockCoords::new(0, 0, 0), &BlockCoords::new(1, 0, 0), BlockType::DIRT);
+    assert_eq!(quads(&voxel_volume), 10);
+}
+
+#[test]
+fn separate_cubes_draw_every_face() {
+    let voxel_volume = volume_with(&[(0, 0, 0, BlockType::DIRT), (2, 0, 0, BlockType::DIRT)]);
+    assert_eq!(quads(&voxel_volume), 12);
+}
+
+#[test]
+fn l_shape_face_count() {
+    let voxel_volume = volume_with(&[
+        (0, 0, 0, BlockType::DIRT),
+        (1, 0, 0, BlockType::DIRT),
+        (0, 0, 1, BlockType::DIRT),
+    ]);
+    assert_eq!(quads(&voxel_volume), 14);
+}
+
+#[test]
+fn different_block_types_cull_between_them() {
+    let voxel_volume = volume_with(&[(0, 0, 0, BlockType::DIRT), (1, 0, 0, BlockType::CHEST)]);
+    assert_eq!(quads(&voxel_volume), 10);
+}
+
+#[test]
+fn faces_between_chunks_are_culled() {
+    let voxel_volume = volume_with(&[(CHUNK_SIZE - 1, 0, 0, BlockType::DIRT), (CHUNK_SIZE, 0, 0, BlockType::DIRT)]);
+    let chunk_meshes = mesh_volume(&voxel_volume);
+    assert_eq!(chunk_meshes.len(), 2);
+    assert_eq!(chunk_meshes[0].get_quad_count(), 5);
+    assert_eq!(chunk_meshes[1].get_quad_count(), 5);
+}
+
+#[test]
+fn positive_faces_sit_past_the_block() {
+    let voxel_volume = volume_with(&[(0, 0, 0, BlockType::DIRT)]);
+    let chunk_mesh = mesh_chunk(&voxel_volume, &ChunkCoords::new(0, 0, 0));
+    let mut max = [0.0f32; 3];
+    for vertex in chunk_mesh.get_vertices() {
+        for axis in 0..3 {
+            max[axis] = max[axis].max(vertex.get_position()[axis]);
+            assert!(vertex.get_position()[axis] >= 0.0);
+        }
+    }
+    assert_eq!(max, [1.0, 1.0, 1.0]);
+}
+
+#[test]
+fn faces_wind_clockwise_from_outside() {
+    let voxel_volume = volume_with(&[(0, 0, 0, BlockType::DIRT)]);
+    let chunk_mesh = mesh_chunk(&voxel_volume, &ChunkCoords::new(0, 0, 0));
+    let vertices = chunk_mesh.get_vertices();
+    for triangle in chunk_mesh.get_indices().chunks(3) {
+        let a = vertices[triangle[0] as usize].get_position();
+        let b = vertices[triangle[1] as usize].get_position();
+        let c = vertices[triangle[2] as usize].get_position();
+        let ab = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+        let ac = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+        let normal = [ab[1] * ac[2] - ab[2] * ac[1], ab[2] * ac[0] - ab[0] * ac[2], ab[0] * ac[1] - ab[1] * ac[0]];
+        let to_center = [0.5 - a[0], 0.5 - a[1], 0.5 - a[2]];
+        assert!(normal[0] * to_center[0] + normal[1] * to_center[1] + normal[2] * to_center[2] > 0.0);
+    }
+}
+
+#[test]
+fn transparent_blocks_hide_only_their_own_kind() {
+    let water = block_registry().get_by_name("water").unwrap();
+    let voxel_volume = volume_with(&[(0, 0, 0, water), (1, 0, 0, water)]);
+    assert_eq!(quads(&voxel_volume), 10);
+    let voxel_volume = volume_with(&[(0, 0, 0, BlockType::DIRT), (1, 0, 0, water)]);
+    assert_eq!(quads(&voxel_volume), 11);
+}
@@ -0,0 +1,66 @@
+extern crate dorp;
+extern crate rogue;
+
+use dorp::{World, IdManager, Id, Vec2};
+
+use rogue::{RogueData, RogueWorld, BlockCoords, BlockType, new_block_entity, tick_mut_entity};
+
+fn new_world() -> RogueWorld {
+    World::<RogueData>::new(Vec2::from([1.0, 1.0]))
+}
+
+fn fill(width: i64, height: i64, depth: i64, manager: &mut IdManager, world: &mut RogueWorld) -> Vec<Id> {
+    let mut ids = vec!();
+    for z in 0..depth {
+        for y in 0..height {
+            for x in 0..width {
+                ids.push(new_block_entity(BlockType::DIRT, BlockCoords::new(x, y, z), manager, world).unwrap());
+            }
+        }
+    }
+    ids
+}
+
+fn neighbor_count(id: Id, world: &RogueWorld) -> usize {
+    world.get_entity_by_id(id).unwrap().get_block().unwrap().get_neighbors().len()
+}
+
+#[test]
+fn slab_neighbors() {
+    let mut manager = IdManager::new();
+    let mut world = new_world();
+    let ids = fill(3, 1, 3, &mut manager, &mut world);
+    for id in ids.iter() {
+        tick_mut_entity(*id, 0, &mut manager, &mut world).unwrap();
+    }
+    assert_eq!(neighbor_count(ids[0], &world), 3);
+    assert_eq!(neighbor_count(ids[1], &world), 5);
+    assert_eq!(neighbor_count(ids[4], &world), 8);
+}
+
+#[test]
+fn cube_center_has_all_neighbors() {
+    let mut manager = IdManager::new();
+    let mut world = new_world();
+    let ids = fill(3, 3, 3, &mut manager, &mut world);
+    for id in ids.iter() {
+        tick_mut_entity(*id, 0, &mut manager, &mut world).unwrap();
+    }
+    assert_eq!(neighbor_count(ids[13], &world), 26);
+    assert_eq!(neighbor_count(ids[0], &world), 7);
+}
+
+#[test]
+fn new_block_updates_its_neighbors() {
+    let mut manager = IdManager::new();
+    let mut world = new_world();
+    let ids = fill(2, 1, 1, &mut manager, &mut world);
+    tick_mut_entity(ids[0], 0, &mut manager, &mut world).unwrap();
+    assert_eq!(neighbor_count(ids[0], &world), 1);
+    assert_eq!(neighbor_count(ids[1], &world), 1);
+
+    let late = new_block_entity(BlockType::DIRT, BlockCoords::new(2, 0, 0), &mut manager, &mut world).unwrap();
+    tick_mut_entity(late, 1, &mut manager, &mut world).unwrap();
+    assert_eq!(neighbor_count(late, &world), 1);
+    assert_eq!(neighbor_count(ids[1], &world), 2);
+}
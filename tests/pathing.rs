@@ -0,0 +1,138 @@
+extern crate rogue;
+
+use rogue::{BlockCoords, BlockType, BlockRegistry, VoxelVolume, BLOCKS_PATH};
+use rogue::pathing::{NavGrid, DijkstraMap, FLEE_FACTOR, find_path};
+
+// A stone floor at y = -1 under a 16 by 16 room
+fn room() -> VoxelVolume {
+    let mut voxel_volume = VoxelVolume::new(BlockCoords::new(0, -4, 0), 16, 12, 16);
+    voxel_volume.fill(&BlockCoords::new(0, -4, 0), &BlockCoords::new(15, -1, 15), BlockType::STONE);
+    voxel_volume
+}
+
+fn water(block_registry: &BlockRegistry) -> BlockType {
+    block_registry.get_by_name("water").unwrap()
+}
+
+#[test]
+fn path_goes_around_a_wall() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let mut voxel_volume = room();
+    voxel_volume.fill(&BlockCoords::new(5, 0, 0), &BlockCoords::new(5, 1, 10), BlockType::STONE);
+    let nav_grid = NavGrid::new(&voxel_volume, &block_registry);
+    let (path, cost) = find_path(&nav_grid, &BlockCoords::new(2, 0, 2), &BlockCoords::new(8, 0, 2)).unwrap();
+    assert_eq!(path[0], BlockCoords::new(2, 0, 2));
+    assert_eq!(path[path.len() - 1], BlockCoords::new(8, 0, 2));
+    assert!(path.iter().all(|cell| cell.get_y() == 0 && !(cell.get_x() == 5 && cell.get_z() <= 10)));
+    // Down to z = 11, across and back up
+    assert_eq!(cost, 9 + 6 + 9);
+    assert_eq!(path.len() as u32, cost + 1);
+}
+
+#[test]
+fn walkers_climb_one_block_but_not_two() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let mut voxel_volume = room();
+    // A one high step the whole way across, then a two high ledge
+    voxel_volume.fill(&BlockCoords::new(4, 0, 0), &BlockCoords::new(15, 0, 15), BlockType::STONE);
+    voxel_volume.fill(&BlockCoords::new(10, 1, 0), &BlockCoords::new(15, 2, 15), BlockType::STONE);
+    let nav_grid = NavGrid::new(&voxel_volume, &block_registry);
+    let (path, cost) = find_path(&nav_grid, &BlockCoords::new(2, 0, 5), &BlockCoords::new(8, 1, 5)).unwrap();
+    assert_eq!(path.len(), 7);
+    assert_eq!(cost, 6 + 1);
+    assert!(find_path(&nav_grid, &BlockCoords::new(2, 0, 5), &BlockCoords::new(12, 3, 5)).is_none());
+}
+
+#[test]
+fn falls_only_go_one_way() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let mut voxel_volume = room();
+    // A tower three high with a walled top, open on one side
+    voxel_volume.fill(&BlockCoords::new(4, 0, 4), &BlockCoords::new(8, 2, 8), BlockType::STONE);
+    let top = BlockCoords::new(6, 3, 6);
+    let nav_grid = NavGrid::new(&voxel_volume, &block_registry);
+    let (path, _) = find_path(&nav_grid, &top, &BlockCoords::new(10, 0, 6)).unwrap();
+    assert_eq!(path[3], BlockCoords::new(9, 0, 6));
+    assert!(find_path(&nav_grid, &BlockCoords::new(10, 0, 6), &top).is_none());
+    // Too long a drop is not a step
+    let careful = NavGrid::new(&voxel_volume, &block_registry).with_max_fall(2);
+    assert!(find_path(&careful, &top, &BlockCoords::new(10, 0, 6)).is_none());
+}
+
+#[test]
+fn walk_costs_steer_around_water() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let mut voxel_volume = room();
+    // A pool three wide across most of the room
+    voxel_volume.fill(&BlockCoords::new(6, 0, 0), &BlockCoords::new(8, 0, 12), water(&block_registry));
+    let nav_grid = NavGrid::new(&voxel_volume, &block_registry);
+    let (path, cost) = find_path(&nav_grid, &BlockCoords::new(3, 0, 10), &BlockCoords::new(11, 0, 10)).unwrap();
+    assert!(path.iter().all(|cell| voxel_volume.get(cell) == BlockType::AIR));
+    assert_eq!(cost, 8 + 6);
+    // Swimming is cheaper than a long walk around
+    let (path, cost) = find_path(&nav_grid, &BlockCoords::new(3, 0, 2), &BlockCoords::new(11, 0, 2)).unwrap();
+    assert!(path.iter().any(|cell| voxel_volume.get(cell) != BlockType::AIR));
+    assert_eq!(cost, 5 + 3 * 4);
+}
+
+#[test]
+fn doors_block_unless_passable() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let mut voxel_volume = room();
+    voxel_volume.fill(&BlockCoords::new(5, 0, 0), &BlockCoords::new(5, 1, 15), BlockType::STONE);
+    voxel_volume.set(&BlockCoords::new(5, 0, 7), BlockType::DOOR);
+    let from = BlockCoords::new(2, 0, 7);
+    let to = BlockCoords::new(8, 0, 7);
+    assert!(find_path(&NavGrid::new(&voxel_volume, &block_registry), &from, &to).is_none());
+    let opener = NavGrid::new(&voxel_volume, &block_registry).with_passable(BlockType::DOOR);
+    assert_eq!(find_path(&opener, &from, &to).unwrap().1, 6);
+
+    // Dungeon doorways leave air over the door, which is still no step to climb
+    voxel_volume.set(&BlockCoords::new(5, 1, 7), BlockType::AIR);
+    assert!(find_path(&NavGrid::new(&voxel_volume, &block_registry), &from, &to).is_none());
+    let opener = NavGrid::new(&voxel_volume, &block_registry).with_passable(BlockType::DOOR);
+    assert_eq!(find_path(&opener, &from, &to).unwrap().1, 6);
+}
+
+#[test]
+fn chase_map_matches_path_costs_and_leads_to_a_goal() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let mut voxel_volume = room();
+    voxel_volume.fill(&BlockCoords::new(5, 0, 0), &BlockCoords::new(5, 1, 10), BlockType::STONE);
+    voxel_volume.fill(&BlockCoords::new(10, 0, 4), &BlockCoords::new(15, 0, 15), BlockType::STONE);
+    let nav_grid = NavGrid::new(&voxel_volume, &block_registry);
+    let goals = [BlockCoords::new(2, 0, 2), BlockCoords::new(12, 1, 12)];
+    let chase = DijkstraMap::new(&nav_grid, &goals, 100);
+    for start in [BlockCoords::new(8, 0, 2), BlockCoords::new(0, 0, 15), BlockCoords::new(15, 1, 4)].iter() {
+        let best = goals.iter().filter_map(|goal| find_path(&nav_grid, start, goal)).map(|(_, cost)| cost as i64).min();
+        assert_eq!(chase.get(start), best);
+        let mut cell = *start;
+        let mut steps = 0;
+        while let Some(next) = chase.get_downhill(&nav_grid, &cell) {
+            cell = next;
+            steps += 1;
+            assert!(steps < 64);
+        }
+        assert!(goals.contains(&cell));
+    }
+    // The cap keeps far cells out of the map
+    let near = DijkstraMap::new(&nav_grid, &goals[..1], 3);
+    assert_eq!(near.get(&BlockCoords::new(5, 0, 2)), None);
+    assert_eq!(near.get(&BlockCoords::new(4, 0, 3)), Some(3));
+}
+
+#[test]
+fn flee_map_moves_away() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let voxel_volume = room();
+    let nav_grid = NavGrid::new(&voxel_volume, &block_registry);
+    let threat = BlockCoords::new(4, 0, 4);
+    let chase = DijkstraMap::new(&nav_grid, &[threat], 100);
+    let flee = DijkstraMap::flee(&nav_grid, &chase, FLEE_FACTOR);
+    let mut cell = BlockCoords::new(6, 0, 6);
+    for _ in 0..6 {
+        let next = flee.get_downhill(&nav_grid, &cell).unwrap();
+        assert!(chase.get(&next).unwrap() > chase.get(&cell).unwrap());
+        cell = next;
+    }
+}
@@ -0,0 +1,232 @@
+extern crate dorp;
+extern crate rogue;
+
+use dorp::{World, IdManager, Id, OptErr, Vec2, EntityData};
+
+use rogue::{
+    RogueData, RogueWorld, BlockCoords, BlockType, BlockRegistry, VoxelVolume, MonsterRegistry, ItemRegistry, Scheduler,
+    PlayerAction, Direction, EquipSlot, AssetLoader, AssetManifest, LevelKind, DungeonParams, SaveFile, SaveErr, RestoreErr,
+    BLOCKS_PATH, MONSTERS_PATH, MANIFEST_PATH, BLOCK_MAP_NAME, PLAYER_NAME, SCHEDULER_NAME, ITEM_MAP_NAME, build_scene,
+    new_block_registry_entity, new_voxel_volume_entity, new_block_map_entity, new_scheduler_entity, new_player_entity,
+    new_item_entity, save_world, restore_world, tick_mut_entity
+};
+
+static ITEMS: &'static str = "
+[sword]
+weight = 3
+slot = weapon
+damage = 3 5
+accuracy = 1
+
+[helm]
+weight = 2
+slot = head
+defense = 2
+
+[potion]
+weight = 0.5
+heal = 8
+";
+
+struct Game {
+    manager: IdManager,
+    world: RogueWorld,
+    block_registry: BlockRegistry,
+    monster_registry: MonsterRegistry,
+    item_registry: ItemRegistry,
+    asset_manifest: AssetManifest,
+}
+
+impl Game {
+    fn empty(item_registry: ItemRegistry) -> Game {
+        Game {
+            manager: IdManager::new(),
+            world: World::<RogueData>::new(Vec2::from([1.0, 1.0])),
+            block_registry: BlockRegistry::load(BLOCKS_PATH).unwrap(),
+            monster_registry: MonsterRegistry::load(MONSTERS_PATH).unwrap(),
+            item_registry: item_registry,
+            asset_manifest: AssetLoader::load(MANIFEST_PATH).unwrap().get_manifest().clone(),
+        }
+    }
+
+    fn generate(seed: u64) -> Game {
+        let mut game = Game::empty(ItemRegistry::parse(ITEMS).unwrap());
+        let level_kind = LevelKind::Dungeon(DungeonParams::new(32, 32));
+        build_scene(&level_kind, &game.block_registry, &game.monster_registry, &game.item_registry, &game.asset_manifest, seed, &mut game.manager, &mut game.world).unwrap();
+        game
+    }
+
+    fn restore(&self, save_file: &SaveFile) -> Game {
+        let mut game = Game::empty(self.item_registry.clone());
+        restore_world(save_file, &game.block_registry, &game.monster_registry, &game.item_registry, &game.asset_manifest, &mut game.manager, &mut game.world).unwrap();
+        game
+    }
+
+    fn id(&self, name: &str) -> Id {
+        self.world.get_entity_by_name(name).unwrap().get_id()
+    }
+
+    // Gives the player an action and runs the scheduler and the player's sight
+    fn turn(&mut self, player_action: PlayerAction) {
+        let player_id = self.id(PLAYER_NAME);
+        match self.world.get_mut_entity_by_id(player_id) {
+            OptErr::Full(entity) => match entity.get_mut_player() {
+                OptErr::Full(player) => player.set_next_action(player_action),
+                _ => panic!("player entity has no player"),
+            },
+            _ => panic!("player entity missing"),
+        }
+        let scheduler_id = self.id(SCHEDULER_NAME);
+        tick_mut_entity(scheduler_id, 0, &mut self.manager, &mut self.world).unwrap();
+        if self.world.get_entity_by_id(player_id).is_some() {
+            tick_mut_entity(player_id, 0, &mut self.manager, &mut self.world).unwrap();
+        }
+    }
+
+    fn play(&mut self, turns: usize) {
+        let actions = [
+            PlayerAction::Move(Direction::North), PlayerAction::Move(Direction::East), PlayerAction::Wait,
+            PlayerAction::Move(Direction::South), PlayerAction::Move(Direction::West), PlayerAction::PickUp
+        ];
+        for index in 0..turns {
+            if self.world.get_entity_by_name(PLAYER_NAME).is_none() {
+                return;
+            }
+            self.turn(actions[index % actions.len()]);
+        }
+    }
+
+    fn save(&self) -> SaveFile {
+        save_world(&self.world).unwrap()
+    }
+}
+
+fn room() -> VoxelVolume {
+    let mut voxel_volume = VoxelVolume::new(BlockCoords::new(0, -2, 0), 16, 8, 16);
+    voxel_volume.fill(&BlockCoords::new(0, -2, 0), &BlockCoords::new(15, -1, 15), BlockType::STONE);
+    voxel_volume.set(&BlockCoords::new(8, 0, 8), BlockType::DOOR);
+    voxel_volume
+}
+
+#[test]
+fn saves_round_trip_through_text() {
+    let mut game = Game::generate(7);
+    game.play(30);
+    let save_file = game.save();
+    assert!(save_file.get_turn() > 0);
+    assert!(save_file.get_actors().len() > 1);
+    let text = save_file.to_text();
+    let parsed = SaveFile::parse(&text).unwrap();
+    assert_eq!(parsed, save_file);
+    assert_eq!(parsed.to_text(), text);
+}
+
+#[test]
+fn restored_worlds_save_the_same() {
+    let mut game = Game::generate(3);
+    game.play(20);
+    let save_file = game.save();
+    let restored = game.restore(&save_file);
+    let again = restored.save();
+    assert_eq!(again, save_file);
+    assert_eq!(again.to_text(), save_file.to_text());
+}
+
+#[test]
+fn restored_worlds_play_on_the_same() {
+    let mut game = Game::generate(11);
+    game.play(15);
+    let save_file = game.save();
+    let mut restored = game.restore(&save_file);
+    game.play(40);
+    restored.play(40);
+    let save_file = game.save();
+    assert!(save_file.get_turn() > 15);
+    assert_eq!(restored.save(), save_file);
+}
+
+#[test]
+fn restore_rebuilds_the_block_map() {
+    let mut game = Game::empty(ItemRegistry::parse(ITEMS).unwrap());
+    new_block_registry_entity(game.block_registry.clone(), &mut game.manager, &mut game.world).unwrap();
+    new_voxel_volume_entity(room(), &mut game.manager, &mut game.world).unwrap();
+    new_block_map_entity(&mut game.manager, &mut game.world).unwrap();
+    new_scheduler_entity(Scheduler::new(), &mut game.manager, &mut game.world).unwrap();
+    new_player_entity(BlockCoords::new(2, 0, 2), None, &mut game.manager, &mut game.world).unwrap();
+
+    let restored = game.restore(&game.save());
+    let door = BlockCoords::new(8, 0, 8);
+    let block_map = restored.world.get_entity_by_name(BLOCK_MAP_NAME).unwrap().get_block_map().unwrap();
+    let block_id = block_map.get_at(&door).unwrap();
+    let block = restored.world.get_entity_by_id(block_id).unwrap();
+    assert_eq!(block.get_block().unwrap().get_block_type(), BlockType::DOOR);
+    assert_eq!(*block.get_block_coords().unwrap(), door);
+    assert!(block_map.get_at(&BlockCoords::new(2, 0, 2)).is_none());
+}
+
+#[test]
+fn carried_items_and_equipment_come_back() {
+    let mut game = Game::empty(ItemRegistry::parse(ITEMS).unwrap());
+    new_block_registry_entity(game.block_registry.clone(), &mut game.manager, &mut game.world).unwrap();
+    new_voxel_volume_entity(room(), &mut game.manager, &mut game.world).unwrap();
+    new_block_map_entity(&mut game.manager, &mut game.world).unwrap();
+    new_scheduler_entity(Scheduler::new(), &mut game.manager, &mut game.world).unwrap();
+    let player_coords = BlockCoords::new(2, 0, 2);
+    let player_id = new_player_entity(player_coords, None, &mut game.manager, &mut game.world).unwrap();
+    let potion_coords = BlockCoords::new(5, 0, 5);
+    for &(name, block_coords) in [("sword", player_coords), ("helm", player_coords), ("potion", potion_coords), ("potion", potion_coords)].iter() {
+        let item_def = game.item_registry.get(name).unwrap().clone();
+        new_item_entity(&item_def, block_coords, None, &mut game.manager, &mut game.world).unwrap();
+    }
+    game.turn(PlayerAction::PickUp);
+    game.turn(PlayerAction::PickUp);
+    let helm_id = game.world.get_entity_by_id(player_id).unwrap().get_inventory().unwrap().get_items()[1].0;
+    game.turn(PlayerAction::Equip(helm_id));
+    let player = game.world.get_entity_by_id(player_id).unwrap();
+    assert!(player.get_inventory().unwrap().is_equipped(helm_id));
+
+    let restored = game.restore(&game.save());
+    let player = restored.world.get_entity_by_name(PLAYER_NAME).unwrap();
+    let inventory = player.get_inventory().unwrap();
+    let names: Vec<&str> = inventory.get_items().iter().map(|&(_, ref item)| item.get_name()).collect();
+    assert_eq!(names, vec!("sword", "helm"));
+    let helm_id = inventory.get_equipped_in(EquipSlot::Head).unwrap();
+    assert!(restored.world.get_entity_by_id(helm_id).unwrap().get_block_coords().is_none());
+    assert!(inventory.get_equipped_in(EquipSlot::Weapon).is_none());
+    let combat = player.get_combat().unwrap();
+    let original = game.world.get_entity_by_id(player_id).unwrap().get_combat().unwrap();
+    assert_eq!(combat.get_defense(), original.get_defense());
+    assert_eq!(combat.get_bonus(), original.get_bonus());
+
+    let item_map = restored.world.get_entity_by_name(ITEM_MAP_NAME).unwrap().get_item_map().unwrap();
+    assert_eq!(item_map.len(), 2);
+    assert_eq!(item_map.get_at(&potion_coords).len(), 2);
+    assert!(item_map.get_at(&player_coords).is_empty());
+}
+
+#[test]
+fn bad_saves_are_refused() {
+    let game = Game::generate(5);
+    let text = game.save().to_text();
+
+    match SaveFile::parse(&text.replacen("version = 1", "version = 99", 1)) {
+        Err(SaveErr::Version(99)) => (),
+        other => panic!("expected a version error, got {:?}", other.map(|_| ())),
+    }
+    match SaveFile::parse(&text.replacen("[level]", "[levels]", 1)) {
+        Err(SaveErr::Missing("level")) => (),
+        other => panic!("expected a missing level, got {:?}", other.map(|_| ())),
+    }
+    match SaveFile::parse(&text.replacen("blocks = ", "blocks = 0 ", 1)) {
+        Err(SaveErr::Config(_, _)) => (),
+        other => panic!("expected a block count error, got {:?}", other.map(|_| ())),
+    }
+
+    let save_file = SaveFile::parse(&text.replacen("palette = air", "palette = lava", 1)).unwrap();
+    let mut manager = IdManager::new();
+    let mut world = World::<RogueData>::new(Vec2::from([1.0, 1.0]));
+    match restore_world(&save_file, &game.block_registry, &game.monster_registry, &game.item_registry, &game.asset_manifest, &mut manager, &mut world) {
+        Err(RestoreErr::UnknownBlock(ref name)) if name == "lava" => (),
+        other => panic!("expected an unknown block, got {:?}", other.map(|_| ())),
+    }
+}
@@ -0,0 +1,107 @@
+extern crate dorp;
+extern crate rogue;
+
+use dorp::{World, IdManager, Id, IdType, OptErr, Vec2};
+
+use rogue::{
+    RogueData, RogueWorld, BlockCoords, Actor, Scheduler, PlayerAction, NORMAL_SPEED, SCHEDULER_NAME,
+    new_block_map_entity, new_player_entity, new_scheduler_entity, tick_mut_entity
+};
+
+fn new_world(manager: &mut IdManager) -> (RogueWorld, Id) {
+    let mut world = World::<RogueData>::new(Vec2::from([1.0, 1.0]));
+    new_block_map_entity(manager, &mut world).unwrap();
+    let scheduler_id = new_scheduler_entity(Scheduler::new(), manager, &mut world).unwrap();
+    (world, scheduler_id)
+}
+
+fn new_actor(speed: i32, manager: &mut IdManager, world: &mut RogueWorld) -> Id {
+    let id = Id::new(manager, IdType::Entity);
+    world.add_entity(RogueData::new(id).with_actor(Actor::new(speed))).unwrap();
+    Scheduler::register_entity(id, world).unwrap();
+    id
+}
+
+fn tick(scheduler_id: Id, manager: &mut IdManager, world: &mut RogueWorld) -> (Vec<Id>, u64, bool) {
+    tick_mut_entity(scheduler_id, 0, manager, world).unwrap();
+    let scheduler = world.get_entity_by_name(SCHEDULER_NAME).unwrap().get_scheduler().unwrap();
+    (scheduler.get_acted().clone(), scheduler.get_turn(), scheduler.is_waiting())
+}
+
+fn queue(player_action: PlayerAction, player_id: Id, world: &mut RogueWorld) {
+    match world.get_mut_entity_by_id(player_id) {
+        OptErr::Full(entity) => match entity.get_mut_player() {
+            OptErr::Full(player) => player.set_next_action(player_action),
+            _ => panic!("player entity has no player"),
+        },
+        _ => panic!("player entity missing"),
+    }
+}
+
+#[test]
+fn speed_sets_how_often_actors_act() {
+    let mut manager = IdManager::new();
+    let (mut world, scheduler_id) = new_world(&mut manager);
+    let fast = new_actor(NORMAL_SPEED * 2, &mut manager, &mut world);
+    let normal = new_actor(NORMAL_SPEED, &mut manager, &mut world);
+    let slow = new_actor(NORMAL_SPEED / 2, &mut manager, &mut world);
+
+    let (acted, turn, waiting) = tick(scheduler_id, &mut manager, &mut world);
+    assert!(!waiting);
+    let count = |id: Id| acted.iter().filter(|actor| **actor == id).count() as u64;
+    assert_eq!(count(fast), turn * 2);
+    assert_eq!(count(normal), turn);
+    assert_eq!(count(slow), turn / 2);
+    // Most energy first, ties by registration, extra actions after everyone else
+    assert_eq!(&acted[..5], &[fast, normal, fast, fast, normal]);
+}
+
+#[test]
+fn world_waits_for_player_input() {
+    let mut manager = IdManager::new();
+    let (mut world, scheduler_id) = new_world(&mut manager);
+    let monster = new_actor(NORMAL_SPEED, &mut manager, &mut world);
+    let player = new_player_entity(BlockCoords::new(0, 0, 0), None, &mut manager, &mut world).unwrap();
+
+    assert_eq!(tick(scheduler_id, &mut manager, &mut world), (vec!(monster), 1, true));
+    assert_eq!(tick(scheduler_id, &mut manager, &mut world), (vec!(), 1, true));
+
+    queue(PlayerAction::Wait, player, &mut world);
+    assert_eq!(tick(scheduler_id, &mut manager, &mut world), (vec!(player, monster), 2, true));
+}
+
+#[test]
+fn slow_player_lets_monsters_catch_up() {
+    let mut manager = IdManager::new();
+    let (mut world, scheduler_id) = new_world(&mut manager);
+    let monster = new_actor(NORMAL_SPEED, &mut manager, &mut world);
+    let player = new_player_entity(BlockCoords::new(0, 0, 0), None, &mut manager, &mut world).unwrap();
+    match world.get_mut_entity_by_id(player) {
+        OptErr::Full(entity) => match entity.get_mut_actor() {
+            OptErr::Full(actor) => actor.set_speed(NORMAL_SPEED / 2),
+            _ => panic!("player entity has no actor"),
+        },
+        _ => panic!("player entity missing"),
+    }
+
+    assert_eq!(tick(scheduler_id, &mut manager, &mut world), (vec!(monster, monster), 2, true));
+    queue(PlayerAction::Wait, player, &mut world);
+    assert_eq!(tick(scheduler_id, &mut manager, &mut world), (vec!(player, monster, monster), 4, true));
+}
+
+#[test]
+fn removed_actors_drop_out() {
+    let mut manager = IdManager::new();
+    let (mut world, scheduler_id) = new_world(&mut manager);
+    let first = new_actor(NORMAL_SPEED, &mut manager, &mut world);
+    let second = new_actor(NORMAL_SPEED, &mut manager, &mut world);
+    tick(scheduler_id, &mut manager, &mut world);
+    match world.take_entity_by_id(first) {
+        OptErr::Full(_) => (),
+        _ => panic!("actor missing"),
+    }
+    let (acted, _, _) = tick(scheduler_id, &mut manager, &mut world);
+    assert!(acted.iter().all(|id| *id == second));
+    let scheduler = world.get_entity_by_name(SCHEDULER_NAME).unwrap().get_scheduler().unwrap();
+    assert_eq!(scheduler.get_actors(), &vec!(second));
+}
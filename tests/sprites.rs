@@ -0,0 +1,136 @@
+extern crate dorp;
+extern crate image;
+extern crate rogue;
+
+use std::sync::{Arc};
+
+use dorp::{World, IdManager, Vec2, EntityData};
+use image::{RgbaImage};
+
+use rogue::{
+    RogueData, BillboardView, ScreenFacing, Sprite, SpriteSheet, BlockCoords, BlockType, BlockRegistry, VoxelVolume, MonsterRegistry,
+    Scheduler, Direction, UvRect, BLOCKS_PATH, MONSTERS_PATH, new_block_registry_entity, new_voxel_volume_entity,
+    new_block_map_entity, new_scheduler_entity, new_player_entity, new_monster_entity, tick_mut_entity
+};
+
+fn dot(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn close(a: f32, b: f32) -> bool {
+    (a - b).abs() < 1e-5
+}
+
+fn sprite(sheet: SpriteSheet) -> Sprite {
+    Sprite::new("sprite.test", Arc::new(RgbaImage::new(8, 8)), sheet, &mut IdManager::new())
+}
+
+#[test]
+fn isometric_basis_is_orthonormal() {
+    let view = BillboardView::isometric();
+    let (right, up, forward) = (view.get_right(), view.get_up(), view.get_forward());
+    for axis in [right, up, forward].iter() {
+        assert!(close(dot(*axis, *axis), 1.0));
+    }
+    assert!(close(dot(right, up), 0.0));
+    assert!(close(dot(right, forward), 0.0));
+    assert!(close(dot(up, forward), 0.0));
+    // Looking down onto the map
+    assert!(forward[1] < 0.0);
+}
+
+#[test]
+fn quad_stands_upright_in_its_cell_facing_the_camera() {
+    let view = BillboardView::isometric();
+    let corners = view.get_quad(&BlockCoords::new(2, 1, -3), 1.0, 1.0);
+    let height = 1.0 / 45.0f32.to_radians().cos();
+    assert!(close(corners[0][1], 1.0) && close(corners[3][1], 1.0));
+    assert!(close(corners[1][1], 1.0 + height) && close(corners[2][1], 1.0 + height));
+    for corner in corners.iter() {
+        assert!(corner[0] > 2.0 && corner[0] < 3.0);
+        assert!(corner[2] > -3.0 && corner[2] < -2.0);
+    }
+    // Bottom left, top left, top right, bottom right on screen, the same winding as block faces
+    let screen: Vec<(f32, f32)> = corners.iter().map(|corner| (dot(*corner, view.get_right()), dot(*corner, view.get_up()))).collect();
+    assert!(screen[0].0 < screen[3].0 && screen[1].0 < screen[2].0);
+    assert!(screen[1].1 > screen[0].1 && screen[2].1 > screen[3].1);
+}
+
+#[test]
+fn facing_is_relative_to_the_view() {
+    let view = BillboardView::isometric();
+    assert_eq!(view.get_screen_facing(Direction::North), ScreenFacing::AwayLeft);
+    assert_eq!(view.get_screen_facing(Direction::East), ScreenFacing::AwayRight);
+    assert_eq!(view.get_screen_facing(Direction::South), ScreenFacing::TowardRight);
+    assert_eq!(view.get_screen_facing(Direction::West), ScreenFacing::TowardLeft);
+    // A quarter turn of the view turns every facing with it
+    let turned = BillboardView::new(45.0f32.to_radians(), 135.0f32.to_radians());
+    assert_eq!(turned.get_screen_facing(Direction::East), ScreenFacing::AwayLeft);
+}
+
+#[test]
+fn sheet_frames_split_the_image() {
+    let sheet = SpriteSheet::new(4, 4);
+    assert_eq!(sheet.get_frame_rect(1, 2), UvRect::new([0.25, 0.5], [0.5, 0.75]));
+    assert_eq!(sheet.get_frame_rect(5, 0), sheet.get_frame_rect(1, 0));
+    assert_eq!(SpriteSheet::new(1, 1).get_frame_rect(0, 0), UvRect::new([0.0, 0.0], [1.0, 1.0]));
+}
+
+#[test]
+fn frames_advance_and_mark_dirty() {
+    let mut sprite = sprite(SpriteSheet::new(4, 1)).with_ticks_per_frame(2);
+    assert!(sprite.is_dirty());
+    sprite.set_clean();
+    sprite.tick(1);
+    assert_eq!(sprite.get_frame(), 0);
+    assert!(!sprite.is_dirty());
+    sprite.tick(2);
+    assert_eq!(sprite.get_frame(), 1);
+    assert!(sprite.is_dirty());
+    sprite.tick(8);
+    assert_eq!(sprite.get_frame(), 0);
+    sprite.set_clean();
+    sprite.set_block_coords(BlockCoords::new(1, 0, 0));
+    assert!(sprite.is_dirty());
+}
+
+#[test]
+fn facing_picks_row_or_mirrors() {
+    let view = BillboardView::isometric();
+    let mut single = sprite(SpriteSheet::new(1, 1));
+    single.set_facing(Direction::West);
+    assert_eq!(single.get_frame_rect(&view), (UvRect::new([0.0, 0.0], [1.0, 1.0]), true));
+    single.set_facing(Direction::South);
+    assert!(!single.get_frame_rect(&view).1);
+    let mut facings = sprite(SpriteSheet::new(2, 4));
+    facings.set_facing(Direction::North);
+    assert_eq!(facings.get_frame_rect(&view), (UvRect::new([0.0, 0.5], [0.5, 0.75]), false));
+    let mut toward_away = sprite(SpriteSheet::new(1, 2));
+    toward_away.set_facing(Direction::North);
+    assert_eq!(toward_away.get_frame_rect(&view), (UvRect::new([0.0, 0.5], [1.0, 1.0]), true));
+}
+
+#[test]
+fn monsters_out_of_sight_are_not_drawn() {
+    let mut manager = IdManager::new();
+    let mut world = World::<RogueData>::new(Vec2::from([1.0, 1.0]));
+    let mut voxel_volume = VoxelVolume::new(BlockCoords::new(0, -1, 0), 16, 3, 16);
+    voxel_volume.fill(&BlockCoords::new(0, -1, 0), &BlockCoords::new(15, -1, 15), BlockType::STONE);
+    // A wall between the player and the monster
+    voxel_volume.fill(&BlockCoords::new(6, 0, 0), &BlockCoords::new(6, 1, 15), BlockType::STONE);
+    new_block_registry_entity(BlockRegistry::load(BLOCKS_PATH).unwrap(), &mut manager, &mut world).unwrap();
+    new_voxel_volume_entity(voxel_volume, &mut manager, &mut world).unwrap();
+    new_block_map_entity(&mut manager, &mut world).unwrap();
+    new_scheduler_entity(Scheduler::new(), &mut manager, &mut world).unwrap();
+    let player_id = new_player_entity(BlockCoords::new(2, 0, 8), None, &mut manager, &mut world).unwrap();
+    let monster_registry = MonsterRegistry::load(MONSTERS_PATH).unwrap();
+    let rat = monster_registry.get("rat").unwrap();
+    let hidden = new_monster_entity(rat, BlockCoords::new(10, 0, 8), Some(sprite(SpriteSheet::new(1, 1))), 1, &mut manager, &mut world).unwrap();
+    let seen = new_monster_entity(rat, BlockCoords::new(4, 0, 8), Some(sprite(SpriteSheet::new(1, 1))), 2, &mut manager, &mut world).unwrap();
+
+    tick_mut_entity(player_id, 0, &mut manager, &mut world).unwrap();
+    tick_mut_entity(hidden, 0, &mut manager, &mut world).unwrap();
+    tick_mut_entity(seen, 0, &mut manager, &mut world).unwrap();
+    assert!(world.get_entity_by_id(hidden).unwrap().get_renderable().is_none());
+    assert!(world.get_entity_by_id(seen).unwrap().get_renderable().is_some());
+}
@@ -0,0 +1,145 @@
+extern crate dorp;
+extern crate rogue;
+
+use std::collections::{HashSet, VecDeque};
+
+use dorp::{World, IdManager, Vec2};
+
+use rogue::{
+    RogueData, RogueWorld, TerrainParams, TerrainGenerator, Perlin, LevelKind, CaveParams, VoxelVolume, BlockCoords, BlockType,
+    ChunkCoords, BlockRegistry, MonsterRegistry, ItemRegistry, AssetLoader, Player, Direction, CHUNK_SIZE, BLOCKS_PATH,
+    MONSTERS_PATH, ITEMS_PATH, MANIFEST_PATH, build_scene
+};
+
+fn flat_params() -> TerrainParams {
+    TerrainParams::new(32, 32, 32).with_overhangs(0.0, 0.1).with_caves(2.0, 0.1)
+}
+
+#[test]
+fn perlin_is_seeded_and_bounded() {
+    let a = Perlin::new(1);
+    let b = Perlin::new(1);
+    let c = Perlin::new(2);
+    let mut differs = false;
+    for i in 0..200 {
+        let (x, y, z) = (i as f64 * 0.37, i as f64 * 0.11, i as f64 * 0.73);
+        let value = a.noise3(x, y, z);
+        assert!(value >= -1.5 && value <= 1.5);
+        assert_eq!(value, b.noise3(x, y, z));
+        differs = differs || value != c.noise3(x, y, z);
+    }
+    assert!(differs);
+    assert_eq!(a.noise3(3.0, 4.0, 5.0), 0.0);
+}
+
+#[test]
+fn chunks_match_region_fill() {
+    let terrain = TerrainGenerator::new(TerrainParams::new(32, 32, 32), 77);
+    for chunk_coords in [ChunkCoords::new(0, 0, 0), ChunkCoords::new(-1, 0, 2)].iter() {
+        let origin = chunk_coords.get_origin();
+        let size = CHUNK_SIZE as usize;
+        let mut voxel_volume = VoxelVolume::new(origin, size, size, size);
+        terrain.fill_volume(&mut voxel_volume);
+        assert_eq!(&terrain.generate_chunk(chunk_coords), voxel_volume.get_blocks());
+        for index in (0..voxel_volume.get_len()).filter(|index| index % 97 == 0) {
+            let block_coords = voxel_volume.get_coords(index);
+            assert_eq!(terrain.get_block(&block_coords), voxel_volume.get(&block_coords));
+        }
+    }
+}
+
+#[test]
+fn columns_are_stratified() {
+    let terrain = TerrainGenerator::new(flat_params().with_dirt_depth(3), 5);
+    for &(x, z) in [(0, 0), (7, -3), (-20, 15)].iter() {
+        let surface = terrain.find_surface(x, z, -64, 64).unwrap();
+        assert_eq!(terrain.get_block(&BlockCoords::new(x, surface + 1, z)), BlockType::AIR);
+        assert_eq!(terrain.get_block(&BlockCoords::new(x, surface, z)), BlockType::GRASS);
+        assert_eq!(terrain.get_block(&BlockCoords::new(x, surface - 1, z)), BlockType::DIRT);
+        assert_eq!(terrain.get_block(&BlockCoords::new(x, surface - 2, z)), BlockType::DIRT);
+        assert_eq!(terrain.get_block(&BlockCoords::new(x, surface - 3, z)), BlockType::STONE);
+        assert_eq!(terrain.get_block(&BlockCoords::new(x, surface - 10, z)), BlockType::STONE);
+    }
+}
+
+#[test]
+fn caves_and_overhangs_open_up_the_ground() {
+    let solid_flat = TerrainGenerator::new(flat_params(), 9);
+    let carved = TerrainGenerator::new(TerrainParams::new(32, 32, 32).with_caves(0.1, 1.0 / 6.0), 9);
+    let mut buried_air = 0;
+    for z in 0..16 {
+        for x in 0..16 {
+            let surface = solid_flat.get_surface_height(x, z) as i64 - 4;
+            for y in 0..surface {
+                assert!(solid_flat.is_solid(&BlockCoords::new(x, y, z)));
+                if !carved.is_solid(&BlockCoords::new(x, y, z)) {
+                    buried_air += 1;
+                }
+            }
+        }
+    }
+    assert!(buried_air > 0);
+}
+
+#[test]
+fn terrain_level_spawns_on_the_surface() {
+    let (voxel_volume, spawn) = LevelKind::Terrain(TerrainParams::new(32, 32, 32)).build_volume(3);
+    assert_eq!(voxel_volume.get(&spawn), BlockType::AIR);
+    assert!(voxel_volume.get(&spawn.offset(0, -1, 0)) != BlockType::AIR);
+    assert!(voxel_volume.count(BlockType::STONE) > 0);
+    assert!(voxel_volume.count(BlockType::GRASS) > 0);
+}
+
+// Every cell the player can get to from start by stepping, climbing and dropping
+fn walkable_from(start: BlockCoords, world: &RogueWorld) -> HashSet<BlockCoords> {
+    let mut seen = HashSet::new();
+    let mut open = VecDeque::new();
+    seen.insert(start);
+    open.push_back(start);
+    while let Some(block_coords) = open.pop_front() {
+        for direction in Direction::get_all().iter() {
+            match Player::get_step(&block_coords, *direction, world).unwrap() {
+                Some(next) => if seen.insert(next) {
+                    open.push_back(next);
+                },
+                None => (),
+            }
+        }
+    }
+    seen
+}
+
+#[test]
+fn player_can_walk_to_every_monster_and_item() {
+    let block_registry = BlockRegistry::load(BLOCKS_PATH).unwrap();
+    let monster_registry = MonsterRegistry::load(MONSTERS_PATH).unwrap();
+    let item_registry = ItemRegistry::load(ITEMS_PATH).unwrap();
+    let asset_manifest = AssetLoader::load(MANIFEST_PATH).unwrap().get_manifest().clone();
+    let level_kinds = [LevelKind::Terrain(TerrainParams::new(48, 32, 48)), LevelKind::Cave(CaveParams::new(48, 48))];
+    for level_kind in level_kinds.iter() {
+        for seed in 0..3 {
+            let mut manager = IdManager::new();
+            let mut world = World::<RogueData>::new(Vec2::from([1.0, 1.0]));
+            let ids = build_scene(level_kind, &block_registry, &monster_registry, &item_registry, &asset_manifest, seed, &mut manager, &mut world).unwrap();
+            let reachable = walkable_from(level_kind.find_spawn(seed), &world);
+            let (mut monsters, mut items) = (0, 0);
+            for id in ids.iter() {
+                let entity = match world.get_entity_by_id(*id) {
+                    Some(entity) => entity,
+                    None => continue,
+                };
+                if entity.get_ai().is_none() && entity.get_item().is_none() {
+                    continue;
+                }
+                let block_coords = *entity.get_block_coords().unwrap();
+                assert!(reachable.contains(&block_coords), "{:?} seed {}: spawn at {:?} can't be walked to", level_kind, seed, block_coords);
+                if entity.get_ai().is_some() {
+                    monsters += 1;
+                } else {
+                    items += 1;
+                }
+            }
+            assert!(monsters > 0 && items > 0, "{:?} seed {}: {} monsters and {} items spawned", level_kind, seed, monsters, items);
+        }
+    }
+}
@@ -0,0 +1,48 @@
+extern crate rogue;
+
+use rogue::{VoxelVolume, BlockCoords, BlockType, BlockRegistry, BLOCKS_PATH, run_voxel_bench};
+
+fn block_registry() -> BlockRegistry {
+    BlockRegistry::load(BLOCKS_PATH).unwrap()
+}
+
+#[test]
+fn index_round_trips_coords() {
+    let voxel_volume = VoxelVolume::new(BlockCoords::new(-4, -2, 7), 5, 3, 6);
+    assert_eq!(voxel_volume.get_len(), 90);
+    for index in 0..voxel_volume.get_len() {
+        assert_eq!(voxel_volume.get_index(&voxel_volume.get_coords(index)), Some(index));
+    }
+    assert_eq!(voxel_volume.get_coords(0), BlockCoords::new(-4, -2, 7));
+    assert_eq!(voxel_volume.get_max(), BlockCoords::new(0, 0, 12));
+}
+
+#[test]
+fn outside_reads_as_air() {
+    let mut voxel_volume = VoxelVolume::new(BlockCoords::new(0, 0, 0), 2, 2, 2);
+    voxel_volume.fill(&BlockCoords::new(0, 0, 0), &BlockCoords::new(1, 1, 1), BlockType::DIRT);
+    assert_eq!(voxel_volume.get(&BlockCoords::new(1, 1, 1)), BlockType::DIRT);
+    assert_eq!(voxel_volume.get(&BlockCoords::new(2, 1, 1)), BlockType::AIR);
+    assert_eq!(voxel_volume.get(&BlockCoords::new(0, -1, 0)), BlockType::AIR);
+    assert_eq!(voxel_volume.set(&BlockCoords::new(5, 5, 5), BlockType::DIRT), None);
+    assert_eq!(voxel_volume.count(BlockType::DIRT), 8);
+}
+
+#[test]
+fn only_special_blocks_are_listed() {
+    let mut voxel_volume = VoxelVolume::new(BlockCoords::new(0, 0, 0), 4, 1, 4);
+    voxel_volume.fill(&BlockCoords::new(0, 0, 0), &BlockCoords::new(3, 0, 3), BlockType::DIRT);
+    voxel_volume.set(&BlockCoords::new(1, 0, 2), BlockType::DOOR);
+    voxel_volume.set(&BlockCoords::new(3, 0, 0), BlockType::CHEST);
+    assert_eq!(voxel_volume.get_special_blocks(&block_registry()), vec!(
+        (BlockCoords::new(3, 0, 0), BlockType::CHEST),
+        (BlockCoords::new(1, 0, 2), BlockType::DOOR),
+    ));
+}
+
+#[test]
+fn bench_approaches_agree() {
+    let report = run_voxel_bench(&block_registry(), 4, 3, 5, 1).unwrap();
+    assert_eq!(report.get_volume_neighbor_links(), report.get_entity_neighbor_links());
+    assert!(report.get_volume_bytes() < report.get_entity_bytes());
+}